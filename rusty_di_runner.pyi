@@ -6,7 +6,106 @@ enabling concurrent batch processing of documents from URLs or local files with
 round-robin load balancing across multiple Azure resources.
 """
 
-from typing import Any, Optional
+import os
+from typing import Any, Callable, Optional, Union
+
+
+def rate_limiter_groups() -> list[dict[str, Any]]:
+    """
+    List the process-wide rate limiter buckets created via `rate_limiter_group`
+    that are still used by a live client.
+    
+    Returns:
+        One dict per (group, endpoint) with `group`, `endpoint`, `rate`
+        (requests per second), `available_tokens` and `clients` (number of live
+        client credentials sharing the bucket).
+    """
+    ...
+
+
+def tiff_page_count(path: str | os.PathLike[str]) -> int:
+    """
+    Count the pages of a TIFF file without decoding it.
+    
+    Only the file's directory chain is read, so this is cheap even for large
+    files. Classic TIFF and BigTIFF are supported in both byte orders.
+    
+    Args:
+        path: Path to a local .tif/.tiff file.
+    
+    Returns:
+        Number of pages.
+    
+    Raises:
+        IOError: If the file cannot be read.
+        ValueError: If the file is not a TIFF or its structure is invalid or
+            truncated.
+    """
+    ...
+
+
+class AnalysisError(Exception):
+    """
+    A document in a batch could not be analyzed.
+    
+    Attributes:
+        kind (str): Why the document failed, e.g. "unsupported_type"
+    """
+    kind: str
+
+
+class DocumentAnalysisError(AnalysisError):
+    """
+    Base class of the typed errors in batch results. Subclasses say why the
+    document failed, so retry decisions don't depend on the message text.
+    
+    Attributes:
+        kind (str): "rate_limit", "authentication", "invalid_request",
+            "invalid_document", "http", "analysis_failed", "timeout", "cancelled",
+            "panic" (the document's task panicked), "deserialization" (a
+            response or result could not be parsed), "file_read" (a local file
+            could not be read) or "error"
+        status_code (int | None): HTTP status of the failed request
+        error_code (str | None): Service error code, when the service sent one
+        source (str | None): The URL or file path of the failed document
+        operation_location (str | None): The analyze operation's status URL,
+            when the document was submitted
+        elapsed_secs (float | None): Time from the document leaving the queue to
+            its failure, when it was started
+        throttled (int | None): 429 responses the document was retried on
+        endpoint (str | None): Endpoint of the credential that failed it
+    """
+    status_code: Optional[int]
+    error_code: Optional[str]
+    source: Optional[str]
+    operation_location: Optional[str]
+    elapsed_secs: Optional[float]
+    throttled: Optional[int]
+    endpoint: Optional[str]
+
+
+class RateLimitError(DocumentAnalysisError):
+    """The service throttled the document (HTTP 429) and retries were exhausted."""
+
+
+class AuthenticationError(DocumentAnalysisError):
+    """The service rejected the credential (HTTP 401 or 403)."""
+
+
+class InvalidRequestError(DocumentAnalysisError):
+    """
+    The service rejected the request (any other HTTP 4xx), or the document
+    failed local validation and was not submitted (`kind == "invalid_document"`).
+    """
+
+
+class AnalysisFailedError(DocumentAnalysisError):
+    """The analyze operation finished with status 'failed'."""
+
+
+class PollTimeoutError(DocumentAnalysisError):
+    """The document or batch timeout passed before the analysis finished."""
+
 
 class Credentials:
     """
@@ -17,18 +116,56 @@ class Credentials:
     
     Attributes:
         endpoint (str): The Azure Document Intelligence endpoint URL
+        cloud (str): The Azure cloud the resource belongs to
+        aad_scope (str): Entra ID token scope for the cloud
+        aad_authority (str): Entra ID authority host for the cloud
+        is_bearer_token (bool): Whether requests authenticate with an Entra ID
+            bearer token rather than an API key
+        weight (int): Relative share of a batch's documents routed to this
+            credential
     """
     
     endpoint: str
+    cloud: str
+    aad_scope: str
+    aad_authority: str
+    is_bearer_token: bool
+    weight: int
     
-    def __init__(self, endpoint: str, api_key: str) -> None:
+    def __init__(
+        self,
+        endpoint: str,
+        api_key: Optional[str] = None,
+        expand_resource_name: bool = False,
+        cloud: Optional[str] = None,
+        bearer_token: Optional[str] = None,
+        weight: int = 1,
+    ) -> None:
         """
         Initialize credentials for Azure Document Intelligence.
         
         Args:
             endpoint: The Azure Document Intelligence endpoint URL 
-                (e.g., "https://your-resource.cognitiveservices.azure.com")
+                (e.g., "https://your-resource.cognitiveservices.azure.com").
+                A missing scheme defaults to `https://`; trailing slashes and any
+                pasted API path (e.g. `/documentintelligence/...`) are stripped.
             api_key: The API subscription key for authentication
+            expand_resource_name: Accept a bare resource name like `myresource`
+                and append the cloud's default domain. Defaults to False.
+            cloud: One of 'public', 'usgov', 'china' or 'custom'. When given, the
+                endpoint must use that cloud's domain (any host is accepted for
+                'custom'). Defaults to None: public cloud, no suffix validation.
+            bearer_token: Entra ID access token sent as `Authorization: Bearer`
+                instead of an API key, e.g. acquired with `azure.identity` for
+                `aad_scope`. Used as given and not refreshed. Defaults to None.
+            weight: Relative share of documents routed to this credential, e.g.
+                10 for an endpoint with ten times the quota of one with weight 1.
+                Between 1 and 10000. Defaults to 1.
+
+        Raises:
+            ValueError: If the endpoint cannot be normalized, unless exactly one
+                of `api_key` and `bearer_token` is given, or if `weight` is out
+                of range.
         
         Example:
             >>> creds = Credentials(
@@ -38,6 +175,163 @@ class Credentials:
         """
         ...
 
+    @staticmethod
+    def from_env() -> "Credentials":
+        """
+        Create API-key credentials from the `AZURE_DI_ENDPOINT` and
+        `AZURE_DI_API_KEY` environment variables.
+
+        Raises:
+            ValueError: If either variable is unset or empty, or the endpoint
+                cannot be normalized.
+        """
+        ...
+
+    @staticmethod
+    def from_env_prefix(prefix: str) -> "Credentials":
+        """
+        Create API-key credentials from `<prefix>_AZURE_DI_ENDPOINT` and
+        `<prefix>_AZURE_DI_API_KEY`, for setups with several resources.
+
+        Args:
+            prefix: Variable name prefix, e.g. "STAGE" for
+                `STAGE_AZURE_DI_ENDPOINT`. A trailing underscore is optional;
+                an empty prefix behaves like `from_env()`.
+
+        Raises:
+            ValueError: If either variable is unset or empty, or the endpoint
+                cannot be normalized.
+
+        Example:
+            >>> stage = Credentials.from_env_prefix("STAGE")
+        """
+        ...
+
+    @staticmethod
+    def from_bearer_token(
+        endpoint: str,
+        token: str,
+        expand_resource_name: bool = False,
+        cloud: Optional[str] = None,
+    ) -> "Credentials":
+        """
+        Create credentials that authenticate with an Entra ID access token
+        (`Authorization: Bearer`) instead of an API key. The token is used as
+        given and not refreshed.
+
+        Args:
+            endpoint: As for the constructor.
+            token: Access token for the cloud's `aad_scope`.
+            expand_resource_name: As for the constructor. Defaults to False.
+            cloud: As for the constructor. Defaults to None.
+
+        Raises:
+            ValueError: If the endpoint cannot be normalized.
+
+        Example:
+            >>> from azure.identity import DefaultAzureCredential
+            >>> token = DefaultAzureCredential().get_token(
+            ...     "https://cognitiveservices.azure.com/.default"
+            ... ).token
+            >>> creds = Credentials.from_bearer_token(
+            ...     "https://your-resource.cognitiveservices.azure.com", token
+            ... )
+        """
+        ...
+
+    def masked_key(self) -> str:
+        """
+        The API key or bearer token with all but its first and last 4
+        characters replaced by `*`, safe to log when checking which secret is
+        in use. Secrets of 8 characters or fewer are masked entirely.
+        """
+        ...
+
+class BatchProcessingConfig:
+    """
+    Reusable settings for the batch methods, built up with `with_*` calls.
+    
+    Every `with_*` method returns a new config, so a base config can be shared
+    and specialized per call. Settings made on the config take precedence over
+    the corresponding keyword arguments of the batch method it is passed to;
+    unset settings fall back to those arguments.
+    
+    Example:
+        >>> config = (
+        ...     BatchProcessingConfig()
+        ...     .with_features(["ocrHighResolution"])
+        ...     .with_output_format("markdown")
+        ...     .with_max_rps(5)
+        ... )
+        >>> results = client.process_batch_documents_from_urls(
+        ...     "prebuilt-layout", urls, config=config
+        ... )
+    """
+
+    features: Optional[list[str]]
+    output_format: Optional[str]
+    max_rps: Optional[int]
+    timeout_secs: Optional[float]
+    poll_interval: Optional[float]
+    max_retries: Optional[int]
+    initial_backoff_ms: Optional[int]
+
+    def __init__(self) -> None: ...
+
+    def with_features(self, features: list[str]) -> "BatchProcessingConfig":
+        """Analysis features to enable, e.g. ['ocrHighResolution', 'formulas']."""
+        ...
+
+    def with_output_format(self, output_format: str) -> "BatchProcessingConfig":
+        """
+        Output content format, 'text' or 'markdown'.
+
+        Raises:
+            ValueError: If the format is neither 'text' nor 'markdown'.
+        """
+        ...
+
+    def with_max_rps(self, max_rps: int) -> "BatchProcessingConfig":
+        """
+        Maximum concurrent requests per resource.
+
+        Raises:
+            ValueError: If `max_rps` is 0.
+        """
+        ...
+
+    def with_timeout_per_document(self, timeout_secs: float) -> "BatchProcessingConfig":
+        """
+        Timeout for each document, in seconds, covering submission and polling.
+        Must not exceed the client's `batch_timeout_secs`.
+
+        Raises:
+            ValueError: If `timeout_secs` is negative or not finite.
+        """
+        ...
+
+    def with_polling_config(
+        self,
+        poll_interval: Optional[float] = None,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+    ) -> "BatchProcessingConfig":
+        """
+        Status polling and retry settings. Arguments left as None keep the
+        current value.
+
+        Args:
+            poll_interval: Delay before the first status poll, in seconds
+            max_retries: Retries of the analyze request and of each status poll
+                on 429 and 5xx responses
+            initial_backoff_ms: Delay before the first retry without a
+                `Retry-After`, doubling on each further retry
+
+        Raises:
+            ValueError: If `poll_interval` is negative or not finite.
+        """
+        ...
+
 class RustyAnalysisClient:
     """
     A high-performance client for analyzing documents using Azure Document Intelligence API.
@@ -58,7 +352,34 @@ class RustyAnalysisClient:
         - Optional analysis features (OCR, formulas, fonts, etc.)
     """
     
-    def __init__(self, credentials: list[Credentials], enable_logs=False) -> RustyAnalysisClient:
+    def __init__(
+        self,
+        credentials: list[Credentials],
+        enable_logs=False,
+        max_retry_after_secs: float = 30.0,
+        max_result_bytes: Optional[int] = None,
+        on_state_change: Optional[Callable[[dict[str, int]], None]] = None,
+        max_state_change_rate: float = 10.0,
+        routing: Optional[str] = None,
+        routing_min_share: float = 0.2,
+        timeout_secs: Optional[float] = None,
+        batch_timeout_secs: Optional[float] = None,
+        poll_interval: float = 0.5,
+        rate_limiter_group: Optional[str] = None,
+        rate_limiter_rps: float = 15.0,
+        shutdown_grace_secs: float = 5.0,
+        file_read_attempts: int = 3,
+        file_read_retry_delay_secs: float = 0.5,
+        markdown_models: Optional[dict[str, bool]] = None,
+        allow_duplicate_endpoints: bool = False,
+        on_status: Optional[Callable[[str, str, Optional[int], int], None]] = None,
+        on_status_error: str = "log",
+        max_poll_interval: float = 10.0,
+        poll_backoff: float = 1.5,
+        poll_jitter: float = 0.1,
+        auth_failure_threshold: int = 3,
+        auth_probe_interval_secs: float = 60.0,
+    ) -> RustyAnalysisClient:
         """
         Create a new RustyAnalysisClient instance.
         
@@ -69,12 +390,106 @@ class RustyAnalysisClient:
             
             - **enable_logs**: Boolean flag to enable or disable logging output. 
             Defaults to False.
+
+            - **max_retry_after_secs**: Upper bound on the `Retry-After` delay the
+            service may request between status polls. Defaults to 30.
+
+            - **max_result_bytes**: Largest analysis result (in bytes) accepted per
+            document. Larger results fail that document instead of exhausting
+            memory. Defaults to None (no limit).
+
+            - **on_state_change**: Optional callback invoked with the `in_flight()`
+            dict whenever a document moves between the waiting, submitting and
+            polling phases. Called from a background thread.
+
+            - **max_state_change_rate**: Maximum `on_state_change` calls per second.
+            Transitions in between are coalesced into one call with the latest
+            counts once the interval has passed. Defaults to 10.
+
+            - **routing**: How documents are assigned to credentials. 'round_robin'
+            (default) is deterministic and follows each credential's `weight`;
+            'latency' biases towards credentials with lower measured submission
+            latency.
+
+            - **routing_min_share**: In 'latency' mode, the fraction of documents
+            spread over all credentials by weight so none goes stale. Latency
+            shares are scaled by weight too. Defaults to 0.2.
+
+            - **auth_failure_threshold**: Consecutive 401/403 responses after
+            which a credential is skipped, e.g. because its key was rotated.
+            Documents it rejects are retried on another credential. See
+            `credential_status()`. Defaults to 3.
+
+            - **auth_probe_interval_secs**: How long a skipped credential is left
+            alone before one document is sent to it as a probe; it rejoins the
+            rotation once a document is accepted. Defaults to 60.
+
+            - **timeout_secs**: Default per-document timeout, counted from when the
+            document starts submitting. Defaults to None (no timeout).
+
+            - **batch_timeout_secs**: Deadline for a whole batch, including time spent
+            waiting for the rate limiter. Defaults to None (no deadline).
+
+            - **poll_interval**: Default delay in seconds before the first status
+            poll. Without a `Retry-After` from the service, later polls back off
+            exponentially from it. Defaults to 0.5.
+
+            - **max_poll_interval**: Cap on the backoff delay between polls, in
+            seconds. Defaults to 10.
+
+            - **poll_backoff**: Factor the delay grows by after each poll. 1 polls
+            at a fixed `poll_interval`. Defaults to 1.5.
+
+            - **poll_jitter**: Fraction of each delay that is randomized, from 0 to
+            1, so documents submitted together don't poll in lockstep. Defaults
+            to 0.1.
+
+            - **rate_limiter_group**: Name of a process-wide rate limiter. Every
+            client created with the same group shares one token bucket per
+            endpoint, so their combined submissions stay under
+            `rate_limiter_rps`. Defaults to None (no shared limit).
+
+            - **rate_limiter_rps**: Submissions per second allowed per endpoint
+            within the group. The first client to create a bucket sets its rate.
+            Defaults to 15.
+
+            - **shutdown_grace_secs**: How long `close()` (or garbage collection
+            of the client) waits for in-flight documents before cancelling them.
+            Defaults to 5.
+            - **file_read_attempts**: Attempts at reading each local file before
+            the document fails. Interrupted, timed-out and low-level I/O (EIO)
+            errors are retried; missing files and permission errors fail at once.
+            Defaults to 3.
+            - **file_read_retry_delay_secs**: Delay between file read attempts.
+            Defaults to 0.5.
+            - **markdown_models**: Model IDs mapped to whether they support
+            markdown output, overriding the built-in table (which marks
+            'prebuilt-read' and 'prebuilt-invoice' as unsupported). Models not
+            in the table are assumed to support it. Defaults to None.
+            - **allow_duplicate_endpoints**: Keep credentials that share an
+            endpoint, e.g. two keys of one resource used for key rotation. By
+            default only the first credential per endpoint is kept and a warning
+            is logged, so a copy-pasted entry does not double a resource's share
+            of traffic. Defaults to False.
+
+            - **on_status**: Optional callback invoked after every status poll of a
+            document with `(source, status, percent_completed, poll)`. `status` is
+            'notStarted', 'running', 'succeeded' or 'failed'; `percent_completed`
+            is the service's `percentCompleted`, or None when not reported; `poll`
+            counts from 1. Called from a background thread while holding the GIL;
+            the document waits for it to return. Defaults to None.
+
+            - **on_status_error**: What happens when `on_status` raises. 'log'
+            (default) logs a warning and keeps polling; 'raise' fails that
+            document with the callback's error.
         
         Returns:
             A new client instance configured with the provided credentials.
         
         Raises:
-            ValueError: If credentials list is empty or None.
+            ValueError: If credentials list is empty or None, or an option is out
+                of range.
+            RuntimeError: If the async runtime cannot be started.
         
         Example:
             >>> from rusty_di_runner import RustyAnalysisClient, Credentials
@@ -100,13 +515,177 @@ class RustyAnalysisClient:
         """
         ...
     
+    def info(self) -> dict[str, Any]:
+        """
+        Describe this client's configuration and counters for bug reports.
+        
+        Safe to call at any time, including after `close()`. Secrets are never
+        included; credentials are listed by endpoint, cloud and auth kind only. The result
+        contains only plain types, so it can be passed to `json.dumps` and pasted
+        into an issue verbatim.
+        
+        Returns:
+            Dict with `version`, `api_version`, `credentials`, `runtime` (flavor
+            and worker threads), `rate_limits`, `routing`, `retry_policy`,
+            `timeouts`, `logging`, `markdown_models`, `metrics` (per-credential
+            request counts and latencies) and `in_flight`.
+        
+        Example:
+            >>> import json
+            >>> print(json.dumps(client.info(), indent=2))
+        """
+        ...
+    
+    def close(self, grace_period_secs: Optional[float] = None) -> None:
+        """
+        Shut the client down, letting in-flight documents finish first.
+        
+        Running batches stop submitting new documents immediately; those report
+        a "closed" error. Documents already submitted get up to the grace period
+        to complete; any still in flight after it are aborted and reported with
+        `kind == "cancelled"` before the runtime is shut down. Any later call on
+        the client raises RuntimeError. Calling `close()` again is a no-op.
+        
+        Args:
+            grace_period_secs: Overrides the client's `shutdown_grace_secs`.
+                Defaults to None.
+        """
+        ...
+    
+    def in_flight(self) -> dict[str, int]:
+        """
+        Snapshot of how many documents are currently in each phase.
+
+        Returns:
+            A dict with `waiting` (queued on the rate limiter), `submitting`
+            (uploading to the analyze endpoint) and `polling` (awaiting results)
+            counts. All counts are zero once every batch has completed.
+        """
+        ...
+
+    def get_runtime_stats(self) -> dict[str, int]:
+        """
+        Internal counters for this client's runtime and batches.
+
+        Returns:
+            A dict with `tokio_threads` (runtime worker threads),
+            `active_permits_url` and `active_permits_file` (documents from URLs
+            and from uploaded files or bytes currently held by a worker),
+            `total_requests` (documents taken by a worker so far) and
+            `total_errors` (how many of them failed or timed out).
+        """
+        ...
+
+    def credential_stats(self) -> list[dict[str, Any]]:
+        """
+        Per-credential routing statistics accumulated over the client's lifetime.
+
+        Returns:
+            One dict per credential, in order, with `endpoint`, `requests`
+            (documents routed to it) and `latency_ms` (EWMA of the submission
+            latency, or None before the first measurement). A submission that
+            fails or times out counts as taking at least 10 seconds.
+        """
+        ...
+
+    def credential_status(self) -> list[dict[str, Any]]:
+        """
+        Authentication health of each credential.
+
+        A credential that gets `auth_failure_threshold` 401/403 responses in a
+        row is skipped by routing until a probe document, sent to it every
+        `auth_probe_interval_secs`, is accepted. Documents only fail with an
+        authentication error when every credential rejects them or is skipped.
+
+        Returns:
+            One dict per credential, in order, with `endpoint`, `healthy` (False
+            while skipped), `consecutive_failures` (401/403 responses since a
+            document was last accepted) and `last_error` (message of the latest
+            401/403, or None).
+        """
+        ...
+
+    def generate_report(
+        self,
+        results: Union[list[Any], dict[str, Any]],
+        format: str = "markdown",
+        path: Optional[Union[str, os.PathLike[str]]] = None,
+        redact_urls: bool = True,
+    ) -> Optional[str]:
+        """
+        Summarize a finished batch as a Markdown or HTML report.
+
+        Covers the document count, success rate, pages analyzed, throttling
+        events, failures grouped by category (`AnalysisError.kind`, or the
+        exception type) with each failed document's source and message, the
+        slowest documents, and how the batch's documents were spread over the
+        credentials. Everything is taken from the results' `elapsed_secs`,
+        `throttled` and `endpoint` attributes, so the report describes that
+        batch only.
+
+        Args:
+            results: Results returned by one of the batch methods.
+            format: 'markdown' (default) or 'html'.
+            path: Write the report to this file instead of returning it.
+            redact_urls: Drop the query string of every URL, since it often
+                carries a SAS token. The report states whether this was on.
+                Defaults to True.
+
+        Returns:
+            The report, or None when it was written to `path`.
+
+        Raises:
+            ValueError: If `format` is not recognized.
+            TypeError: If an item is not an AnalyzeResult, CheckpointedResult or
+                Exception.
+            IOError: If `path` cannot be written.
+        """
+        ...
+
+    def validate_credentials(
+        self,
+        timeout_secs: float = 10.0,
+        raise_on_failure: bool = False,
+    ) -> list[dict[str, Any]]:
+        """
+        Check that every credential can reach its endpoint and authenticate.
+
+        Issues one lightweight authenticated GET per credential, in parallel.
+        API keys are never logged or included in the report.
+
+        Args:
+            timeout_secs: Per-request timeout in seconds. Defaults to 10.
+            raise_on_failure: Raise RuntimeError if any credential is not `ok`
+                instead of only reporting it. Defaults to False.
+
+        Returns:
+            One dict per credential, in order, with `endpoint`, `status`
+            (`ok`, `auth_failed`, `throttled`, `unreachable` or `error`),
+            `http_status` and `detail`.
+        """
+        ...
+
     def process_batch_documents_from_urls(
         self,
         model_id: str,
-        document_urls: list[str],
+        document_urls: list[str | tuple[str, int]],
         features: Optional[list[str]] = None,
         output_format: Optional[str] = None,
-        max_rps: int = 15
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        priorities: Optional[list[int]] = None,
+        checkpoint_path: Optional[str | os.PathLike[str]] = None,
+        output_dir: Optional[str | os.PathLike[str]] = None,
+        select: Optional[list[str]] = None,
+        strict: bool = False,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+        pages: Optional[str] = None,
+        locale: Optional[str] = None,
+        query_fields: Optional[list[str]] = None,
+        config: Optional[BatchProcessingConfig] = None,
+        retry_policy: Optional[dict[str, int]] = None,
     ) -> list[dict[str, Any] | Exception]:
         """
         Process multiple documents from URLs concurrently with round-robin distribution.
@@ -146,13 +725,88 @@ class RustyAnalysisClient:
             
             max_rps: Maximum requests per second per resource to control rate limiting.
                 Helps respect Azure API quotas. Defaults to 15.
+
+            timeout_secs: Per-document timeout for this call, overriding the
+                client's `timeout_secs`. Must not exceed the client's
+                `batch_timeout_secs`. Defaults to None (use the client setting).
+
+            poll_interval: Initial delay between status polls for this call, overriding the
+                client's `poll_interval`. Defaults to None (use the client setting).
+
+            priorities: Submission priority per input, aligned with `document_urls`.
+                Higher values are submitted first; equal values keep their input
+                order. Items of `document_urls` may instead be `(url, priority)`
+                tuples. Results are always returned in input order.
+                Defaults to None (submit in input order).
+
+            checkpoint_path: JSON Lines file to which every finished document is
+                appended (source hash, status, result path or error). On a later
+                call with the same checkpoint, model, features and query options,
+                inputs that already succeeded are skipped and only the remainder
+                is submitted. A line truncated by a crash is ignored.
+                Defaults to None.
+
+            output_dir: Used with `checkpoint_path`. Each successful result is
+                saved here as JSON so skipped inputs can be returned in full;
+                without it, skipped inputs are returned as `CheckpointedResult`
+                markers. Defaults to None.
+
+            select: Result sections to keep, any of 'content', 'pages',
+                'paragraphs', 'tables', 'languages', 'documents',
+                'key_value_pairs', 'styles', 'figures' and 'sections'.
+                Other sections are skipped without being parsed and are left out of
+                `to_dict()`. Unknown names raise ValueError before anything is
+                submitted. Defaults to None (keep everything).
+
+            strict: Raise ValueError when markdown output is requested from a
+                model known not to support it. Otherwise a warning is logged and
+                `outputContentFormat` is omitted from the request. Defaults to False.
+
+            max_retries: Retries of the analyze request and of each status poll
+                on 429, 500, 502, 503 and 504 responses. Retries wait for
+                `Retry-After` when sent, else back off exponentially with jitter.
+                A retrying document keeps its concurrency slot, so retries never
+                exceed `max_rps`. Defaults to None (3).
+
+            initial_backoff_ms: Delay before the first retry without a
+                `Retry-After`, doubling on each further retry. Defaults to None (500).
+
+            pages: Pages to analyze, as 1-based page numbers and ranges such as
+                '1-3,5'. Sent as the `pages` query parameter; malformed values
+                such as '1--3' raise ValueError before anything is submitted.
+                Defaults to None (all pages).
+
+            locale: Language hint for OCR, e.g. 'en-US' or 'de', sent as the
+                `locale` query parameter. Defaults to None (the service detects
+                the language).
+
+            query_fields: Extra fields to extract, e.g. ['Name', 'Address'],
+                sent as the `queryFields` query parameter. The `queryFields`
+                feature is added when missing, and the extracted values come
+                back as fields of `documents`. Defaults to None.
+
+            config: Settings built with `BatchProcessingConfig`. Those set on
+                it override the matching arguments above. Defaults to None.
+
+            retry_policy: HTTP retry settings for this call, with any of
+                'max_retries', 'initial_backoff_ms' and 'max_backoff_ms' (cap on
+                the backoff delay; 30000 by default). Each may be given here or
+                as an argument, not both. Settings on `config` come first, then
+                these, then the client's. Unknown keys and negative values raise
+                ValueError. Defaults to None.
         
         Returns:
             List of results where each item corresponds to the input document at the
             same index. Each item is either:
                 - dict: Successfully analyzed document result containing the full
                   analyzeResult with 'content', 'pages', 'tables', etc.
-                - Exception: Error object if processing failed for that document
+                - DocumentAnalysisError: Error object if processing failed for that
+                  document; the subclass (RateLimitError, AuthenticationError, ...)
+                  says why
+            Every item, success or failure, has `source` (the input) and `index`
+            (its position in the input list) attributes, and `elapsed_secs`,
+            `throttled` (429 responses retried) and `endpoint` (the credential
+            used) for the documents that were started.
         
         Example:
             >>> urls = [
@@ -193,13 +847,82 @@ class RustyAnalysisClient:
         """
         ...
     
+    def process_batch_documents_from_urls_with_entities(
+        self,
+        model_id: str,
+        document_urls: list[str | tuple[str, int]],
+        language_endpoint: str,
+        language_api_key: str,
+        features: Optional[list[str]] = None,
+        output_format: Optional[str] = None,
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        language: Optional[str] = None,
+    ) -> list[Any]:
+        """
+        Process documents from URLs, then find named entities in each result's
+        content with Azure AI Language entity recognition.
+        
+        Documents are analyzed as by `process_batch_documents_from_urls`. The
+        content of every successful result is sent to the Language resource in
+        chunks within the service's size limit, and the entities are stored on
+        the result; read them with `AnalyzeResult.get_entities()`.
+        
+        Args:
+            model_id: The Document Intelligence model ID
+            document_urls: Document URLs, optionally as `(url, priority)` tuples
+            language_endpoint: Endpoint of the Azure AI Language resource
+            language_api_key: API key of the Language resource
+            features: Analysis features. Defaults to None.
+            output_format: 'text' (default) or 'markdown'
+            max_rps: Maximum concurrent requests per resource, also used for
+                the Language requests. Defaults to 15.
+            timeout_secs: Per-document analysis timeout. Defaults to None.
+            language: Document language for entity recognition, e.g. 'en'.
+                Defaults to None (the service default).
+        
+        Returns:
+            AnalyzeResult objects with `entities` set, or DocumentAnalysisError
+            instances for documents whose analysis or entity recognition
+            failed, in input order.
+        
+        Raises:
+            ValueError: If the Language endpoint is invalid.
+        
+        Example:
+            >>> results = client.process_batch_documents_from_urls_with_entities(
+            ...     "prebuilt-layout",
+            ...     urls,
+            ...     language_endpoint="https://my-language.cognitiveservices.azure.com",
+            ...     language_api_key="language-key",
+            ... )
+            >>> for entity in results[0].get_entities():
+            ...     print(entity["category"], entity["text"])
+        """
+        ...
+
     def process_batch_documents_from_file_paths(
         self,
         model_id: str,
-        file_paths: list[str],
+        file_paths: list[str | os.PathLike[str] | tuple[str | os.PathLike[str], int]],
         features: Optional[list[str]] = None,
         output_format: Optional[str] = None,
-        max_rps: int = 15
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        priorities: Optional[list[int]] = None,
+        checkpoint_path: Optional[str | os.PathLike[str]] = None,
+        output_dir: Optional[str | os.PathLike[str]] = None,
+        select: Optional[list[str]] = None,
+        strict: bool = False,
+        skip_unsupported: bool = False,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+        pages: Optional[str] = None,
+        locale: Optional[str] = None,
+        query_fields: Optional[list[str]] = None,
+        config: Optional[BatchProcessingConfig] = None,
+        retry_policy: Optional[dict[str, int]] = None,
     ) -> list[dict[str, Any] | Exception]:
         """
         Process multiple documents from local file paths concurrently with round-robin distribution.
@@ -218,8 +941,11 @@ class RustyAnalysisClient:
                 - 'prebuilt-document': General document analysis
                 - Custom model IDs from your trained models
             
-            file_paths: List of local file paths to process. Paths can be absolute
-                or relative to the current working directory.
+            file_paths: List of local file paths (str or os.PathLike) to process.
+                Paths can be absolute or relative to the current working
+                directory. Names that are not valid UTF-8 are supported, and on
+                Windows paths longer than 260 characters are opened with the
+                `\\\\?\\` prefix automatically.
             
             features: Optional list of analysis features to enable. Available features:
                 - 'ocrHighResolution': High-resolution OCR for better accuracy
@@ -237,13 +963,93 @@ class RustyAnalysisClient:
             
             max_rps: Maximum requests per second per resource to control rate limiting.
                 Helps respect Azure API quotas. Defaults to 15.
+
+            timeout_secs: Per-document timeout for this call, overriding the
+                client's `timeout_secs`. Must not exceed the client's
+                `batch_timeout_secs`. Defaults to None (use the client setting).
+
+            poll_interval: Initial delay between status polls for this call, overriding the
+                client's `poll_interval`. Defaults to None (use the client setting).
+
+            priorities: Submission priority per input, aligned with `file_paths`.
+                Higher values are submitted first; equal values keep their input
+                order. Items of `file_paths` may instead be `(path, priority)`
+                tuples. Results are always returned in input order.
+                Defaults to None (submit in input order).
+
+            checkpoint_path: JSON Lines file to which every finished document is
+                appended (source hash, status, result path or error). On a later
+                call with the same checkpoint, model, features and query options,
+                inputs that already succeeded are skipped and only the remainder
+                is submitted. A line truncated by a crash is ignored.
+                Defaults to None.
+
+            output_dir: Used with `checkpoint_path`. Each successful result is
+                saved here as JSON so skipped inputs can be returned in full;
+                without it, skipped inputs are returned as `CheckpointedResult`
+                markers. Defaults to None.
+
+            select: Result sections to keep, any of 'content', 'pages',
+                'paragraphs', 'tables', 'languages', 'documents',
+                'key_value_pairs', 'styles', 'figures' and 'sections'.
+                Other sections are skipped without being parsed and are left out of
+                `to_dict()`. Unknown names raise ValueError before anything is
+                submitted. Defaults to None (keep everything).
+
+            strict: Raise ValueError when markdown output is requested from a
+                model known not to support it. Otherwise a warning is logged and
+                `outputContentFormat` is omitted from the request. Defaults to False.
+
+            skip_unsupported: Don't submit files whose extension the service
+                does not accept (e.g. `.docx`). Their result slot holds an
+                `AnalysisError` with `kind == "unsupported_type"` instead.
+                Defaults to False (submit them and let the service reject them).
+
+            max_retries: Retries of the analyze request and of each status poll
+                on 429, 500, 502, 503 and 504 responses. Retries wait for
+                `Retry-After` when sent, else back off exponentially with jitter.
+                A retrying document keeps its concurrency slot, so retries never
+                exceed `max_rps`. Defaults to None (3).
+
+            initial_backoff_ms: Delay before the first retry without a
+                `Retry-After`, doubling on each further retry. Defaults to None (500).
+
+            pages: Pages to analyze, as 1-based page numbers and ranges such as
+                '1-3,5'. Sent as the `pages` query parameter; malformed values
+                such as '1--3' raise ValueError before anything is submitted.
+                Defaults to None (all pages).
+
+            locale: Language hint for OCR, e.g. 'en-US' or 'de', sent as the
+                `locale` query parameter. Defaults to None (the service detects
+                the language).
+
+            query_fields: Extra fields to extract, e.g. ['Name', 'Address'],
+                sent as the `queryFields` query parameter. The `queryFields`
+                feature is added when missing, and the extracted values come
+                back as fields of `documents`. Defaults to None.
+
+            config: Settings built with `BatchProcessingConfig`. Those set on
+                it override the matching arguments above. Defaults to None.
+
+            retry_policy: HTTP retry settings for this call, with any of
+                'max_retries', 'initial_backoff_ms' and 'max_backoff_ms' (cap on
+                the backoff delay; 30000 by default). Each may be given here or
+                as an argument, not both. Settings on `config` come first, then
+                these, then the client's. Unknown keys and negative values raise
+                ValueError. Defaults to None.
         
         Returns:
             List of results where each item corresponds to the input file at the
             same index. Each item is either:
                 - dict: Successfully analyzed document result containing the full
                   analyzeResult with 'content', 'pages', 'tables', etc.
-                - Exception: Error object if processing failed for that document
+                - DocumentAnalysisError: Error object if processing failed for that
+                  document; the subclass (RateLimitError, AuthenticationError, ...)
+                  says why
+            Every item, success or failure, has `source` (the input) and `index`
+            (its position in the input list) attributes, and `elapsed_secs`,
+            `throttled` (429 responses retried) and `endpoint` (the credential
+            used) for the documents that were started.
         
         Supported file formats:
             - PDF (.pdf)
@@ -294,3 +1100,567 @@ class RustyAnalysisClient:
             read or processed, but the function itself won't raise exceptions.
         """
         ...
+
+    async def process_batch_documents_from_urls_async(
+        self,
+        model_id: str,
+        document_urls: list[str | tuple[str, int]],
+        features: Optional[list[str]] = None,
+        output_format: Optional[str] = None,
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        priorities: Optional[list[int]] = None,
+        checkpoint_path: Optional[str | os.PathLike[str]] = None,
+        output_dir: Optional[str | os.PathLike[str]] = None,
+        select: Optional[list[str]] = None,
+        strict: bool = False,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+        pages: Optional[str] = None,
+        locale: Optional[str] = None,
+        query_fields: Optional[list[str]] = None,
+        config: Optional[BatchProcessingConfig] = None,
+        retry_policy: Optional[dict[str, int]] = None,
+    ) -> list[dict[str, Any] | Exception]:
+        """
+        Coroutine version of `process_batch_documents_from_urls`, for use from
+        `async def` code such as FastAPI handlers.
+        
+        Takes the same arguments and returns the same list. Awaiting it does
+        not block the event loop: documents are processed on the client's
+        worker threads and the coroutine resumes when the batch is done.
+        Cancelling the coroutine aborts the documents still in flight.
+        
+        Raises:
+            RuntimeError: If the client has been closed.
+        
+        Example:
+            >>> results = await client.process_batch_documents_from_urls_async(
+            ...     "prebuilt-layout", urls
+            ... )
+        """
+        ...
+
+    async def process_batch_documents_from_file_paths_async(
+        self,
+        model_id: str,
+        file_paths: list[str | os.PathLike[str] | tuple[str | os.PathLike[str], int]],
+        features: Optional[list[str]] = None,
+        output_format: Optional[str] = None,
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        priorities: Optional[list[int]] = None,
+        checkpoint_path: Optional[str | os.PathLike[str]] = None,
+        output_dir: Optional[str | os.PathLike[str]] = None,
+        select: Optional[list[str]] = None,
+        strict: bool = False,
+        skip_unsupported: bool = False,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+        pages: Optional[str] = None,
+        locale: Optional[str] = None,
+        query_fields: Optional[list[str]] = None,
+        config: Optional[BatchProcessingConfig] = None,
+        retry_policy: Optional[dict[str, int]] = None,
+    ) -> list[dict[str, Any] | Exception]:
+        """
+        Coroutine version of `process_batch_documents_from_file_paths`, for use
+        from `async def` code such as FastAPI handlers.
+        
+        Takes the same arguments and returns the same list. Awaiting it does
+        not block the event loop: files are read and processed on the client's
+        worker threads and the coroutine resumes when the batch is done.
+        Cancelling the coroutine aborts the documents still in flight.
+        
+        Raises:
+            RuntimeError: If the client has been closed.
+        """
+        ...
+
+    def process_batch_documents_from_urls_named(
+        self,
+        model_id: str,
+        documents: dict[str, str | tuple[str, int]],
+        features: Optional[list[str]] = None,
+        output_format: Optional[str] = None,
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        checkpoint_path: Optional[str | os.PathLike[str]] = None,
+        output_dir: Optional[str | os.PathLike[str]] = None,
+        select: Optional[list[str]] = None,
+        strict: bool = False,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+        pages: Optional[str] = None,
+        locale: Optional[str] = None,
+        query_fields: Optional[list[str]] = None,
+        config: Optional[BatchProcessingConfig] = None,
+        retry_policy: Optional[dict[str, int]] = None,
+    ) -> dict[str, Any]:
+        """
+        Like `process_batch_documents_from_urls`, but takes a dict from caller-chosen names
+        to URLs and returns a dict from the same names to each document's
+        AnalyzeResult or exception, in the order of `documents`. Results never
+        have to be matched to inputs by position.
+        
+        The other arguments are as for `process_batch_documents_from_urls`.
+        """
+        ...
+
+    def process_batch_documents_from_file_paths_named(
+        self,
+        model_id: str,
+        documents: dict[str, str | os.PathLike[str] | tuple[str | os.PathLike[str], int]],
+        features: Optional[list[str]] = None,
+        output_format: Optional[str] = None,
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        checkpoint_path: Optional[str | os.PathLike[str]] = None,
+        output_dir: Optional[str | os.PathLike[str]] = None,
+        select: Optional[list[str]] = None,
+        strict: bool = False,
+        skip_unsupported: bool = False,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+        pages: Optional[str] = None,
+        locale: Optional[str] = None,
+        query_fields: Optional[list[str]] = None,
+        config: Optional[BatchProcessingConfig] = None,
+        retry_policy: Optional[dict[str, int]] = None,
+    ) -> dict[str, Any]:
+        """
+        Like `process_batch_documents_from_file_paths`, but takes a dict from caller-chosen names
+        to file paths and returns a dict from the same names to each document's
+        AnalyzeResult or exception, in the order of `documents`. Results never
+        have to be matched to inputs by position.
+        
+        The other arguments are as for `process_batch_documents_from_file_paths`.
+        """
+        ...
+
+    def process_batch_from_config_file(
+        self,
+        config_path: str | os.PathLike[str],
+    ) -> list[dict[str, Any] | Exception]:
+        """
+        Process a batch described by a JSON config file.
+        
+        The file holds an object with `model_id` and exactly one of
+        `document_urls` and `file_paths`, plus optional `features` and
+        `output_format`; unknown keys are rejected. Relative `file_paths` are
+        resolved against the config file's directory.
+        
+        Args:
+            config_path: Path to the JSON config file
+        
+        Returns:
+            One result or DocumentAnalysisError per document, as returned by
+            `process_batch_documents_from_urls` or
+            `process_batch_documents_from_file_paths`.
+        
+        Raises:
+            IOError: If the config file cannot be read
+            ValueError: If the file is not a valid config
+        
+        Example:
+            >>> # batch.json:
+            >>> # {"model_id": "prebuilt-layout", "file_paths": ["a.pdf", "b.pdf"]}
+            >>> results = client.process_batch_from_config_file("batch.json")
+        """
+        ...
+
+    def process_batch_documents_from_zip(
+        self,
+        model_id: str,
+        zip_path: str | os.PathLike[str],
+        features: Optional[list[str]] = None,
+        output_format: Optional[str] = None,
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        select: Optional[list[str]] = None,
+        strict: bool = False,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+        retry_policy: Optional[dict[str, int]] = None,
+        checkpoint_path: Optional[str | os.PathLike[str]] = None,
+        output_dir: Optional[str | os.PathLike[str]] = None,
+    ) -> dict[str, Any]:
+        """
+        Process every supported document inside a ZIP archive concurrently.
+        
+        The archive is read into memory up front. Entries with a supported
+        extension are uploaded as raw bytes and distributed across resources just
+        like file paths; directories and other file types are skipped.
+        
+        Args:
+            model_id: The Document Intelligence model ID to use for analysis.
+            
+            zip_path: Path to a local ZIP archive.
+            
+            features: Optional list of analysis features to enable. Defaults to None.
+            
+            output_format: 'text' (default) or 'markdown'.
+            
+            max_rps: Maximum requests per second per resource. Defaults to 15.
+
+            timeout_secs: Per-document timeout for this call, overriding the
+                client's `timeout_secs`. Defaults to None (use the client setting).
+
+            poll_interval: Initial delay between status polls for this call, overriding the
+                client's `poll_interval`. Defaults to None (use the client setting).
+
+            select: Result sections to keep; see
+                `process_batch_documents_from_urls`. Defaults to None.
+
+            strict: Raise ValueError when markdown output is requested from a
+                model known not to support it. Otherwise a warning is logged and
+                `outputContentFormat` is omitted from the request. Defaults to False.
+
+            max_retries: Retries on 429 and transient 5xx responses; see
+                `process_batch_documents_from_urls`. Defaults to None (3).
+
+            initial_backoff_ms: Delay before the first retry without a
+                `Retry-After`. Defaults to None (500).
+
+            retry_policy: HTTP retry settings for this call; see
+                `process_batch_documents_from_urls`. Defaults to None.
+
+            checkpoint_path: Checkpoint file; see
+                `process_batch_documents_from_urls`. Entries are matched by
+                their path and content. Defaults to None.
+
+            output_dir: Directory for checkpointed results; see
+                `process_batch_documents_from_urls`. Defaults to None.
+        
+        Returns:
+            Dict keyed by each entry's path inside the archive. Each value is
+            either an AnalyzeResult or an Exception if that entry failed.
+        
+        Raises:
+            IOError: If the archive cannot be opened, an entry cannot be extracted or
+                exceeds 500 MB, or two entries share the same path.
+        
+        Example:
+            >>> results = client.process_batch_documents_from_zip(
+            ...     "prebuilt-layout", "invoices.zip"
+            ... )
+            >>> for name, result in results.items():
+            ...     if isinstance(result, Exception):
+            ...         print(f"{name} failed: {result}")
+        """
+        ...
+
+    def process_batch_documents_from_file_objects(
+        self,
+        model_id: str,
+        files: list[Any],
+        features: Optional[list[str]] = None,
+        output_format: Optional[str] = None,
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        select: Optional[list[str]] = None,
+        image_format: Optional[str] = None,
+        jpeg_quality: int = 90,
+        strict: bool = False,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+        retry_policy: Optional[dict[str, int]] = None,
+        checkpoint_path: Optional[str | os.PathLike[str]] = None,
+        output_dir: Optional[str | os.PathLike[str]] = None,
+    ) -> list[Any]:
+        """
+        Process in-memory file-like objects or images concurrently.
+        
+        Accepts any object with a binary `read(size)` method, such as open files,
+        `zipfile.ZipFile.open()` entries or S3 streaming bodies, so documents do
+        not have to be written to disk first. Each object is read exactly once,
+        in bounded chunks, before the batch is submitted. Objects are not closed.
+
+        PIL images and uint8 image arrays (numpy-style `(H, W)`, `(H, W, 3)` or
+        `(H, W, 4)`) are also accepted and encoded in memory with Pillow, so
+        preprocessed pages need no temporary files.
+        
+        Args:
+            model_id: The Document Intelligence model ID to use for analysis.
+            
+            files: File-like objects, PIL images or image arrays. A file's `name`
+                attribute, if present, is used to detect the content type from
+                its extension.
+            
+            features: Optional list of analysis features to enable. Defaults to None.
+            
+            output_format: 'text' (default) or 'markdown'.
+            
+            max_rps: Maximum requests per second per resource. Defaults to 15.
+
+            timeout_secs: Per-document timeout for this call, overriding the
+                client's `timeout_secs`. Defaults to None (use the client setting).
+
+            poll_interval: Initial delay between status polls for this call, overriding the
+                client's `poll_interval`. Defaults to None (use the client setting).
+
+            select: Result sections to keep; see
+                `process_batch_documents_from_urls`. Defaults to None.
+
+            image_format: Encoding for images and arrays, 'png' (default) or
+                'jpeg'.
+
+            jpeg_quality: Quality from 1 to 100 when `image_format` is 'jpeg'.
+                Defaults to 90.
+
+            strict: Raise ValueError when markdown output is requested from a
+                model known not to support it. Otherwise a warning is logged and
+                `outputContentFormat` is omitted from the request. Defaults to False.
+
+            max_retries: Retries on 429 and transient 5xx responses; see
+                `process_batch_documents_from_urls`. Defaults to None (3).
+
+            initial_backoff_ms: Delay before the first retry without a
+                `Retry-After`. Defaults to None (500).
+
+            retry_policy: HTTP retry settings for this call; see
+                `process_batch_documents_from_urls`. Defaults to None.
+
+            checkpoint_path: Checkpoint file; see
+                `process_batch_documents_from_urls`. Documents are matched
+                by their name and content. Defaults to None.
+
+            output_dir: Directory for checkpointed results; see
+                `process_batch_documents_from_urls`. Defaults to None.
+        
+        Returns:
+            List of results in input order. Each item is either an AnalyzeResult
+            or an Exception if that document failed, and has `source` (the
+            object's name) and `index` attributes.
+        
+        Raises:
+            TypeError: If an object is neither readable nor an image, its `read()`
+                does not return bytes, or an array is not uint8. The message
+                names the offending index.
+            
+            ValueError: If an array has an unsupported shape, `image_format` is
+                unknown, or `jpeg_quality` is outside 1-100 for 'jpeg'.
+            
+            ImportError: If an array is given and Pillow is not installed.
+        
+        Example:
+            >>> with zipfile.ZipFile("invoices.zip") as archive:
+            ...     files = [archive.open(name) for name in archive.namelist()]
+            ...     results = client.process_batch_documents_from_file_objects(
+            ...         "prebuilt-invoice", files
+            ...     )
+        """
+        ...
+
+    def process_batch_documents_from_bytes(
+        self,
+        model_id: str,
+        documents: list[Union[bytes, tuple[str, bytes], tuple[str, bytes, str]]],
+        features: Optional[list[str]] = None,
+        output_format: Optional[str] = None,
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        select: Optional[list[str]] = None,
+        strict: bool = False,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+        retry_policy: Optional[dict[str, int]] = None,
+        checkpoint_path: Optional[str | os.PathLike[str]] = None,
+        output_dir: Optional[str | os.PathLike[str]] = None,
+    ) -> list[Any]:
+        """
+        Process in-memory documents concurrently.
+
+        For documents generated or received in memory (e.g. web uploads), so
+        they don't have to be written to temporary files first. Documents are
+        spread over the credentials and rate-limited exactly like the other
+        batch methods.
+
+        Args:
+            model_id: The Document Intelligence model ID to use for analysis.
+
+            documents: Items of `bytes`, `(name, bytes)` or
+                `(name, bytes, content_type)`. The name appears in logs and
+                errors, and its extension picks the content type when none is
+                given; otherwise the type is sniffed from the data's magic bytes
+                (PDF, JPEG, PNG, TIFF, BMP).
+
+            features: Optional list of analysis features to enable. Defaults to None.
+
+            output_format: 'text' (default) or 'markdown'.
+
+            max_rps: Maximum requests per second per resource. Defaults to 15.
+
+            timeout_secs: Per-document timeout for this call, overriding the
+                client's `timeout_secs`. Defaults to None (use the client setting).
+
+            poll_interval: Initial delay between status polls for this call, overriding the
+                client's `poll_interval`. Defaults to None (use the client setting).
+
+            select: Result sections to keep; see
+                `process_batch_documents_from_urls`. Defaults to None.
+
+            strict: Raise ValueError when markdown output is requested from a
+                model known not to support it. Otherwise a warning is logged and
+                `outputContentFormat` is omitted from the request. Defaults to False.
+
+            max_retries: Retries on 429 and transient 5xx responses; see
+                `process_batch_documents_from_urls`. Defaults to None (3).
+
+            initial_backoff_ms: Delay before the first retry without a
+                `Retry-After`. Defaults to None (500).
+
+            retry_policy: HTTP retry settings for this call; see
+                `process_batch_documents_from_urls`. Defaults to None.
+
+            checkpoint_path: Checkpoint file; see
+                `process_batch_documents_from_urls`. Documents are matched
+                by their name and content. Defaults to None.
+
+            output_dir: Directory for checkpointed results; see
+                `process_batch_documents_from_urls`. Defaults to None.
+
+        Returns:
+            List of results in input order. Each item is either an AnalyzeResult
+            or an Exception if that document failed, and has `source` (the
+            document's name, or `document-{i}`) and `index` attributes.
+
+        Raises:
+            ValueError: If a given content type is not one the service accepts.
+
+        Example:
+            >>> results = client.process_batch_documents_from_bytes(
+            ...     "prebuilt-layout",
+            ...     [("upload.pdf", pdf_bytes), png_bytes],
+            ... )
+        """
+        ...
+
+    def process_batch_from_bytes(
+        self,
+        model_id: str,
+        documents: list[tuple[bytes, str]],
+        features: Optional[list[str]] = None,
+        output_format: Optional[str] = None,
+        max_rps: int = 15,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        select: Optional[list[str]] = None,
+        strict: bool = False,
+        max_retries: Optional[int] = None,
+        initial_backoff_ms: Optional[int] = None,
+        retry_policy: Optional[dict[str, int]] = None,
+        checkpoint_path: Optional[str | os.PathLike[str]] = None,
+        output_dir: Optional[str | os.PathLike[str]] = None,
+    ) -> list[Any]:
+        """
+        Process in-memory documents, each with an explicit MIME type.
+
+        The bytes are uploaded directly with the given `Content-Type`, skipping
+        all filesystem I/O. See `process_batch_documents_from_bytes` for named
+        documents and content type detection.
+
+        Args:
+            model_id: The Document Intelligence model ID to use for analysis.
+
+            documents: `(data, mime_type)` pairs. The MIME type must be one the
+                service accepts: application/pdf, image/jpeg, image/png,
+                image/tiff or image/bmp.
+
+            features, output_format, max_rps, timeout_secs, poll_interval,
+            select, strict, max_retries, initial_backoff_ms, retry_policy,
+            checkpoint_path, output_dir: As for
+                `process_batch_documents_from_bytes`; documents are matched in
+                the checkpoint by their content.
+
+        Returns:
+            List of results in input order. Each item is either an AnalyzeResult
+            or an Exception if that document failed, and has `source`
+            (`document-{i}`) and `index` attributes.
+
+        Raises:
+            ValueError: If a MIME type is not supported. The message names the
+                offending index.
+        """
+        ...
+
+    def analyze_document_paged(
+        self,
+        model_id: str,
+        url_or_path: str,
+        pages_per_request: int = 100,
+        total_pages: Optional[int] = None,
+        features: Optional[list[str]] = None,
+        output_format: Optional[str] = None,
+        max_retries: int = 2,
+        timeout_secs: Optional[float] = None,
+        poll_interval: Optional[float] = None,
+        initial_backoff_ms: Optional[int] = None,
+        retry_policy: Optional[dict[str, int]] = None,
+    ) -> Any:
+        """
+        Analyze one large document as several page ranges in parallel.
+        
+        The document is split into `pages` ranges of `pages_per_request` pages
+        that are analyzed concurrently across all configured resources, then
+        merged into one AnalyzeResult with document-wide page numbers and span
+        offsets. Ranges that fail with a retryable error (429, 5xx, timeouts) are
+        resubmitted individually.
+        
+        Args:
+            model_id: The Document Intelligence model ID to use for analysis.
+            
+            url_or_path: A document URL (http/https) or a local file path.
+            
+            pages_per_request: Pages per analyze request. Defaults to 100.
+            
+            total_pages: Page count of the document, if known. When None, ranges
+                are submitted in waves of one per resource until a range comes
+                back short. Defaults to None.
+            
+            features: Optional list of analysis features to enable. Defaults to None.
+            
+            output_format: 'text' (default) or 'markdown'.
+            
+            max_retries: Resubmissions allowed per failed range. Defaults to 2.
+
+            timeout_secs: Per-request timeout, overriding the client's
+                `timeout_secs`. Defaults to None (use the client setting).
+
+            poll_interval: Delay between status polls, overriding the client's
+                `poll_interval`. Defaults to None (use the client setting).
+
+            initial_backoff_ms: Delay before the first HTTP retry of a request on
+                429 or 5xx without a `Retry-After`, doubling on each further
+                retry. Defaults to None (use the client setting).
+
+            retry_policy: HTTP retry settings for each request, with any of
+                'max_retries' (HTTP retries, unlike the `max_retries` argument),
+                'initial_backoff_ms' and 'max_backoff_ms'. Unknown keys and
+                negative values raise ValueError. Defaults to None.
+        
+        Returns:
+            The merged AnalyzeResult. Its `page_ranges` attribute lists each
+            request made (`pages`, `page_count`, `attempts`); every request is
+            billed separately for the pages it analyzed.
+        
+        Raises:
+            ValueError: If `pages_per_request` or `total_pages` is zero, a local
+                TIFF is truncated or malformed, or `retry_policy` is invalid.
+            IOError: If the local file cannot be read.
+            RuntimeError: If a range still fails after `max_retries` resubmissions.
+        
+        Example:
+            >>> result = client.analyze_document_paged(
+            ...     "prebuilt-layout", "filing.pdf", pages_per_request=100
+            ... )
+            >>> print(len(result.pages))
+        """
+        ...
@@ -1,3 +1,8 @@
 pub mod base;
+pub mod checkpoint;
 pub mod document_intelligence;
+pub mod language;
+pub mod rate_limit;
+pub mod routing;
+pub mod shutdown;
 //pub mod form_recognizer;
@@ -1,18 +1,515 @@
-use std::sync::{
-    Arc,
-    atomic::{AtomicUsize, Ordering},
+use std::{
+    borrow::Cow,
+    cmp::Reverse,
+    collections::VecDeque,
+    future::Future,
+    path::PathBuf,
+    pin::{Pin, pin},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
-use futures::future::join_all;
-use reqwest::Client;
-use serde_json::Value;
-use tokio::sync::Semaphore;
+use futures::{
+    StreamExt,
+    future::{self, Either, join_all},
+    stream,
+};
+use pyo3::{prelude::*, types::PyDict};
+use reqwest::{Client, StatusCode};
+use serde_json::{Value, value::RawValue};
+use tokio::{
+    task::{JoinError, JoinHandle},
+    time::Instant as TokioInstant,
+};
+use tracing::warn;
 
 use crate::{
-    clients::document_intelligence::{analyze_document_from_file_path, analyze_document_from_urls},
-    models::analysis_client::RustyAnalysisClient,
+    clients::{
+        checkpoint::fnv1a,
+        document_intelligence::{
+            analyze_document_from_bytes, analyze_document_from_file_path,
+            analyze_document_from_urls, check_credentials,
+        },
+        language::recognize_entities,
+        routing::CredentialRouter,
+    },
+    models::{
+        AnalyzeOptions, BatchSettings, CredentialCheck, Credentials, DocumentBytes, DocumentEntity,
+        PageRangeAnalysis, PollingConfig, analysis_client::RustyAnalysisClient,
+        errors::DocumentAnalysisError,
+    },
+    utils::{merge::merge_analyze_results, report::redact_url},
 };
 
+/// The phase a document is currently in while a batch runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentState {
+    /// Queued until a worker is free.
+    Waiting,
+    /// Uploading the document or URL to the analyze endpoint.
+    Submitting,
+    /// Polling the operation for completion.
+    Polling,
+}
+
+/// Live counts of documents in each [`DocumentState`], shared by every batch
+/// run on a client.
+#[derive(Default)]
+pub struct InFlightGauges {
+    waiting: AtomicUsize,
+    submitting: AtomicUsize,
+    polling: AtomicUsize,
+    notifier: Option<StateNotifier>,
+    status_notifier: Option<StatusNotifier>,
+}
+
+impl InFlightGauges {
+    pub fn new(notifier: Option<StateNotifier>, status_notifier: Option<StatusNotifier>) -> Self {
+        Self {
+            notifier,
+            status_notifier,
+            ..Self::default()
+        }
+    }
+
+    fn gauge(&self, state: DocumentState) -> &AtomicUsize {
+        match state {
+            DocumentState::Waiting => &self.waiting,
+            DocumentState::Submitting => &self.submitting,
+            DocumentState::Polling => &self.polling,
+        }
+    }
+
+    pub fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.snapshot().to_dict(py)
+    }
+
+    fn snapshot(&self) -> GaugeSnapshot {
+        GaugeSnapshot {
+            waiting: self.waiting.load(Ordering::Relaxed),
+            submitting: self.submitting.load(Ordering::Relaxed),
+            polling: self.polling.load(Ordering::Relaxed),
+        }
+    }
+
+    fn notify(&self) {
+        if let Some(notifier) = &self.notifier {
+            notifier.fire(self.snapshot());
+        }
+    }
+}
+
+/// The gauge counts at one moment, as passed to `on_state_change`.
+#[derive(Clone, Copy)]
+struct GaugeSnapshot {
+    waiting: usize,
+    submitting: usize,
+    polling: usize,
+}
+
+impl GaugeSnapshot {
+    fn to_dict<'py>(self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("waiting", self.waiting)?;
+        dict.set_item("submitting", self.submitting)?;
+        dict.set_item("polling", self.polling)?;
+        Ok(dict)
+    }
+}
+
+/// Invokes a Python callback on state transitions, at most once per `min_interval`
+/// so busy batches don't thrash the GIL.
+///
+/// The callback runs on a dedicated thread, never on a runtime worker. A
+/// transition inside `min_interval` is not dropped: the latest counts are
+/// delivered in one trailing call once the interval has passed, so the last
+/// call of a batch always reports its final counts.
+pub struct StateNotifier {
+    sender: mpsc::Sender<GaugeSnapshot>,
+}
+
+impl StateNotifier {
+    pub fn new(callback: Py<PyAny>, max_calls_per_sec: f64) -> Self {
+        let min_interval = Duration::from_secs_f64(1.0 / max_calls_per_sec);
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("rusty-di-state-notifier".to_string())
+            .spawn(move || notify_loop(&callback, min_interval, receiver))
+            .expect("failed to spawn the on_state_change thread");
+        Self { sender }
+    }
+
+    fn fire(&self, snapshot: GaugeSnapshot) {
+        // Only fails once the notifier thread is gone, when nobody is listening.
+        let _ = self.sender.send(snapshot);
+    }
+}
+
+/// Delivers snapshots to `callback` until the notifier is dropped. A snapshot
+/// arriving within `min_interval` of the previous call is held, replaced by
+/// any newer one, and delivered when the interval ends.
+fn notify_loop(callback: &Py<PyAny>, min_interval: Duration, receiver: mpsc::Receiver<GaugeSnapshot>) {
+    let mut pending = None;
+    let mut next_allowed = Instant::now();
+    loop {
+        let received = match pending {
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            Some(_) => receiver.recv_timeout(next_allowed.saturating_duration_since(Instant::now())),
+        };
+        let disconnected = match received {
+            Ok(snapshot) => {
+                pending = Some(snapshot);
+                false
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => true,
+        };
+        if let Some(snapshot) = pending
+            && (disconnected || Instant::now() >= next_allowed)
+        {
+            pending = None;
+            Python::attach(|py| {
+                let result = snapshot.to_dict(py).and_then(|dict| callback.call1(py, (dict,)));
+                if let Err(err) = result {
+                    warn!(error = %err, "on_state_change callback raised an exception");
+                }
+            });
+            next_allowed = Instant::now() + min_interval;
+        }
+        if disconnected {
+            return;
+        }
+    }
+}
+
+/// Invokes the `on_status` Python callback once per status poll of a document.
+///
+/// The callback runs on tokio's blocking pool, so a slow callback or one waiting
+/// for the GIL never stalls the runtime's worker threads.
+pub struct StatusNotifier {
+    callback: Arc<Py<PyAny>>,
+    raise_errors: bool,
+}
+
+impl StatusNotifier {
+    /// With `raise_errors`, an exception from the callback fails the document;
+    /// otherwise it is logged and polling continues.
+    pub fn new(callback: Py<PyAny>, raise_errors: bool) -> Self {
+        Self { callback: Arc::new(callback), raise_errors }
+    }
+
+    async fn fire(&self, source: &str, status: &str, percent_completed: Option<u32>, poll: u32) -> anyhow::Result<()> {
+        let callback = self.callback.clone();
+        let args = (source.to_string(), status.to_string(), percent_completed, poll);
+        let called = tokio::task::spawn_blocking(move || {
+            Python::attach(|py| callback.call1(py, args).map(drop))
+        })
+        .await?;
+        match called {
+            Ok(()) => Ok(()),
+            Err(err) if self.raise_errors => {
+                Err(anyhow::anyhow!("on_status callback raised an exception: {}", err))
+            }
+            Err(err) => {
+                warn!(source = source, error = %err, "on_status callback raised an exception");
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Moves a single document between gauges, releasing its slot when dropped so
+/// the gauges return to zero even if the task fails or panics.
+///
+/// Once routed to a credential, it also reports how long the submission request
+/// took, which feeds latency-aware credential routing. A document that leaves
+/// while still submitting (the request failed or the document timed out) is
+/// reported as a slow submission.
+pub struct DocumentStateTracker {
+    gauges: Arc<InFlightGauges>,
+    current: Option<DocumentState>,
+    submitted_at: Option<Instant>,
+    route: Option<(Arc<CredentialRouter>, usize)>,
+    counters: Arc<DocumentCounters>,
+}
+
+/// Per-document counters a batch reads once the document finishes. Shared
+/// between the tracker, which moves into the document's task, and the worker.
+#[derive(Debug, Default)]
+pub struct DocumentCounters {
+    throttled: AtomicU32,
+    /// Index of the credential last routed to, plus one; zero before routing.
+    credential: AtomicUsize,
+}
+
+impl DocumentCounters {
+    fn credential(&self) -> Option<usize> {
+        self.credential.load(Ordering::Relaxed).checked_sub(1)
+    }
+}
+
+/// What a batch measured about one document: set on its result so a report
+/// can describe that batch rather than the client's lifetime.
+#[derive(Clone, Debug, Default)]
+pub struct DocumentMetrics {
+    /// From the document leaving the queue to its outcome; None if it was never
+    /// started.
+    pub elapsed: Option<Duration>,
+    /// 429 responses the document was retried on.
+    pub throttled: u32,
+    /// Endpoint of the credential that produced the outcome.
+    pub endpoint: Option<String>,
+}
+
+/// A document's result together with its [`DocumentMetrics`].
+pub struct DocumentOutcome {
+    pub result: Result<Box<RawValue>, DocumentAnalysisError>,
+    pub metrics: DocumentMetrics,
+}
+
+impl DocumentStateTracker {
+    pub fn new(gauges: Arc<InFlightGauges>) -> Self {
+        let mut tracker = Self {
+            gauges,
+            current: None,
+            submitted_at: None,
+            route: None,
+            counters: Arc::default(),
+        };
+        tracker.enter(DocumentState::Waiting);
+        tracker
+    }
+
+    /// Records which credential the document was routed to.
+    pub fn route_to(&mut self, router: Arc<CredentialRouter>, credential_index: usize) {
+        self.counters.credential.store(credential_index + 1, Ordering::Relaxed);
+        self.route = Some((router, credential_index));
+    }
+
+    /// Counts a 429 response the document is about to be retried on.
+    pub fn record_throttle(&self) {
+        self.counters.throttled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Closes an attempt rejected by its credential before the document is
+    /// retried on another, charging the rejected credential as `Drop` would.
+    fn end_failed_attempt(&mut self) {
+        self.record_failed_submission();
+        self.route = None;
+    }
+
+    /// Counts a submission abandoned while in flight as a slow one for the
+    /// credential it was routed to.
+    fn record_failed_submission(&mut self) {
+        if self.current == Some(DocumentState::Submitting)
+            && let (Some(started), Some((router, index))) = (self.submitted_at.take(), &self.route)
+        {
+            router.record_failed_submission(*index, started.elapsed());
+        }
+    }
+
+    /// Marks the start of the analyze request, after any local work such as
+    /// reading the file, so only the service's latency is measured.
+    pub fn begin_request(&mut self) {
+        self.submitted_at = Some(Instant::now());
+    }
+
+    pub fn enter(&mut self, state: DocumentState) {
+        if state == DocumentState::Polling
+            && let (Some(started), Some((router, index))) = (self.submitted_at.take(), &self.route)
+        {
+            router.record_latency(*index, started.elapsed());
+        }
+        if let Some(previous) = self.current.replace(state) {
+            self.gauges.gauge(previous).fetch_sub(1, Ordering::Relaxed);
+        }
+        self.gauges.gauge(state).fetch_add(1, Ordering::Relaxed);
+        self.gauges.notify();
+    }
+
+    /// Passes the outcome of one status poll to the `on_status` callback, if any.
+    pub async fn report_status(
+        &self,
+        source: &str,
+        status: &str,
+        percent_completed: Option<u32>,
+        poll: u32,
+    ) -> anyhow::Result<()> {
+        match &self.gauges.status_notifier {
+            Some(notifier) => notifier.fire(source, status, percent_completed, poll).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for DocumentStateTracker {
+    fn drop(&mut self) {
+        self.record_failed_submission();
+        if let Some(previous) = self.current.take() {
+            self.gauges.gauge(previous).fetch_sub(1, Ordering::Relaxed);
+            self.gauges.notify();
+        }
+    }
+}
+
+/// Everything a batch task hands to the per-document analyze call. The call
+/// returns the tracker with its result, so a document failing over to another
+/// credential keeps its gauge slot and counters.
+pub struct DocumentJob<I> {
+    pub client: Client,
+    pub creds: Credentials,
+    pub input: I,
+    pub polling: PollingConfig,
+    pub tracker: DocumentStateTracker,
+}
+
+/// Where `analyze_document_paged` reads its document from.
+#[derive(Clone)]
+pub enum PagedSource {
+    Url(String),
+    Bytes(DocumentBytes),
+}
+
+/// Identifies a batch input in checkpoints and logs.
+pub trait BatchSource {
+    fn source(&self) -> Cow<'_, str>;
+
+    /// Identifies the document across runs in a checkpoint. Defaults to `source`.
+    fn checkpoint_key(&self) -> Cow<'_, str> {
+        self.source()
+    }
+
+    /// Whether the service fetches the document from a URL, as opposed to the
+    /// client uploading its bytes.
+    fn is_url(&self) -> bool {
+        false
+    }
+}
+
+impl BatchSource for String {
+    fn source(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+
+    fn is_url(&self) -> bool {
+        true
+    }
+}
+
+impl BatchSource for PathBuf {
+    fn source(&self) -> Cow<'_, str> {
+        self.to_string_lossy()
+    }
+}
+
+impl BatchSource for DocumentBytes {
+    fn source(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.name)
+    }
+
+    /// Names like `document-0` are only unique within a call, so the content
+    /// is part of the key.
+    fn checkpoint_key(&self) -> Cow<'_, str> {
+        Cow::Owned(format!("{}#{:016x}", self.name, fnv1a(&self.data)))
+    }
+}
+
+impl BatchSource for (String, PagedSource) {
+    fn source(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.0)
+    }
+
+    fn is_url(&self) -> bool {
+        matches!(self.1, PagedSource::Url(_))
+    }
+}
+
+/// Counts a worker slot as active for as long as it is held.
+struct ActiveSlot<'a>(&'a AtomicUsize);
+
+impl<'a> ActiveSlot<'a> {
+    fn hold(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for ActiveSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A spawned task that is aborted when its handle is dropped, so a batch
+/// future dropped mid-flight (a cancelled coroutine) takes its tasks with it.
+pub(crate) struct AbortOnDrop<T>(pub(crate) JoinHandle<T>);
+
+impl<T> AbortOnDrop<T> {
+    pub(crate) fn abort(&self) {
+        self.0.abort();
+    }
+}
+
+impl<T> Future for AbortOnDrop<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Awaits `future`, failing with `on_timeout` if it runs past `deadline`.
+async fn with_deadline<T>(
+    deadline: Option<TokioInstant>,
+    future: impl Future<Output = anyhow::Result<T>>,
+    on_timeout: impl FnOnce() -> anyhow::Error,
+) -> anyhow::Result<T> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, future)
+            .await
+            .unwrap_or_else(|_| Err(on_timeout())),
+        None => future.await,
+    }
+}
+
+/// What a document's outcome says about the credential it was sent with.
+enum AuthOutcome {
+    /// The service accepted the credential: the document succeeded, failed
+    /// analysis, or was refused for another client-side reason (e.g. 400, 429).
+    Accepted,
+    /// The service answered 401 or 403, with the error message.
+    Rejected(String),
+    /// No answer from the service to judge by, e.g. a timeout or connection error.
+    Unknown,
+}
+
+fn auth_outcome(result: &anyhow::Result<Box<RawValue>>) -> AuthOutcome {
+    let Err(err) = result else {
+        return AuthOutcome::Accepted;
+    };
+    match err.downcast_ref::<DocumentAnalysisError>() {
+        Some(DocumentAnalysisError::HttpError { status_code: 401 | 403, message, .. }) => {
+            AuthOutcome::Rejected(message.clone())
+        }
+        Some(
+            DocumentAnalysisError::HttpError { status_code: 400..=499, .. }
+            | DocumentAnalysisError::AnalysisFailed { .. },
+        ) => AuthOutcome::Accepted,
+        _ => AuthOutcome::Unknown,
+    }
+}
+
 // TODO Add enum routing to batches
 // pub enum ClientType {
 //     DocumentIntelligenceClient(BatchType),
@@ -40,95 +537,857 @@ use crate::{
 //     }
 // }
 impl RustyAnalysisClient {
+    pub async fn validate_credentials_async(&self, timeout: Duration) -> Vec<CredentialCheck> {
+        let client = Client::new();
+        join_all(
+            self.credentials
+                .iter()
+                .map(|creds| check_credentials(&client, creds, timeout)),
+        )
+        .await
+    }
+
+    /// Runs entity recognition over each text, at most `concurrency` requests
+    /// at a time. `None` texts (failed documents) are skipped.
+    pub async fn recognize_entities_async(
+        &self,
+        creds: &Credentials,
+        texts: Vec<Option<String>>,
+        language: Option<&str>,
+        concurrency: usize,
+        polling: &PollingConfig,
+    ) -> Vec<Option<anyhow::Result<Vec<DocumentEntity>>>> {
+        let client = Client::new();
+        stream::iter(texts)
+            .map(|text| {
+                let client = &client;
+                async move {
+                    match text {
+                        Some(text) => Some(recognize_entities(client, creds, &text, language, polling).await),
+                        None => None,
+                    }
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
     pub async fn process_documents_async_from_urls(
         &self,
-        model_id: &str,
         document_urls: Vec<String>,
-        features: Option<Vec<String>>,
-        output_format: &str,
-        semaphore_size: usize,
-    ) -> Vec<Result<Value, String>> {
+        settings: BatchSettings,
+    ) -> Vec<DocumentOutcome> {
+        let options = Arc::new(settings.options.clone());
+        let results = self.run_batch(document_urls, settings, move |job: DocumentJob<String>| {
+            let options = options.clone();
+            async move {
+                let DocumentJob { client, creds, input, polling, mut tracker } = job;
+                let result = analyze_document_from_urls(&client, &options, creds, &input, &polling, &mut tracker).await;
+                (result, tracker)
+            }
+        })
+        .await;
+        flatten_results(results)
+    }
+
+    pub async fn process_documents_async_from_file_paths(
+        &self,
+        file_paths: Vec<PathBuf>,
+        settings: BatchSettings,
+    ) -> Vec<DocumentOutcome> {
+        let options = Arc::new(settings.options.clone());
+        let file_read = settings.file_read;
+        let results = self.run_batch(file_paths, settings, move |job: DocumentJob<PathBuf>| {
+            let options = options.clone();
+            async move {
+                let DocumentJob { client, creds, input, polling, mut tracker } = job;
+                let result =
+                    analyze_document_from_file_path(&client, &options, creds, &input, &file_read, &polling, &mut tracker)
+                        .await;
+                (result, tracker)
+            }
+        })
+        .await;
+        flatten_results(results)
+    }
+
+    pub async fn process_documents_async_from_bytes(
+        &self,
+        documents: Vec<DocumentBytes>,
+        settings: BatchSettings,
+    ) -> Vec<DocumentOutcome> {
+        let options = Arc::new(settings.options.clone());
+        let results = self.run_batch(documents, settings, move |job: DocumentJob<DocumentBytes>| {
+            let options = options.clone();
+            async move {
+                let DocumentJob { client, creds, input, polling, mut tracker } = job;
+                let result = analyze_document_from_bytes(&client, &options, creds, input, &polling, &mut tracker).await;
+                (result, tracker)
+            }
+        })
+        .await;
+        flatten_results(results)
+    }
+
+    /// Runs `analyze` over every input with bounded concurrency, routing each
+    /// document to a credential and applying the batch and document deadlines.
+    ///
+    /// `semaphore_size` workers pull documents from a queue ordered by priority
+    /// (highest first, input order among equals), so urgent documents are
+    /// submitted first. Once the client starts closing, workers stop taking new
+    /// documents.
+    ///
+    /// Ordering contract: each document carries its input index through the
+    /// queue and its task, and its outcome is stored in that slot, so outcomes
+    /// are returned in input order whatever the priorities, routing or
+    /// completion order.
+    async fn run_batch<I, F, Fut>(
+        &self,
+        inputs: Vec<I>,
+        settings: BatchSettings,
+        analyze: F,
+    ) -> Vec<(TaskOutcome, DocumentMetrics)>
+    where
+        I: BatchSource + Clone + Send + 'static,
+        F: Fn(DocumentJob<I>) -> Fut + Clone + Send + 'static,
+        Fut: Future<Output = (anyhow::Result<Box<RawValue>>, DocumentStateTracker)> + Send + 'static,
+    {
+        let BatchSettings { semaphore_size, polling, timeouts, priorities, checkpoint, .. } = settings;
         let client = Client::new();
+        let batch_deadline = timeouts.batch.map(|timeout| TokioInstant::now() + timeout);
         let cred_list = Arc::new(self.credentials.clone());
-        let list_len = cred_list.len();
-        let semaphore = Arc::new(Semaphore::new(semaphore_size));
-        let current_index = Arc::new(AtomicUsize::new(0));
-        let tasks = document_urls.into_iter().map(|url| {
-            let client = client.clone();
-            let cred_list_clone = cred_list.clone();
-            let index_counter = current_index.clone();
-            let model_id_str = model_id.to_string();
-            let features = features.clone();
-            let semaphore = semaphore.clone();
-            let output_format = output_format.to_owned();
-
-            tokio::spawn(async move {
-                let _permit = semaphore.acquire().await.unwrap();
-                let old_index = index_counter.fetch_add(1, Ordering::Relaxed);
-                let actual_index = old_index % list_len;
-                let creds = cred_list_clone[actual_index].clone();
-
-                analyze_document_from_urls(&client, &model_id_str, creds, &url, &output_format, &features).await
-            })
-        });
+        let count = inputs.len();
+
+        let mut queued: Vec<(usize, I, DocumentStateTracker)> = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(index, input)| (index, input, DocumentStateTracker::new(self.gauges.clone())))
+            .collect();
+        if let Some(priorities) = &priorities {
+            // Stable sort: equal priorities keep their input order.
+            queued.sort_by_key(|(index, _, _)| Reverse(priorities.get(*index).copied().unwrap_or(0)));
+        }
+        let queue = Mutex::new(VecDeque::from(queued));
+        let outcomes: Mutex<Vec<Option<(TaskOutcome, DocumentMetrics)>>> =
+            Mutex::new((0..count).map(|_| None).collect());
+        let current_index = AtomicUsize::new(0);
+
+        let workers = (0..semaphore_size.clamp(1, count.max(1))).map(|_| async {
+            while !self.shutdown.is_closing() {
+                let next = queue.lock().unwrap().pop_front();
+                let Some((index, input, tracker)) = next else {
+                    break;
+                };
+                let _slot = ActiveSlot::hold(if input.is_url() {
+                    &self.active_permits_url
+                } else {
+                    &self.active_permits_file
+                });
+                self.total_requests.fetch_add(1, Ordering::Relaxed);
+                let client = client.clone();
+                let cred_list_clone = cred_list.clone();
+                let router = self.router.clone();
+                let rate_limiters = self.rate_limiters.clone();
+                let analyze = analyze.clone();
+                let counters = tracker.counters.clone();
+                let started = Instant::now();
+                let ticket = current_index.fetch_add(1, Ordering::Relaxed);
+                let source = checkpoint.as_ref().map(|_| input.checkpoint_key().into_owned());
+                let batch_timeout = move || -> anyhow::Error {
+                    DocumentAnalysisError::PollingTimeout(format!(
+                        "Batch timed out after {:?}",
+                        timeouts.batch.unwrap_or_default()
+                    ))
+                    .into()
+                };
 
-        let results = join_all(tasks).await;
+                let task = AbortOnDrop(tokio::spawn(with_deadline(
+                    batch_deadline,
+                    async move {
+                        let actual_index = router.select(ticket);
+                        // Throttling by a shared rate limiter is not submission latency.
+                        if let Some(bucket) = rate_limiters.get(actual_index) {
+                            bucket.acquire().await;
+                        }
+                        let timeout_source = timeouts.document.map(|_| redact_url(&input.source()));
+                        let document_deadline = timeouts.document.map(|timeout| TokioInstant::now() + timeout);
 
-        results
+                        // A document rejected with 401/403 moves on to the next
+                        // credential that is not skipped, so it fails only when
+                        // every credential has rejected it or is skipped.
+                        let analyze_with_failover = async move {
+                            let (mut input, mut tracker) = (input, tracker);
+                            let mut index = actual_index;
+                            let mut tried = vec![index];
+                            loop {
+                                // The deadline timer is only checked once this future
+                                // yields, so a document dequeued or failing over after
+                                // the deadline would otherwise still be submitted.
+                                if batch_deadline.is_some_and(|deadline| TokioInstant::now() >= deadline) {
+                                    return Err(batch_timeout());
+                                }
+                                tracker.enter(DocumentState::Submitting);
+                                tracker.route_to(router.clone(), index);
+                                // Only a document that can still fail over keeps a copy of its input.
+                                let spare = (tried.len() < cred_list_clone.len()).then(|| input.clone());
+                                let creds = cred_list_clone[index].clone();
+
+                                let job = DocumentJob { client: client.clone(), creds, input, polling, tracker };
+                                let (result, returned) = analyze(job).await;
+                                tracker = returned;
+                                match auth_outcome(&result) {
+                                    AuthOutcome::Rejected(message) => router.record_auth_failure(index, message),
+                                    AuthOutcome::Accepted => {
+                                        router.record_success(index);
+                                        return result;
+                                    }
+                                    AuthOutcome::Unknown => return result,
+                                }
+                                let Some(spare) = spare else {
+                                    return result;
+                                };
+                                let Some(next) = router.failover(ticket, &tried) else {
+                                    return result;
+                                };
+                                warn!(
+                                    source = %spare.source(),
+                                    credential_index = next,
+                                    "Credential rejected the document, retrying on another credential"
+                                );
+                                if let Some(bucket) = rate_limiters.get(next) {
+                                    bucket.acquire().await;
+                                }
+                                index = next;
+                                tried.push(next);
+                                input = spare;
+                                tracker.end_failed_attempt();
+                            }
+                        };
+                        with_deadline(
+                            document_deadline,
+                            analyze_with_failover,
+                            || {
+                                DocumentAnalysisError::PollingTimeout(format!(
+                                    "Document {} timed out after {:?}",
+                                    timeout_source.unwrap_or_default(),
+                                    timeouts.document.unwrap_or_default()
+                                ))
+                                .into()
+                            },
+                        )
+                        .await
+                    },
+                    batch_timeout,
+                )));
+                let outcome = match future::select(task, pin!(self.shutdown.cancelled())).await {
+                    Either::Left((outcome, _)) => outcome,
+                    Either::Right(((), task)) => {
+                        task.abort();
+                        task.await
+                    }
+                };
+                if let (Some(checkpoint), Some(source)) = (&checkpoint, &source) {
+                    let recorded = match &outcome {
+                        Ok(Ok(raw)) => Ok(&**raw),
+                        Ok(Err(err)) => Err(err.to_string()),
+                        Err(join_err) => Err(join_failure(join_err).to_string()),
+                    };
+                    checkpoint.record(source, recorded).await;
+                }
+                if !matches!(outcome, Ok(Ok(_))) {
+                    self.total_errors.fetch_add(1, Ordering::Relaxed);
+                }
+                let metrics = DocumentMetrics {
+                    elapsed: Some(started.elapsed()),
+                    throttled: counters.throttled.load(Ordering::Relaxed),
+                    endpoint: counters.credential().map(|index| cred_list[index].endpoint.clone()),
+                };
+                outcomes.lock().unwrap()[index] = Some((outcome, metrics));
+            }
+        });
+        join_all(workers).await;
+
+        outcomes
+            .into_inner()
+            .unwrap()
             .into_iter()
-            .map(|join_result| match join_result {
-                Err(join_err) => Err(format!("Task panicked: {}", join_err)),
-                Ok(api_result) => match api_result {
-                    Ok(analysis) => Ok(analysis),
-                    Err(api_err) => Err(format!("API Error: {}", api_err)),
-                },
+            .map(|outcome| {
+                outcome.unwrap_or_else(|| {
+                    let outcome = Ok(Err(anyhow::anyhow!("Client closed before the document was submitted")));
+                    (outcome, DocumentMetrics::default())
+                })
             })
             .collect()
     }
 
-    pub async fn process_documents_async_from_file_paths(
+    /// Analyzes one document as several `pages` ranges spread over the credential
+    /// pool and merges the partial results.
+    ///
+    /// With `total_pages` unknown, ranges are submitted in waves of one per
+    /// credential until a range comes back short, or a range past the first is
+    /// rejected by the service (past the end of the document).
+    pub async fn analyze_document_paged_async(
         &self,
-        model_id: &str,
-        file_paths: Vec<String>,
-        features: Option<Vec<String>>,
-        output_format: &str,
-        semaphore_size: usize,
-    ) -> Vec<Result<Value, String>> {
-        let client = Client::new();
-        let semaphore = Arc::new(Semaphore::new(semaphore_size));
-        let cred_list = Arc::new(self.credentials.clone());
-        let current_index = Arc::new(AtomicUsize::new(0));
-        let list_len = cred_list.len();
-        let tasks = file_paths.into_iter().map(|url| {
-            let client = client.clone();
-            let cred_list_clone = cred_list.clone();
-            let index_counter = current_index.clone();
-            let model_id_str = model_id.to_string();
-            let features = features.clone();
-            let semaphore = semaphore.clone();
-            let output_format = output_format.to_owned();
-
-            tokio::spawn(async move {
-                let _permit = semaphore.acquire().await.unwrap();
-                let old_index = index_counter.fetch_add(1, Ordering::Relaxed);
-                let actual_index = old_index % list_len;
-                let creds = cred_list_clone[actual_index].clone();
-                analyze_document_from_file_path(&client, &model_id_str, creds, &url, &output_format, &features)
-                    .await
-            })
-        });
+        source: PagedSource,
+        settings: BatchSettings,
+        pages_per_request: usize,
+        total_pages: Option<usize>,
+        max_retries: u32,
+    ) -> anyhow::Result<(Value, Vec<PageRangeAnalysis>)> {
+        let wave_size = match total_pages {
+            Some(total) => total.div_ceil(pages_per_request),
+            None => self.credentials.len().max(1),
+        };
+        let mut parts = Vec::new();
+        let mut page_ranges = Vec::new();
+        let mut next_start = 1;
+
+        loop {
+            let wave: Vec<(usize, usize)> = (0..wave_size)
+                .map(|i| {
+                    let start = next_start + i * pages_per_request;
+                    let end = start + pages_per_request - 1;
+                    (start, total_pages.map_or(end, |total| end.min(total)))
+                })
+                .collect();
+            next_start += wave_size * pages_per_request;
+
+            let outcomes = self
+                .analyze_page_ranges(&source, &wave, &settings, max_retries)
+                .await;
+            let mut reached_end = total_pages.is_some();
+            for ((start, end), (result, attempts)) in wave.into_iter().zip(outcomes) {
+                let pages = format!("{}-{}", start, end);
+                match result {
+                    Ok(part) => {
+                        let page_count = part["pages"].as_array().map_or(0, Vec::len);
+                        page_ranges.push(PageRangeAnalysis { pages, page_count, attempts });
+                        parts.push(part);
+                        if page_count < end - start + 1 {
+                            reached_end = true;
+                            break;
+                        }
+                    }
+                    Err(err) if total_pages.is_none() && start > 1 && is_client_error(&err) => {
+                        reached_end = true;
+                        break;
+                    }
+                    Err(err) => {
+                        return Err(anyhow::anyhow!(
+                            "Pages {} failed after {} attempt(s): {}",
+                            pages,
+                            attempts,
+                            err
+                        ));
+                    }
+                }
+            }
+            if reached_end {
+                break;
+            }
+        }
+
+        Ok((merge_analyze_results(parts), page_ranges))
+    }
 
-        let results = join_all(tasks).await;
+    /// Submits each page range of `source`, resubmitting only the ranges that
+    /// failed with a retryable error, up to `max_retries` times. Returns each
+    /// range's final result and the number of attempts it took.
+    async fn analyze_page_ranges(
+        &self,
+        source: &PagedSource,
+        ranges: &[(usize, usize)],
+        settings: &BatchSettings,
+        max_retries: u32,
+    ) -> Vec<(anyhow::Result<Value>, u32)> {
+        let mut outcomes: Vec<Option<(anyhow::Result<Value>, u32)>> =
+            ranges.iter().map(|_| None).collect();
+        let mut pending: Vec<usize> = (0..ranges.len()).collect();
+        let mut attempts = 0;
+
+        while !pending.is_empty() {
+            attempts += 1;
+            let inputs = pending
+                .iter()
+                .map(|&i| (format!("{}-{}", ranges[i].0, ranges[i].1), source.clone()))
+                .collect();
+            let options = Arc::new(settings.options.clone());
+            let results = self
+                .run_batch(inputs, settings.clone(), move |job: DocumentJob<(String, PagedSource)>| {
+                    let options = options.clone();
+                    async move {
+                        let DocumentJob { client, creds, input: (pages, source), polling, mut tracker } = job;
+                        let options = AnalyzeOptions { pages: Some(pages), ..(*options).clone() };
+                        let result = match source {
+                            PagedSource::Url(url) => {
+                                analyze_document_from_urls(&client, &options, creds, &url, &polling, &mut tracker).await
+                            }
+                            PagedSource::Bytes(document) => {
+                                analyze_document_from_bytes(&client, &options, creds, document, &polling, &mut tracker).await
+                            }
+                        };
+                        (result, tracker)
+                    }
+                })
+                .await;
 
-        results
+            let mut retry = Vec::new();
+            for (index, (outcome, _)) in pending.into_iter().zip(results) {
+                let result = match outcome {
+                    Ok(Ok(raw)) => serde_json::from_str::<Value>(raw.get()).map_err(anyhow::Error::from),
+                    Ok(Err(err)) => Err(err),
+                    Err(join_err) => Err(join_failure(&join_err).into()),
+                };
+                if let Err(err) = &result
+                    && attempts <= max_retries
+                    && !is_client_error(err)
+                {
+                    warn!(
+                        pages = format!("{}-{}", ranges[index].0, ranges[index].1),
+                        attempt = attempts,
+                        error = %err,
+                        "Page range failed, retrying"
+                    );
+                    retry.push(index);
+                }
+                outcomes[index] = Some((result, attempts));
+            }
+            pending = retry;
+        }
+
+        outcomes
             .into_iter()
-            .map(|join_result| match join_result {
-                Err(join_err) => Err(format!("Task panicked: {}", join_err)),
-                Ok(api_result) => match api_result {
-                    Ok(analysis) => Ok(analysis),
-                    Err(api_err) => Err(format!("API Error: {}", api_err)),
-                },
-            })
+            .map(|outcome| outcome.expect("every page range has an outcome"))
+            .collect()
+    }
+}
+
+/// Result of one batch task: the join error if it panicked or was aborted,
+/// otherwise the outcome of the analyze call.
+type TaskOutcome = Result<anyhow::Result<Box<RawValue>>, JoinError>;
+
+/// The failure for a task that did not run to completion.
+fn join_failure(join_err: &JoinError) -> DocumentAnalysisError {
+    if join_err.is_cancelled() {
+        DocumentAnalysisError::Cancelled("Document was cancelled because the client was closed".to_string())
+    } else {
+        DocumentAnalysisError::TaskPanic(join_err.to_string())
+    }
+}
+
+/// Flattens task outcomes into the failures surfaced to Python.
+fn flatten_results(outcomes: Vec<(TaskOutcome, DocumentMetrics)>) -> Vec<DocumentOutcome> {
+    outcomes
+        .into_iter()
+        .map(|(join_result, metrics)| {
+            let result = match join_result {
+                Err(join_err) => Err(join_failure(&join_err)),
+                Ok(api_result) => api_result.map_err(DocumentAnalysisError::from_error),
+            };
+            DocumentOutcome { result, metrics }
+        })
+        .collect()
+}
+
+/// Whether `err` is a 4xx response other than 429, i.e. not worth retrying.
+fn is_client_error(err: &anyhow::Error) -> bool {
+    let status = match err.downcast_ref::<DocumentAnalysisError>() {
+        Some(DocumentAnalysisError::HttpError { status_code, .. }) => {
+            StatusCode::from_u16(*status_code).ok()
+        }
+        _ => err.downcast_ref::<reqwest::Error>().and_then(reqwest::Error::status),
+    };
+    status.is_some_and(|status| status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::routing::{AuthHealthPolicy, RoutingMode};
+    use crate::test_support::{ClientOptions, MockServer, Response, client, credentials, settings};
+    use pyo3::types::PyList;
+
+    fn snapshot(polling: usize) -> GaugeSnapshot {
+        GaugeSnapshot { waiting: 0, submitting: 0, polling }
+    }
+
+    #[test]
+    fn state_notifier_delivers_a_trailing_call_with_the_latest_counts() {
+        Python::initialize();
+        let calls = Python::attach(|py| PyList::empty(py).unbind());
+        let append = Python::attach(|py| calls.getattr(py, "append").unwrap());
+        let notifier = StateNotifier::new(append, 5.0);
+
+        notifier.fire(snapshot(1));
+        notifier.fire(snapshot(2));
+        notifier.fire(snapshot(3));
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let polled: Vec<usize> = loop {
+            let polled = Python::attach(|py| {
+                calls
+                    .bind(py)
+                    .iter()
+                    .map(|dict| dict.get_item("polling").unwrap().extract().unwrap())
+                    .collect::<Vec<usize>>()
+            });
+            if polled.len() >= 2 || Instant::now() > deadline {
+                break polled;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        assert_eq!(polled, vec![1, 3]);
+    }
+
+    fn router(credentials: usize) -> Arc<CredentialRouter> {
+        let health = AuthHealthPolicy { failure_threshold: 3, probe_interval: Duration::from_secs(60) };
+        Arc::new(CredentialRouter::new(vec![1; credentials], RoutingMode::LatencyAware, 0.2, health))
+    }
+
+    fn latencies(router: &CredentialRouter) -> Vec<Option<f64>> {
+        router
+            .stats(["a", "b"].into_iter().map(String::from))
+            .into_iter()
+            .map(|stat| stat.latency_ms)
+            .collect()
+    }
+
+    #[test]
+    fn failed_submission_is_recorded_as_slow() {
+        let router = router(2);
+        let gauges = Arc::new(InFlightGauges::default());
+
+        let mut succeeded = DocumentStateTracker::new(gauges.clone());
+        succeeded.enter(DocumentState::Submitting);
+        succeeded.route_to(router.clone(), 0);
+        succeeded.begin_request();
+        succeeded.enter(DocumentState::Polling);
+        drop(succeeded);
+
+        let mut failed = DocumentStateTracker::new(gauges.clone());
+        failed.enter(DocumentState::Submitting);
+        failed.route_to(router.clone(), 1);
+        failed.begin_request();
+        drop(failed);
+
+        let [fast, slow] = latencies(&router)[..] else { unreachable!() };
+        assert!(fast.unwrap() < 1_000.0);
+        assert!(slow.unwrap() >= 10_000.0);
+        assert_eq!(gauges.snapshot().submitting, 0);
+    }
+
+    /// Accepts every analyze request, delaying the one for `slow_url`, and
+    /// reports every operation as succeeded.
+    fn analysis_server(slow_url: &'static str, delay: Duration) -> MockServer {
+        MockServer::start(move |request| {
+            if request.method == "POST" {
+                let response = Response::accepted(request, "/operations/1");
+                match request.url_source() {
+                    Some(url) if url == slow_url => response.delay(delay),
+                    _ => response,
+                }
+            } else {
+                Response::succeeded(serde_json::json!({ "content": "" }))
+            }
+        })
+    }
+
+    fn submitted(server: &MockServer) -> Vec<String> {
+        server
+            .requests()
+            .into_iter()
+            .filter(|request| request.method == "POST")
+            .filter_map(|request| request.url_source())
             .collect()
     }
+
+    #[test]
+    fn higher_priority_documents_are_submitted_first() {
+        let server = analysis_server("", Duration::ZERO);
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+        let urls: Vec<String> = ["https://x/low", "https://x/high", "https://x/mid"].map(String::from).into();
+        let settings = BatchSettings { priorities: Some(vec![0, 5, 1]), ..settings(1) };
+
+        let results = client
+            .block_on(client.process_documents_async_from_urls(urls, settings))
+            .unwrap();
+
+        assert!(results.iter().all(|outcome| outcome.result.is_ok()));
+        assert_eq!(submitted(&server), ["https://x/high", "https://x/mid", "https://x/low"]);
+    }
+
+    #[test]
+    fn weighted_credentials_take_proportional_shares_of_a_batch() {
+        let heavy = analysis_server("", Duration::ZERO);
+        let light = analysis_server("", Duration::ZERO);
+        let client = client(vec![credentials(&heavy.url, 3), credentials(&light.url, 1)], ClientOptions::default());
+        let urls: Vec<String> = (0..8).map(|i| format!("https://x/{}.pdf", i)).collect();
+
+        let results = client
+            .block_on(client.process_documents_async_from_urls(urls, settings(2)))
+            .unwrap();
+
+        assert!(results.iter().all(|outcome| outcome.result.is_ok()));
+        assert_eq!((submitted(&heavy).len(), submitted(&light).len()), (6, 2));
+        let endpoints: Vec<&str> = results.iter().map(|outcome| outcome.metrics.endpoint.as_deref().unwrap()).collect();
+        assert_eq!(endpoints.iter().filter(|&&endpoint| endpoint == light.url).count(), 2);
+    }
+
+    fn rejecting_server() -> MockServer {
+        MockServer::start(|_| {
+            Response::json(401, serde_json::json!({
+                "error": { "code": "401", "message": "Access denied due to invalid subscription key." }
+            }))
+        })
+    }
+
+    #[test]
+    fn documents_fail_over_from_a_rejected_credential_and_stop_using_it() {
+        let (rotated, valid) = (rejecting_server(), analysis_server("", Duration::ZERO));
+        let options = ClientOptions { auth_failure_threshold: 1, ..ClientOptions::default() };
+        let client = client(vec![credentials(&rotated.url, 1), credentials(&valid.url, 1)], options);
+        let urls: Vec<String> = (0..4).map(|i| format!("https://x/{}.pdf", i)).collect();
+
+        let results = client
+            .block_on(client.process_documents_async_from_urls(urls, settings(1)))
+            .unwrap();
+
+        for outcome in &results {
+            assert!(outcome.result.is_ok(), "{:?}", outcome.result);
+            assert_eq!(outcome.metrics.endpoint.as_deref(), Some(valid.url.as_str()));
+        }
+        assert_eq!(rotated.requests().len(), 1);
+        assert_eq!(submitted(&valid).len(), 4);
+        let health = client.router.health([rotated.url.clone(), valid.url.clone()].into_iter());
+        assert!(!health[0].healthy && health[1].healthy);
+        let in_flight = client.gauges.snapshot();
+        assert_eq!((in_flight.waiting, in_flight.submitting, in_flight.polling), (0, 0, 0));
+    }
+
+    #[test]
+    fn documents_fail_once_every_credential_rejects_them() {
+        let (first, second) = (rejecting_server(), rejecting_server());
+        let options = ClientOptions { auth_failure_threshold: 1, ..ClientOptions::default() };
+        let client = client(vec![credentials(&first.url, 1), credentials(&second.url, 1)], options);
+        let urls: Vec<String> = (0..3).map(|i| format!("https://x/{}.pdf", i)).collect();
+
+        let results = client
+            .block_on(client.process_documents_async_from_urls(urls, settings(1)))
+            .unwrap();
+
+        for outcome in &results {
+            assert!(
+                matches!(&outcome.result, Err(DocumentAnalysisError::HttpError { status_code: 401, .. })),
+                "{:?}",
+                outcome.result
+            );
+        }
+        // The first document tries both credentials; after that both are skipped
+        // and each document fails on the credential it was routed to.
+        assert_eq!(first.requests().len() + second.requests().len(), 4);
+    }
+
+    #[test]
+    fn documents_dequeued_after_the_batch_deadline_are_not_submitted() {
+        let server = analysis_server("https://x/first", Duration::from_millis(600));
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+        let urls: Vec<String> = ["https://x/first", "https://x/second", "https://x/third"].map(String::from).into();
+        let mut settings = settings(1);
+        settings.timeouts.batch = Some(Duration::from_millis(200));
+
+        let results = client
+            .block_on(client.process_documents_async_from_urls(urls, settings))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+
+        for outcome in &results {
+            assert!(matches!(&outcome.result, Err(DocumentAnalysisError::PollingTimeout(message)) if message.starts_with("Batch timed out")));
+        }
+        assert_eq!(submitted(&server), ["https://x/first"]);
+    }
+
+    #[test]
+    fn document_timeouts_name_the_url_without_its_sas_token() {
+        let url = "https://blob.example.com/docs/slow.pdf?sv=2024-01-01&sig=secret";
+        let server = analysis_server(url, Duration::from_millis(500));
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+        let mut settings = settings(1);
+        settings.timeouts.document = Some(Duration::from_millis(100));
+
+        let results = client
+            .block_on(client.process_documents_async_from_urls(vec![url.to_string()], settings))
+            .unwrap();
+
+        let Err(DocumentAnalysisError::PollingTimeout(message)) = &results[0].result else {
+            panic!("expected a timeout, got {:?}", results[0].result);
+        };
+        assert!(message.starts_with("Document https://blob.example.com/docs/slow.pdf?<redacted> timed out"), "{}", message);
+        assert!(!message.contains("secret"), "{}", message);
+    }
+
+    #[test]
+    fn truncated_tiffs_fail_validation_without_being_submitted() {
+        let server = analysis_server("", Duration::ZERO);
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+        // A little-endian header pointing at an IFD past the end of the data.
+        let truncated = DocumentBytes::from_bytes(Some("scan.tif".to_string()), b"II*\0\x08\0\0\0\x01".to_vec(), None, 0);
+
+        let results = client
+            .block_on(client.process_documents_async_from_bytes(vec![truncated], settings(1)))
+            .unwrap();
+
+        assert!(matches!(&results[0].result, Err(DocumentAnalysisError::InvalidDocument { source, .. }) if source == "scan.tif"));
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn results_keep_input_order_under_random_latencies() {
+        let seed = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos() as u64 | 1;
+        let state = std::sync::Mutex::new(seed);
+        // xorshift64: a different interleaving on every run, reproducible from `seed`.
+        let random_delay = move || {
+            let mut x = state.lock().unwrap();
+            *x ^= *x << 13;
+            *x ^= *x >> 7;
+            *x ^= *x << 17;
+            Duration::from_millis(*x % 60)
+        };
+        let server = MockServer::start(move |request| {
+            let response = if request.method == "POST" {
+                let id = request.url_source().unwrap().rsplit('/').next().unwrap().to_string();
+                Response::accepted(request, &format!("/operations/{}", id))
+            } else {
+                let id = request.target.split('?').next().unwrap().rsplit('/').next().unwrap();
+                Response::succeeded(serde_json::json!({ "content": id }))
+            };
+            response.delay(random_delay())
+        });
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+        let urls: Vec<String> = (0..24).map(|i| format!("https://x/{}", i)).collect();
+
+        let results = client
+            .block_on(client.process_documents_async_from_urls(urls, settings(8)))
+            .unwrap();
+
+        let contents: Vec<String> = results
+            .iter()
+            .map(|outcome| {
+                let raw = outcome.result.as_ref().unwrap();
+                serde_json::from_str::<Value>(raw.get()).unwrap()["content"].as_str().unwrap().to_string()
+            })
+            .collect();
+        let expected: Vec<String> = (0..24).map(|i| i.to_string()).collect();
+        assert_eq!(contents, expected, "seed {}", seed);
+    }
+
+    #[test]
+    fn outcomes_carry_throttles_and_the_endpoint_used() {
+        let throttled = AtomicUsize::new(0);
+        let server = MockServer::start(move |request| {
+            if request.method == "POST" && throttled.fetch_add(1, Ordering::Relaxed) == 0 {
+                Response::new(429).header("retry-after", "0")
+            } else if request.method == "POST" {
+                Response::accepted(request, "/operations/1")
+            } else {
+                Response::succeeded(serde_json::json!({ "content": "" }))
+            }
+        });
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+
+        let results = client
+            .block_on(client.process_documents_async_from_urls(vec!["https://x/a.pdf".to_string()], settings(1)))
+            .unwrap();
+
+        let metrics = &results[0].metrics;
+        assert!(results[0].result.is_ok());
+        assert_eq!(metrics.throttled, 1);
+        assert_eq!(metrics.endpoint.as_deref(), Some(server.url.as_str()));
+        assert!(metrics.elapsed.is_some());
+    }
+
+    #[test]
+    fn submissions_are_retried_after_two_429s() {
+        let posts = AtomicUsize::new(0);
+        let server = MockServer::start(move |request| {
+            if request.method != "POST" {
+                Response::succeeded(serde_json::json!({ "content": "done" }))
+            } else if posts.fetch_add(1, Ordering::Relaxed) < 2 {
+                Response::json(429, serde_json::json!({ "error": { "code": "429", "message": "Rate limit exceeded" } }))
+            } else {
+                Response::accepted(request, "/operations/1")
+            }
+        });
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+
+        let results = client
+            .block_on(client.process_documents_async_from_urls(vec!["https://x/a.pdf".to_string()], settings(1)))
+            .unwrap();
+
+        assert!(results[0].result.is_ok(), "{:?}", results[0].result);
+        assert_eq!(results[0].metrics.throttled, 2);
+        let posts: Vec<_> = server.requests().into_iter().filter(|request| request.method == "POST").collect();
+        assert_eq!(posts.len(), 3);
+        // Without Retry-After the retries back off from `initial_backoff` (10 ms).
+        for pair in posts.windows(2) {
+            assert!(pair[1].received_at - pair[0].received_at >= Duration::from_millis(5));
+        }
+        assert!(posts.iter().all(|post| post.header("ocp-apim-subscription-key") == Some("test-key")));
+    }
+
+    /// The text and `error_code` of the Python exception for a failed outcome.
+    fn exception(outcome: DocumentOutcome) -> (String, Option<String>) {
+        let failure = outcome.result.expect_err("expected a failure");
+        Python::initialize();
+        Python::attach(|py| {
+            let error = failure.into_exception(py).unwrap();
+            let error = error.bind(py);
+            (error.str().unwrap().to_string(), error.getattr("error_code").unwrap().extract().unwrap())
+        })
+    }
+
+    #[test]
+    fn service_error_messages_reach_the_python_exception() {
+        let error = serde_json::json!({
+            "code": "InvalidRequest",
+            "message": "Invalid request.",
+            "innererror": { "code": "InvalidContent", "message": "The file is corrupted or format is unsupported." }
+        });
+        let status_error = error.clone();
+        let server = MockServer::start(move |request| match request.url_source().as_deref() {
+            Some("https://x/rejected.pdf") => Response::json(400, serde_json::json!({ "error": error })),
+            Some(_) => Response::accepted(request, "/operations/1"),
+            None => Response::json(200, serde_json::json!({ "status": "failed", "error": status_error })),
+        });
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+        let urls = vec!["https://x/rejected.pdf".to_string(), "https://x/failed.pdf".to_string()];
+
+        let mut results = client
+            .block_on(client.process_documents_async_from_urls(urls, settings(1)))
+            .unwrap()
+            .into_iter();
+
+        for outcome in [results.next().unwrap(), results.next().unwrap()] {
+            let (message, error_code) = exception(outcome);
+            assert!(message.contains("The file is corrupted or format is unsupported."), "{}", message);
+            assert_eq!(error_code.as_deref(), Some("InvalidContent"));
+        }
+    }
+
+    #[test]
+    fn oversized_error_bodies_are_not_read() {
+        let message = "x".repeat(100 * 1024);
+        let server = MockServer::start(move |_| {
+            Response::json(400, serde_json::json!({ "error": { "code": "InvalidRequest", "message": message } }))
+        });
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+
+        let results = client
+            .block_on(client.process_documents_async_from_urls(vec!["https://x/a.pdf".to_string()], settings(1)))
+            .unwrap();
+
+        let (message, error_code) = exception(results.into_iter().next().unwrap());
+        assert_eq!(message, "API Error: HTTP 400 Bad Request");
+        assert_eq!(error_code, None);
+    }
 }
@@ -0,0 +1,196 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use tokio::{io::AsyncWriteExt, sync::Mutex};
+use tracing::warn;
+
+use crate::models::AnalyzeOptions;
+use crate::utils::long_path;
+
+/// One line of a checkpoint file.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CheckpointEntry {
+    /// Stable hash of the analyze options and the document source (URL, path,
+    /// or name and content of an in-memory document).
+    pub source: String,
+    /// `"ok"` or `"error"`.
+    pub status: String,
+    /// Where the raw result JSON was written, when an output directory is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// An append-only JSON Lines log of finished documents, used to skip documents
+/// that already succeeded when a batch is re-run.
+///
+/// Entries are keyed by the analyze options as well as the source, so re-running
+/// a checkpoint with another model, features or query options resubmits every
+/// document. Lines that fail to parse (e.g. a final line truncated by a crash)
+/// are ignored.
+pub struct Checkpoint {
+    output_dir: Option<PathBuf>,
+    scope: String,
+    completed: HashMap<String, CheckpointEntry>,
+    file: Mutex<tokio::fs::File>,
+}
+
+impl Checkpoint {
+    pub fn open(path: &Path, output_dir: Option<PathBuf>, options: &AnalyzeOptions) -> anyhow::Result<Self> {
+        if let Some(dir) = &output_dir {
+            fs::create_dir_all(long_path(dir))
+                .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(long_path(path))
+            .map_err(|e| anyhow::anyhow!("Failed to open checkpoint {}: {}", path.display(), e))?;
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        let mut completed = HashMap::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str::<CheckpointEntry>(line) {
+                Ok(entry) if entry.status == "ok" => {
+                    completed.insert(entry.source.clone(), entry);
+                }
+                Ok(_) => {}
+                Err(_) => warn!(checkpoint = %path.display(), "Ignoring unreadable checkpoint line"),
+            }
+        }
+        // Terminate a line truncated mid-write so the next entry starts cleanly.
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            file.seek(SeekFrom::End(0))?;
+            file.write_all(b"\n")?;
+        }
+
+        Ok(Self {
+            output_dir,
+            scope: options_scope(options),
+            completed,
+            file: Mutex::new(tokio::fs::File::from_std(file)),
+        })
+    }
+
+    /// The successful entry for `source` from a previous run with the same
+    /// options, if any.
+    pub fn completed(&self, source: &str) -> Option<&CheckpointEntry> {
+        self.completed.get(&self.key(source))
+    }
+
+    /// Appends the outcome for `source`, first writing the result JSON to the
+    /// output directory if one is set.
+    pub async fn record(&self, source: &str, outcome: Result<&RawValue, String>) {
+        let source = self.key(source);
+        let entry = match outcome {
+            Ok(result) => {
+                let result_path = match &self.output_dir {
+                    Some(dir) => {
+                        let path = dir.join(format!("{}.json", source));
+                        match tokio::fs::write(long_path(&path), result.get().to_owned()).await {
+                            Ok(()) => Some(path.to_string_lossy().into_owned()),
+                            Err(err) => {
+                                warn!(path = %path.display(), error = %err, "Failed to write checkpointed result");
+                                None
+                            }
+                        }
+                    }
+                    None => None,
+                };
+                CheckpointEntry { source, status: "ok".to_string(), result_path, error: None }
+            }
+            Err(error) => CheckpointEntry { source, status: "error".to_string(), result_path: None, error: Some(error) },
+        };
+
+        let Ok(mut line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        line.push('\n');
+        let mut file = self.file.lock().await;
+        if let Err(err) = async { file.write_all(line.as_bytes()).await?; file.flush().await }.await {
+            warn!(error = %err, "Failed to append to checkpoint");
+        }
+    }
+
+    fn key(&self, source: &str) -> String {
+        source_hash(&format!("{}\n{}", self.scope, source))
+    }
+}
+
+/// The request options a checkpointed result depends on, in a canonical form.
+fn options_scope(options: &AnalyzeOptions) -> String {
+    let sorted = |values: &Option<Vec<String>>| {
+        values.clone().map(|mut values| {
+            values.sort();
+            values
+        })
+    };
+    serde_json::json!([
+        options.model_id,
+        sorted(&options.features),
+        options.output_format,
+        options.pages,
+        options.locale,
+        sorted(&options.query_fields),
+    ])
+    .to_string()
+}
+
+/// FNV-1a hash of a source, stable across runs and Rust versions.
+pub fn source_hash(source: &str) -> String {
+    format!("{:016x}", fnv1a(source.as_bytes()))
+}
+
+/// 64-bit FNV-1a of `bytes`.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(model_id: &str, features: &[&str]) -> AnalyzeOptions {
+        AnalyzeOptions {
+            model_id: model_id.to_string(),
+            features: Some(features.iter().map(|feature| feature.to_string()).collect()),
+            output_format: None,
+            pages: None,
+            locale: None,
+            query_fields: None,
+        }
+    }
+
+    #[test]
+    fn completed_documents_are_scoped_to_the_analyze_options() {
+        let path = std::env::temp_dir().join(format!("rusty-di-checkpoint-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let layout = options("prebuilt-layout", &["barcodes", "formulas"]);
+
+        let checkpoint = Checkpoint::open(&path, None, &layout).unwrap();
+        let result = RawValue::from_string("{}".to_string()).unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(checkpoint.record("https://x/a.pdf", Ok(&result)));
+        runtime.block_on(checkpoint.record("https://x/b.pdf", Err("boom".to_string())));
+        drop(checkpoint);
+
+        let reordered = Checkpoint::open(&path, None, &options("prebuilt-layout", &["formulas", "barcodes"])).unwrap();
+        assert!(reordered.completed("https://x/a.pdf").is_some());
+        assert!(reordered.completed("https://x/b.pdf").is_none());
+        for other in [options("prebuilt-read", &["barcodes", "formulas"]), options("prebuilt-layout", &["barcodes"])] {
+            assert!(Checkpoint::open(&path, None, &other).unwrap().completed("https://x/a.pdf").is_none());
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,145 +1,166 @@
+use futures::StreamExt;
 use reqwest::{
-    Client,
-    header::{CONTENT_TYPE, HeaderValue},
+    Client, RequestBuilder, Response, StatusCode, Url,
+    header::{AUTHORIZATION, CONTENT_TYPE, HeaderName, HeaderValue},
 };
 use secrecy::ExposeSecret;
-use serde_json::Value;
-use std::{path::Path, time::Duration};
+use serde_json::value::RawValue;
+use std::{io::Cursor, path::Path, time::Duration};
 use tokio::{fs::File, io::AsyncReadExt};
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::models::{StatusResponse, credentials::Credentials};
-use crate::utils::get_content_type;
+use crate::clients::base::{DocumentState, DocumentStateTracker};
+use crate::models::credentials::CredentialKind;
+use crate::models::{
+    AnalyzeOptions, CredentialCheck, DocumentBytes, ErrorResponse, FileReadRetry, HttpRetry,
+    PollingConfig, StatusResponse, credentials::Credentials, errors::DocumentAnalysisError,
+};
+use crate::utils::{get_content_type, long_path, parse_retry_after, tiff::count_tiff_pages};
+
+pub(crate) const API_VERSION: &str = "2024-11-30";
+/// Largest error response body read for its error object, in bytes.
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
 
 pub async fn analyze_document_from_urls(
     client: &Client,
-    model_id: &str,
+    options: &AnalyzeOptions,
     creds: Credentials,
     document_url: &str,
-    output_format: &str,
-    features: &Option<Vec<String>>,
-) -> anyhow::Result<Value> {
-    let endpoint = creds.endpoint.trim_end_matches('/');
+    polling: &PollingConfig,
+    tracker: &mut DocumentStateTracker,
+) -> anyhow::Result<Box<RawValue>> {
+    let analyze_url = analyze_url(&creds, options)?;
+    let auth = auth_header(&creds)?;
 
-    let api_version = "2024-11-30";
-    let mut analyze_url = format!(
-        "{}/documentintelligence/documentModels/{}:analyze?api-version={}&outputContentFormat={}",
-        endpoint, model_id, api_version, output_format
-    );
-    if let Some(feature_list) = features
-        && !feature_list.is_empty()
-    {
-        let features_param = feature_list.join(",");
-        analyze_url.push_str(&format!("&features={}", features_param));
-    }
-
-    let mut api_key_val = HeaderValue::from_str(creds.api_key.expose_secret())?;
-    api_key_val.set_sensitive(true);
-    let auth_header_value = api_key_val;
-
-    let response = client
-        .post(&analyze_url)
+    let request = client
+        .post(analyze_url)
         .header("Content-Type", "application/json")
-        .header("Ocp-Apim-Subscription-Key", auth_header_value.clone())
+        .header(auth.0.clone(), auth.1.clone())
         .json(&serde_json::json!({
             "urlSource": document_url
-        }))
-        .send()
-        .await?
-        .error_for_status()?;
+        }));
+    submit_and_poll(client, request, &auth, polling, tracker, document_url).await
+}
 
-    let operation_location = response
-        .headers()
-        .get("operation-location")
-        .ok_or_else(|| anyhow::anyhow!("Response missing 'operation-location' header"))?
-        .to_str()?;
+pub async fn analyze_document_from_file_path(
+    client: &Client,
+    options: &AnalyzeOptions,
+    creds: Credentials,
+    file_path: &Path,
+    file_read: &FileReadRetry,
+    polling: &PollingConfig,
+    tracker: &mut DocumentStateTracker,
+) -> anyhow::Result<Box<RawValue>> {
+    let file_contents = read_file_with_retry(file_path, file_read, || read_file(file_path)).await?;
+    let file_name = file_path
+        .file_name()
+        .map_or_else(|| file_path.display().to_string(), |name| name.to_string_lossy().into_owned());
+    // Determine content type based on file extension
+    let document = DocumentBytes {
+        name: file_name,
+        data: file_contents.into(),
+        content_type: get_content_type(file_path).to_string(),
+    };
 
-    info!(
-        document_url = document_url,
-        "Operation Location: {}", operation_location
-    );
+    analyze_document_from_bytes(client, options, creds, document, polling, tracker).await
+}
 
+/// Reads a whole file with `read`, retrying transient errors as configured by
+/// `retry`. `file_path` only names the file in logs and errors.
+async fn read_file_with_retry<F, Fut>(
+    file_path: &Path,
+    retry: &FileReadRetry,
+    mut read: F,
+) -> anyhow::Result<Vec<u8>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::io::Result<Vec<u8>>>,
+{
+    let mut attempt = 1;
     loop {
-        tokio::time::sleep(Duration::from_secs(1)).await;
-        let status_response = client
-            .get(operation_location)
-            .header("Ocp-Apim-Subscription-Key", auth_header_value.clone())
-            .send()
-            .await?
-            .error_for_status()?
-            .json::<StatusResponse>()
-            .await?;
-
-        info!(
-            status = status_response.status.as_str(),
-            "Polling status response: {}",
-            status_response.status.as_str()
-        );
-
-        match status_response.status.as_str() {
-            "succeeded" => {
-                return status_response
-                    .result
-                    .ok_or_else(|| anyhow::anyhow!("API succeeded but returned no result"));
+        match read().await {
+            Ok(contents) => return Ok(contents),
+            Err(e) if attempt < retry.attempts && FileReadRetry::is_retryable(&e) => {
+                warn!(
+                    path = %file_path.display(),
+                    attempt,
+                    max_attempts = retry.attempts,
+                    error = %e,
+                    "Transient error reading file, retrying"
+                );
+                attempt += 1;
+                tokio::time::sleep(retry.delay).await;
+            }
+            Err(e) => {
+                return Err(DocumentAnalysisError::FileReadError {
+                    path: file_path.display().to_string(),
+                    message: e.to_string(),
+                }
+                .into());
             }
-            "failed" => return Err(anyhow::anyhow!("Document analysis failed")),
-            "running" | "notStarted" => continue,
-            other => return Err(anyhow::anyhow!("Unknown status: {}", other)),
         }
     }
 }
 
-pub async fn analyze_document_from_file_path(
+async fn read_file(file_path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut file = File::open(long_path(file_path)).await?;
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents).await?;
+    Ok(contents)
+}
+
+pub async fn analyze_document_from_bytes(
     client: &Client,
-    model_id: &str,
+    options: &AnalyzeOptions,
     creds: Credentials,
-    file_path: &str,
-    output_format: &str,
-    features: &Option<Vec<String>>,
-) -> anyhow::Result<Value> {
-    let mut file = File::open(file_path)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to open file {}: {}", file_path, e))?;
-    let mut file_contents = Vec::new();
-    file.read_to_end(&mut file_contents)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to read file {}: {}", file_path, e))?;
-    let file_name = Path::new(file_path).file_name().unwrap().to_str().unwrap();
-    // Determine content type based on file extension
-    let content_type = get_content_type(file_path);
-    let endpoint = creds.endpoint.trim_end_matches('/');
-    let api_version = "2024-11-30";
-    let mut analyze_url = format!(
-        "{}/documentintelligence/documentModels/{}:analyze?api-version={}&outputContentFormat={}",
-        endpoint, model_id, api_version, output_format
-    );
+    document: DocumentBytes,
+    polling: &PollingConfig,
+    tracker: &mut DocumentStateTracker,
+) -> anyhow::Result<Box<RawValue>> {
+    validate_document(&document)?;
+    let analyze_url = analyze_url(&creds, options)?;
+    let auth = auth_header(&creds)?;
+    let DocumentBytes {
+        name,
+        data,
+        content_type,
+    } = document;
 
-    if let Some(feature_list) = features
-        && !feature_list.is_empty()
-    {
-        let features_param = feature_list.join(",");
-        analyze_url.push_str(&format!("&features={}", features_param));
-    }
-
-    let mut api_key_val = HeaderValue::from_str(creds.api_key.expose_secret())?;
-    api_key_val.set_sensitive(true);
-    let auth_header_value = api_key_val;
+    // Send document as binary data
+    let request = client
+        .post(analyze_url)
+        .header(auth.0.clone(), auth.1.clone())
+        .header(CONTENT_TYPE, HeaderValue::from_str(&content_type)?)
+        .body(data);
+    submit_and_poll(client, request, &auth, polling, tracker, &name).await
+}
 
-    // Send file as binary data
-    let response = client
-        .post(&analyze_url)
-        .header("Ocp-Apim-Subscription-Key", auth_header_value.clone())
-        .header(CONTENT_TYPE, HeaderValue::from_static(content_type))
-        .body(file_contents)
-        .send()
-        .await?
-        .error_for_status()?;
+/// Checks a document's structure before it is uploaded, so a corrupt file fails
+/// here instead of wasting a submission. Only TIFFs are checked, by walking
+/// their directory chain.
+fn validate_document(document: &DocumentBytes) -> Result<(), DocumentAnalysisError> {
+    if document.content_type != "image/tiff" {
+        return Ok(());
+    }
+    count_tiff_pages(Cursor::new(&document.data[..])).map(drop).map_err(|e| {
+        DocumentAnalysisError::InvalidDocument { source: document.name.clone(), message: e.to_string() }
+    })
+}
 
-    info!(
-        file_name = file_name,
-        status_code = response.status().as_u16(),
-        "Document analysis request submitted"
-    );
+/// Sends a prepared analyze request and polls the operation it starts until the
+/// result is ready. Every input path (URL, file, bytes) goes through here, so
+/// submission and polling behave the same regardless of how the document is
+/// supplied. `source` names the document in logs and errors.
+async fn submit_and_poll(
+    client: &Client,
+    request: RequestBuilder,
+    auth: &(HeaderName, HeaderValue),
+    polling: &PollingConfig,
+    tracker: &mut DocumentStateTracker,
+    source: &str,
+) -> anyhow::Result<Box<RawValue>> {
+    tracker.begin_request();
+    let response = send_with_retry(request, polling, source, Some(tracker)).await?;
 
     let operation_location = response
         .headers()
@@ -148,29 +169,194 @@ pub async fn analyze_document_from_file_path(
         .to_str()?;
 
     info!(
-        file_name = file_name,
+        source = source,
+        status_code = response.status().as_u16(),
         operation_location = operation_location,
         "Document analysis operation initiated"
     );
 
+    tracker.enter(DocumentState::Polling);
+    poll_operation(client, operation_location, auth, polling, tracker, source).await
+}
+
+/// Issues a cheap authenticated GET against the resource info endpoint to confirm
+/// the credential can reach its endpoint and authenticate. Never fails; the
+/// outcome is reported in the returned [`CredentialCheck`].
+pub async fn check_credentials(
+    client: &Client,
+    creds: &Credentials,
+    timeout: Duration,
+) -> CredentialCheck {
+    let endpoint = creds.endpoint.trim_end_matches('/');
+    let mut check = CredentialCheck {
+        endpoint: endpoint.to_string(),
+        status: "ok",
+        http_status: None,
+        detail: None,
+    };
+
+    let (auth_name, auth_value) = match auth_header(creds) {
+        Ok(value) => value,
+        Err(err) => {
+            check.status = "auth_failed";
+            check.detail = Some(err.to_string());
+            return check;
+        }
+    };
+
+    let info_url = match service_url(endpoint, &["info"], &[]) {
+        Ok(url) => url,
+        Err(err) => {
+            check.status = "error";
+            check.detail = Some(err.to_string());
+            return check;
+        }
+    };
+    let response = client
+        .get(info_url)
+        .header(auth_name, auth_value)
+        .timeout(timeout)
+        .send()
+        .await;
+
+    match response {
+        Err(err) => {
+            check.status = "unreachable";
+            check.detail = Some(err.to_string());
+        }
+        Ok(response) => {
+            let status_code = response.status();
+            check.http_status = Some(status_code.as_u16());
+            check.status = match status_code.as_u16() {
+                200..=299 => "ok",
+                401 | 403 => "auth_failed",
+                429 => "throttled",
+                _ => "error",
+            };
+            if !status_code.is_success() {
+                check.detail = response.text().await.ok().filter(|body| !body.is_empty());
+            }
+        }
+    }
+
+    info!(
+        endpoint = check.endpoint.as_str(),
+        status = check.status,
+        "Credential validation finished"
+    );
+    check
+}
+
+/// Builds the analyze endpoint URL for `options` against the credential's endpoint.
+fn analyze_url(creds: &Credentials, options: &AnalyzeOptions) -> anyhow::Result<Url> {
+    let model_segment = format!("{}:analyze", options.model_id);
+    let mut query = Vec::new();
+    if let Some(format) = &options.output_format {
+        query.push(("outputContentFormat", format.clone()));
+    }
+    if let Some(feature_list) = &options.features
+        && !feature_list.is_empty()
+    {
+        query.push(("features", feature_list.join(",")));
+    }
+    if let Some(pages) = &options.pages {
+        query.push(("pages", pages.clone()));
+    }
+    if let Some(locale) = &options.locale {
+        query.push(("locale", locale.clone()));
+    }
+    if let Some(query_fields) = &options.query_fields {
+        query.push(("queryFields", query_fields.join(",")));
+    }
+    let query: Vec<(&str, &str)> = query.iter().map(|(name, value)| (*name, value.as_str())).collect();
+    service_url(&creds.endpoint, &["documentModels", &model_segment], &query)
+}
+
+/// Builds `<endpoint>/documentintelligence/<segments>?api-version=...&<query>`.
+///
+/// Every path segment and query value is percent-encoded, so reserved characters
+/// in model IDs, locales or feature names cannot change the URL's structure. Each
+/// query parameter may appear only once.
+fn service_url(endpoint: &str, segments: &[&str], query: &[(&str, &str)]) -> anyhow::Result<Url> {
+    let mut url = Url::parse(endpoint.trim_end_matches('/'))
+        .map_err(|e| anyhow::anyhow!("Invalid endpoint {}: {}", endpoint, e))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("Invalid endpoint {}: not a base URL", endpoint))?
+        .pop_if_empty()
+        .push("documentintelligence")
+        .extend(segments);
+
+    let mut seen = vec!["api-version"];
+    for (name, _) in query {
+        if seen.contains(name) {
+            anyhow::bail!("Query parameter '{}' given more than once", name);
+        }
+        seen.push(*name);
+    }
+    url.query_pairs_mut()
+        .append_pair("api-version", API_VERSION)
+        .extend_pairs(query);
+    Ok(url)
+}
+
+/// The header authenticating requests made with `creds`: the subscription key
+/// header for API keys, `Authorization: Bearer` for Entra ID tokens.
+pub(crate) fn auth_header(creds: &Credentials) -> anyhow::Result<(HeaderName, HeaderValue)> {
+    let (name, mut value) = match &creds.kind {
+        CredentialKind::ApiKey(key) => (
+            HeaderName::from_static("ocp-apim-subscription-key"),
+            HeaderValue::from_str(key.expose_secret())?,
+        ),
+        CredentialKind::BearerToken(token) => (
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token.expose_secret()))?,
+        ),
+    };
+    value.set_sensitive(true);
+    Ok((name, value))
+}
+
+/// Polls an analyze operation until it reaches a terminal state.
+///
+/// The service may send a `Retry-After` header on each status response; it is
+/// honored (capped at `polling.max_retry_after`), otherwise the delay follows
+/// `polling`'s exponential backoff with jitter.
+/// Each parsed status is reported through `tracker` to the `on_status` callback.
+async fn poll_operation(
+    client: &Client,
+    operation_location: &str,
+    auth: &(HeaderName, HeaderValue),
+    polling: &PollingConfig,
+    tracker: &DocumentStateTracker,
+    source: &str,
+) -> anyhow::Result<Box<RawValue>> {
+    let mut delay = polling.backoff(1);
+    let mut poll = 0;
     loop {
-        tokio::time::sleep(Duration::from_secs(1)).await;
+        poll += 1;
+        tokio::time::sleep(delay).await;
 
-        let status_response = client
+        let request = client
             .get(operation_location)
-            .header("Ocp-Apim-Subscription-Key", auth_header_value.clone())
-            .send()
-            .await?
-            .error_for_status()?
-            .json::<StatusResponse>()
-            .await?;
+            .header(auth.0.clone(), auth.1.clone());
+        let response = send_with_retry(request, polling, source, Some(tracker)).await?;
+
+        delay = parse_retry_after(response.headers())
+            .map(|retry_after| retry_after.min(polling.max_retry_after))
+            .unwrap_or_else(|| polling.backoff(poll + 1));
+
+        let body = read_body_limited(response, polling.max_result_bytes, source).await?;
+        let status_response = serde_json::from_slice::<StatusResponse>(&body)
+            .map_err(|e| DocumentAnalysisError::DeserializationError(e.to_string()))?;
 
         info!(
-            file_name = file_name,
+            source = source,
             status = status_response.status.as_str(),
+            percent_completed = status_response.percent_completed,
             operation_location = operation_location,
             "Polling document analysis status"
         );
+        tracker.report_status(source, &status_response.status, status_response.percent_completed, poll).await?;
 
         match status_response.status.as_str() {
             "succeeded" => {
@@ -178,9 +364,334 @@ pub async fn analyze_document_from_file_path(
                     .result
                     .ok_or_else(|| anyhow::anyhow!("API succeeded but returned no result"));
             }
-            "failed" => return Err(anyhow::anyhow!("Document analysis failed")),
+            "failed" => {
+                let error = status_response.error;
+                return Err(DocumentAnalysisError::AnalysisFailed {
+                    error_code: error.as_ref().map(|detail| detail.innermost_code().to_string()),
+                    message: match &error {
+                        Some(detail) => format!("Document analysis failed: {}", detail),
+                        None => "Document analysis failed".to_string(),
+                    },
+                    operation_location: operation_location.to_string(),
+                }
+                .into());
+            }
             "running" | "notStarted" => continue,
             other => return Err(anyhow::anyhow!("Unknown status: {}", other)),
         }
     }
 }
+
+/// Sends `request`, retrying on 429 and transient 5xx responses as configured by
+/// `polling.retry`. A `Retry-After` header (capped at `polling.max_retry_after`)
+/// sets the delay; otherwise it backs off exponentially with jitter. Retries
+/// happen inside the document's task, so it keeps its concurrency slot and
+/// retrying cannot exceed the batch's rate limit. Other error statuses, and the
+/// last retryable one, are returned as `DocumentAnalysisError::HttpError` with
+/// the service's error code and message from the response body. Each retried
+/// 429 is counted on `tracker`, when given.
+pub(crate) async fn send_with_retry(
+    request: RequestBuilder,
+    polling: &PollingConfig,
+    source: &str,
+    tracker: Option<&DocumentStateTracker>,
+) -> anyhow::Result<Response> {
+    let mut retries = 0;
+    loop {
+        let attempt = request
+            .try_clone()
+            .ok_or_else(|| anyhow::anyhow!("Request for {} cannot be retried", source))?;
+        let response = attempt.send().await?;
+        let status = response.status();
+        if !HttpRetry::is_retryable(status) || retries >= polling.retry.max_retries {
+            if status.is_client_error() || status.is_server_error() {
+                return Err(http_error(response, source).await.into());
+            }
+            return Ok(response);
+        }
+
+        retries += 1;
+        if status == StatusCode::TOO_MANY_REQUESTS
+            && let Some(tracker) = tracker
+        {
+            tracker.record_throttle();
+        }
+        let delay = parse_retry_after(response.headers())
+            .map(|retry_after| retry_after.min(polling.max_retry_after))
+            .unwrap_or_else(|| polling.retry.backoff(retries));
+        warn!(
+            source = source,
+            status_code = status.as_u16(),
+            retry = retries,
+            delay_ms = delay.as_millis() as u64,
+            "Transient HTTP error, retrying"
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Builds the error for a failed response, quoting the service's error object
+/// when the body carries one. Bodies over `MAX_ERROR_BODY_BYTES` are not read.
+async fn http_error(response: Response, source: &str) -> DocumentAnalysisError {
+    let status = response.status();
+    let detail = read_body_limited(response, Some(MAX_ERROR_BODY_BYTES), source)
+        .await
+        .ok()
+        .and_then(|body| serde_json::from_slice::<ErrorResponse>(&body).ok())
+        .map(|body| body.error);
+    DocumentAnalysisError::HttpError {
+        status_code: status.as_u16(),
+        error_code: detail.as_ref().map(|detail| detail.innermost_code().to_string()),
+        message: match detail {
+            Some(detail) => format!("API Error: HTTP {}: {}", status, detail),
+            None => format!("API Error: HTTP {}", status),
+        },
+    }
+}
+
+/// Reads a response body chunk by chunk, failing as soon as it exceeds `limit` bytes.
+///
+/// Each chunk is appended to a single buffer and dropped as it arrives. A
+/// `Content-Length` above `limit` is rejected before anything is read; otherwise
+/// it sizes the buffer up front, capped at `limit` so a wrong header cannot
+/// reserve more than the caller allows.
+async fn read_body_limited(
+    response: Response,
+    limit: Option<usize>,
+    source: &str,
+) -> anyhow::Result<Vec<u8>> {
+    let length = response.content_length();
+    if let (Some(limit), Some(length)) = (limit, length)
+        && length > limit as u64
+    {
+        return Err(anyhow::anyhow!(
+            "Result for {} is {} bytes, exceeding max_result_bytes ({})",
+            source,
+            length,
+            limit
+        ));
+    }
+
+    let capacity = length.map_or(0, |length| usize::try_from(length).unwrap_or(usize::MAX));
+    let mut body = Vec::with_capacity(limit.map_or(capacity, |limit| capacity.min(limit)));
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        if let Some(limit) = limit
+            && body.len() + chunk.len() > limit
+        {
+            return Err(anyhow::anyhow!(
+                "Result for {} exceeded max_result_bytes ({}) while streaming",
+                source,
+                limit
+            ));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use crate::clients::base::InFlightGauges;
+    use crate::test_support::{MockServer, Response, credentials, settings};
+
+    /// Runs `read_file_with_retry` with a reader that yields `outcomes` in turn.
+    /// Returns the result and how many reads were made.
+    fn read_with_outcomes(outcomes: Vec<std::io::Result<Vec<u8>>>) -> (anyhow::Result<Vec<u8>>, usize) {
+        let retry = FileReadRetry { attempts: 3, delay: Duration::ZERO };
+        let mut outcomes = outcomes.into_iter();
+        let mut reads = 0;
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let result = runtime.block_on(read_file_with_retry(Path::new("scan.pdf"), &retry, || {
+            reads += 1;
+            std::future::ready(outcomes.next().expect("read after the last scripted outcome"))
+        }));
+        (result, reads)
+    }
+
+    fn io_error(kind: std::io::ErrorKind) -> std::io::Result<Vec<u8>> {
+        Err(kind.into())
+    }
+
+    #[test]
+    fn file_reads_recover_from_a_transient_error() {
+        let (result, reads) = read_with_outcomes(vec![io_error(std::io::ErrorKind::Interrupted), Ok(b"%PDF".to_vec())]);
+        assert_eq!(result.unwrap(), b"%PDF");
+        assert_eq!(reads, 2);
+    }
+
+    #[test]
+    fn missing_or_forbidden_files_fail_without_retrying() {
+        for kind in [std::io::ErrorKind::NotFound, std::io::ErrorKind::PermissionDenied] {
+            let (result, reads) = read_with_outcomes(vec![io_error(kind)]);
+            let error = result.unwrap_err();
+            assert!(
+                matches!(error.downcast_ref(), Some(DocumentAnalysisError::FileReadError { path, .. }) if path == "scan.pdf"),
+                "{:?}: {}",
+                kind,
+                error
+            );
+            assert_eq!(reads, 1, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn file_reads_give_up_after_the_configured_attempts() {
+        let (result, reads) = read_with_outcomes(vec![
+            io_error(std::io::ErrorKind::Interrupted),
+            io_error(std::io::ErrorKind::TimedOut),
+            io_error(std::io::ErrorKind::Interrupted),
+        ]);
+        assert!(matches!(result.unwrap_err().downcast_ref(), Some(DocumentAnalysisError::FileReadError { .. })));
+        assert_eq!(reads, 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_file_names_are_read_and_reported_lossily() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let dir = std::env::temp_dir().join(format!("rusty-di-non-utf8-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(OsStr::from_bytes(b"scan-\xff.pdf"));
+        let retry = FileReadRetry { attempts: 1, delay: Duration::ZERO };
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+
+        std::fs::write(&path, b"%PDF").unwrap();
+        let contents = runtime.block_on(read_file_with_retry(&path, &retry, || read_file(&path)));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(contents.unwrap(), b"%PDF");
+
+        let error = runtime.block_on(read_file_with_retry(&path, &retry, || read_file(&path))).unwrap_err();
+        match error.downcast_ref() {
+            Some(DocumentAnalysisError::FileReadError { path, .. }) => assert!(path.ends_with("scan-\u{FFFD}.pdf"), "{}", path),
+            _ => panic!("{}", error),
+        }
+    }
+
+    #[test]
+    fn locale_is_passed_through_only_when_set() {
+        let creds = credentials("https://a.example.com", 1);
+        let mut options = settings(1).options;
+
+        let url = analyze_url(&creds, &options).unwrap();
+        assert!(!url.as_str().contains("locale"), "{}", url);
+
+        options.locale = Some("en-US".to_string());
+        assert_eq!(
+            analyze_url(&creds, &options).unwrap().as_str(),
+            "https://a.example.com/documentintelligence/documentModels/prebuilt-layout:analyze?api-version=2024-11-30&locale=en-US"
+        );
+    }
+
+    #[test]
+    fn analyze_urls_encode_features_and_model_ids() {
+        let creds = credentials("https://a.example.com", 1);
+        let mut options = settings(1).options;
+
+        options.features = Some(vec!["ocrHighResolution".to_string(), "languages".to_string()]);
+        assert_eq!(
+            analyze_url(&creds, &options).unwrap().as_str(),
+            "https://a.example.com/documentintelligence/documentModels/prebuilt-layout:analyze\
+             ?api-version=2024-11-30&features=ocrHighResolution%2Clanguages"
+        );
+
+        options.features = None;
+        options.model_id = "my model/v2?#%".to_string();
+        assert_eq!(
+            analyze_url(&creds, &options).unwrap().as_str(),
+            "https://a.example.com/documentintelligence/documentModels/my%20model%2Fv2%3F%23%25:analyze?api-version=2024-11-30"
+        );
+    }
+
+    /// Polls an operation that answers `running` with `retry-after` once, then
+    /// `succeeded`, on a runtime whose clock only moves when every task is
+    /// sleeping. Returns the time the polling loop slept in total.
+    fn poll_with_retry_after(retry_after: &'static str, max_retry_after: Duration) -> Duration {
+        let polled = Arc::new(AtomicUsize::new(0));
+        let server = {
+            let polled = polled.clone();
+            MockServer::start(move |_| {
+                if polled.fetch_add(1, Ordering::Relaxed) == 0 {
+                    Response::json(200, serde_json::json!({ "status": "running" })).header("retry-after", retry_after)
+                } else {
+                    Response::succeeded(serde_json::json!({ "content": "" }))
+                }
+            })
+        };
+        let polling = PollingConfig {
+            interval: Duration::from_secs(1),
+            jitter: 0.0,
+            max_retry_after,
+            ..PollingConfig::default()
+        };
+        // Without pooled connections no idle timer can move the paused clock
+        // while a response is awaited.
+        let client = Client::builder().pool_max_idle_per_host(0).build().unwrap();
+        let auth = auth_header(&credentials(&server.url, 1)).unwrap();
+        let tracker = DocumentStateTracker::new(Arc::new(InFlightGauges::default()));
+        let operation = format!("{}/operations/1", server.url);
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().start_paused(true).build().unwrap();
+        runtime.block_on(async {
+            let started = tokio::time::Instant::now();
+            poll_operation(&client, &operation, &auth, &polling, &tracker, "doc.pdf").await.unwrap();
+            assert_eq!(polled.load(Ordering::Relaxed), 2);
+            started.elapsed()
+        })
+    }
+
+    #[test]
+    fn retry_after_on_a_running_status_sets_the_next_poll_delay() {
+        // One second before the first poll, then the five the service asked for.
+        assert_eq!(poll_with_retry_after("5", Duration::from_secs(30)), Duration::from_secs(6));
+    }
+
+    #[test]
+    fn retry_after_on_a_running_status_is_capped() {
+        assert_eq!(poll_with_retry_after("120", Duration::from_secs(2)), Duration::from_secs(3));
+    }
+
+    /// Polls an operation answered once with `response` under a `max_result_bytes`
+    /// of `limit`.
+    fn poll_with_max_result_bytes(response: Response, limit: usize) -> anyhow::Result<Box<RawValue>> {
+        let server = MockServer::start(move |_| response.clone());
+        let polling = PollingConfig { interval: Duration::ZERO, max_result_bytes: Some(limit), ..PollingConfig::default() };
+        let auth = auth_header(&credentials(&server.url, 1)).unwrap();
+        let tracker = DocumentStateTracker::new(Arc::new(InFlightGauges::default()));
+        let operation = format!("{}/operations/1", server.url);
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(poll_operation(&Client::new(), &operation, &auth, &polling, &tracker, "doc.pdf"))
+    }
+
+    fn large_result() -> Response {
+        Response::succeeded(serde_json::json!({ "content": "x".repeat(4096) }))
+    }
+
+    #[test]
+    fn content_length_over_max_result_bytes_is_rejected() {
+        let error = poll_with_max_result_bytes(large_result(), 1024).unwrap_err().to_string();
+        assert!(error.contains("exceeding max_result_bytes (1024)"), "{}", error);
+    }
+
+    #[test]
+    fn streamed_body_over_max_result_bytes_is_rejected() {
+        let error = poll_with_max_result_bytes(large_result().chunked(256), 1024).unwrap_err().to_string();
+        assert!(error.contains("exceeded max_result_bytes (1024) while streaming"), "{}", error);
+    }
+
+    #[test]
+    fn streamed_body_within_max_result_bytes_is_read_whole() {
+        let result = poll_with_max_result_bytes(large_result().chunked(256), 8192).unwrap();
+        let result: serde_json::Value = serde_json::from_str(result.get()).unwrap();
+        assert_eq!(result["content"].as_str().unwrap().len(), 4096);
+    }
+}
@@ -0,0 +1,123 @@
+use reqwest::{Client, Url};
+use serde::Deserialize;
+
+use crate::clients::document_intelligence::{auth_header, send_with_retry};
+use crate::models::{Credentials, DocumentEntity, ErrorDetail, PollingConfig};
+
+pub(crate) const LANGUAGE_API_VERSION: &str = "2023-04-01";
+
+/// Service limits of synchronous entity recognition: characters per document
+/// and documents per request.
+const MAX_CHARS_PER_DOCUMENT: usize = 5120;
+const MAX_DOCUMENTS_PER_REQUEST: usize = 5;
+
+#[derive(Deserialize)]
+struct EntityResponse {
+    results: EntityResults,
+}
+
+#[derive(Deserialize)]
+struct EntityResults {
+    documents: Vec<EntityDocument>,
+    #[serde(default)]
+    errors: Vec<EntityDocumentError>,
+}
+
+#[derive(Deserialize)]
+struct EntityDocument {
+    id: String,
+    entities: Vec<DocumentEntity>,
+}
+
+#[derive(Deserialize)]
+struct EntityDocumentError {
+    id: String,
+    error: ErrorDetail,
+}
+
+/// Runs Azure AI Language named entity recognition over `text`.
+///
+/// The text is split into chunks within the service's per-document limit,
+/// preferably at whitespace, and sent a few chunks per request. Offsets are
+/// in characters (`UnicodeCodePoint`) and relative to the whole `text`.
+pub async fn recognize_entities(
+    client: &Client,
+    creds: &Credentials,
+    text: &str,
+    language: Option<&str>,
+    polling: &PollingConfig,
+) -> anyhow::Result<Vec<DocumentEntity>> {
+    let chars: Vec<char> = text.chars().collect();
+    let chunks = chunk_text(&chars, MAX_CHARS_PER_DOCUMENT);
+    let url = language_url(&creds.endpoint)?;
+    let (auth_name, auth_value) = auth_header(creds)?;
+
+    let mut entities = Vec::new();
+    for (batch_index, batch) in chunks.chunks(MAX_DOCUMENTS_PER_REQUEST).enumerate() {
+        let documents: Vec<serde_json::Value> = batch
+            .iter()
+            .enumerate()
+            .map(|(index, &(start, end))| {
+                let mut document = serde_json::json!({
+                    "id": (batch_index * MAX_DOCUMENTS_PER_REQUEST + index).to_string(),
+                    "text": chars[start..end].iter().collect::<String>(),
+                });
+                if let Some(language) = language {
+                    document["language"] = language.into();
+                }
+                document
+            })
+            .collect();
+        let request = client
+            .post(url.clone())
+            .header(auth_name.clone(), auth_value.clone())
+            .json(&serde_json::json!({
+                "kind": "EntityRecognition",
+                "analysisInput": { "documents": documents },
+                "parameters": { "stringIndexType": "UnicodeCodePoint" },
+            }));
+        let response = send_with_retry(request, polling, &creds.endpoint, None).await?;
+        let body: EntityResponse = response.json().await?;
+
+        if let Some(failed) = body.results.errors.first() {
+            anyhow::bail!("Entity recognition failed for chunk {}: {}", failed.id, failed.error);
+        }
+        for document in body.results.documents {
+            let chunk: usize = document.id.parse()?;
+            let (start, _) = chunks[chunk];
+            entities.extend(document.entities.into_iter().map(|mut entity| {
+                entity.offset += start;
+                entity
+            }));
+        }
+    }
+    entities.sort_by_key(|entity| entity.offset);
+    Ok(entities)
+}
+
+fn language_url(endpoint: &str) -> anyhow::Result<Url> {
+    let mut url = Url::parse(endpoint.trim_end_matches('/'))
+        .map_err(|e| anyhow::anyhow!("Invalid endpoint {}: {}", endpoint, e))?;
+    url.set_path("language/:analyze-text");
+    url.query_pairs_mut().append_pair("api-version", LANGUAGE_API_VERSION);
+    Ok(url)
+}
+
+/// Splits `chars` into `(start, end)` ranges of at most `max_chars`, cutting
+/// after the last whitespace in a range when there is one.
+fn chunk_text(chars: &[char], max_chars: usize) -> Vec<(usize, usize)> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let mut end = (start + max_chars).min(chars.len());
+        if end < chars.len()
+            && let Some(space) = chars[start..end].iter().rposition(|c| c.is_whitespace())
+            && space > 0
+        {
+            end = start + space + 1;
+        }
+        chunks.push((start, end));
+        start = end;
+    }
+    chunks
+}
@@ -0,0 +1,175 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::OnceCell;
+use pyo3::prelude::*;
+use pythonize::pythonize;
+use serde::Serialize;
+use tracing::warn;
+
+/// Shared token buckets, keyed by `(group, endpoint)`. Only weak references are
+/// kept, so a bucket lives as long as some client uses it and every client
+/// naming the same group meanwhile draws from the same bucket.
+type Registry = Mutex<HashMap<(String, String), Weak<TokenBucket>>>;
+
+static REGISTRY: OnceCell<Registry> = OnceCell::new();
+
+/// A token bucket refilled at `rate` tokens per second, holding at most one
+/// second's worth of tokens and never less than one, so rates below one
+/// request per second still let a request through.
+pub struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(rate: f64) -> Self {
+        let capacity = rate.max(1.0);
+        Self {
+            rate,
+            capacity,
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Waits until a token is available and takes it.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                self.refill(&mut state);
+                if state.tokens >= 1.0 {
+                    state.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - state.tokens) / self.rate)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn refill(&self, state: &mut BucketState) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.rate).min(self.capacity);
+        state.refilled_at = now;
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    fn available(&self) -> f64 {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state);
+        state.tokens
+    }
+}
+
+/// Returns the bucket for `endpoint` in `group`, creating it at `rate` requests
+/// per second if no live client uses it. Buckets no client holds any more are
+/// dropped from the registry.
+pub fn shared_bucket(group: &str, endpoint: &str, rate: f64) -> Arc<TokenBucket> {
+    let registry = REGISTRY.get_or_init(Default::default);
+    let mut buckets = registry.lock().unwrap();
+    buckets.retain(|_, bucket| bucket.strong_count() > 0);
+    let key = (group.to_string(), endpoint.to_string());
+    if let Some(bucket) = buckets.get(&key).and_then(Weak::upgrade) {
+        if bucket.rate != rate {
+            warn!(
+                group = group,
+                endpoint = endpoint,
+                rate = bucket.rate,
+                requested_rate = rate,
+                "Rate limiter group already exists with a different rate; keeping the existing rate"
+            );
+        }
+        return bucket;
+    }
+    let bucket = Arc::new(TokenBucket::new(rate));
+    buckets.insert(key, Arc::downgrade(&bucket));
+    bucket
+}
+
+#[derive(Serialize)]
+struct RateLimiterInfo {
+    group: String,
+    endpoint: String,
+    rate: f64,
+    available_tokens: f64,
+    clients: usize,
+}
+
+/// List the process-wide rate limiter buckets created via `rate_limiter_group`
+/// that are still used by a live client.
+///
+/// Returns:
+///     list[dict]: One entry per (group, endpoint) with `group`, `endpoint`,
+///         `rate` (requests per second), `available_tokens` and `clients`
+///         (number of live client credentials sharing the bucket)
+#[pyfunction]
+pub fn rate_limiter_groups(py: Python) -> PyResult<Py<PyAny>> {
+    let mut infos: Vec<RateLimiterInfo> = match REGISTRY.get() {
+        Some(registry) => registry
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|((group, endpoint), bucket)| {
+                let clients = bucket.strong_count();
+                let bucket = bucket.upgrade()?;
+                Some(RateLimiterInfo {
+                    group: group.clone(),
+                    endpoint: endpoint.clone(),
+                    rate: bucket.rate,
+                    available_tokens: bucket.available(),
+                    clients,
+                })
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    infos.sort_by(|a, b| (&a.group, &a.endpoint).cmp(&(&b.group, &b.endpoint)));
+    Ok(pythonize(py, &infos)?.unbind())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_below_one_still_admits_requests() {
+        let bucket = TokenBucket::new(0.5);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let acquired = runtime.block_on(async { tokio::time::timeout(Duration::from_secs(1), bucket.acquire()).await });
+        assert!(acquired.is_ok());
+    }
+
+    #[test]
+    fn buckets_are_dropped_once_no_client_holds_them() {
+        let key = ("prune-test".to_string(), "https://a.example.com".to_string());
+        let live = |key: &(String, String)| {
+            REGISTRY.get().unwrap().lock().unwrap().get(key).is_some_and(|bucket| bucket.strong_count() > 0)
+        };
+
+        let first = shared_bucket(&key.0, &key.1, 5.0);
+        let second = shared_bucket(&key.0, &key.1, 5.0);
+        assert!(Arc::ptr_eq(&first, &second));
+        drop((first, second));
+        assert!(!live(&key));
+
+        shared_bucket("prune-test-other", &key.1, 5.0);
+        assert!(!REGISTRY.get().unwrap().lock().unwrap().contains_key(&key));
+    }
+}
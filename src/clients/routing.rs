@@ -0,0 +1,472 @@
+use std::{
+    str::FromStr,
+    sync::{
+        Mutex,
+        atomic::{AtomicU32, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::Serialize;
+use tracing::{info, warn};
+
+/// Smoothing factor for the submission latency EWMA.
+const LATENCY_ALPHA: f64 = 0.2;
+/// Latency recorded at least for a submission that failed or timed out, so a
+/// credential that errors out quickly never looks like the fastest one.
+const FAILED_SUBMISSION_LATENCY: Duration = Duration::from_secs(10);
+/// Fractional part of the golden ratio, used to spread tickets evenly over [0, 1).
+const GOLDEN_RATIO_FRACTION: f64 = 0.618_033_988_749_895;
+
+/// How documents are assigned to credentials.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RoutingMode {
+    /// Documents cycle through the credentials in proportion to their weights;
+    /// with equal weights document `i` of a batch goes to credential `i % n`.
+    #[default]
+    RoundRobin,
+    /// Bias towards credentials with lower measured submission latency.
+    LatencyAware,
+}
+
+impl FromStr for RoutingMode {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "round_robin" => Ok(RoutingMode::RoundRobin),
+            "latency" => Ok(RoutingMode::LatencyAware),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid routing mode: '{}'. Expected 'round_robin' or 'latency'.",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for RoutingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RoutingMode::RoundRobin => write!(f, "round_robin"),
+            RoutingMode::LatencyAware => write!(f, "latency"),
+        }
+    }
+}
+
+/// When a credential is taken out of rotation after authentication failures,
+/// e.g. because its key was rotated.
+#[derive(Clone, Copy, Debug)]
+pub struct AuthHealthPolicy {
+    /// Consecutive 401/403 responses after which the credential is skipped.
+    pub failure_threshold: u32,
+    /// How long a skipped credential is left alone before one document is sent
+    /// to it as a probe.
+    pub probe_interval: Duration,
+}
+
+/// Per-credential counters. The EWMA is stored as `f64` bits; zero means
+/// no measurement yet.
+#[derive(Default)]
+struct CredentialCounters {
+    requests: AtomicU64,
+    latency_ewma_ms: AtomicU64,
+    /// 401/403 responses since the credential last got a document accepted.
+    auth_failures: AtomicU32,
+    /// Milliseconds after the router was created until which the credential is
+    /// skipped; zero while it is healthy.
+    skipped_until_ms: AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+impl CredentialCounters {
+    fn latency_ms(&self) -> Option<f64> {
+        let bits = self.latency_ewma_ms.load(Ordering::Relaxed);
+        (bits != 0).then(|| f64::from_bits(bits))
+    }
+}
+
+/// Snapshot of one credential's routing statistics.
+#[derive(Serialize, Clone, Debug)]
+pub struct CredentialStats {
+    pub endpoint: String,
+    pub requests: u64,
+    pub latency_ms: Option<f64>,
+}
+
+/// Snapshot of one credential's health, as returned by `credential_status()`.
+#[derive(Serialize, Clone, Debug)]
+pub struct CredentialHealth {
+    pub endpoint: String,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// Picks a credential for each document and keeps the statistics that drive
+/// latency-aware routing and the skipping of credentials that fail to
+/// authenticate. Lives for the lifetime of the client.
+pub struct CredentialRouter {
+    mode: RoutingMode,
+    min_share: f64,
+    health: AuthHealthPolicy,
+    created: Instant,
+    weights: Vec<u32>,
+    /// One period of the weighted round-robin order, indexed by ticket.
+    schedule: Vec<usize>,
+    counters: Vec<CredentialCounters>,
+}
+
+impl CredentialRouter {
+    /// `weights` holds one weight of at least 1 per credential. `min_share` is
+    /// the fraction of traffic spread over all credentials by weight in latency
+    /// mode, so slow credentials keep being measured.
+    pub fn new(weights: Vec<u32>, mode: RoutingMode, min_share: f64, health: AuthHealthPolicy) -> Self {
+        Self {
+            mode,
+            min_share: min_share.clamp(0.0, 1.0),
+            health,
+            created: Instant::now(),
+            schedule: smooth_weighted_schedule(&weights),
+            counters: weights.iter().map(|_| CredentialCounters::default()).collect(),
+            weights,
+        }
+    }
+
+    pub fn mode(&self) -> RoutingMode {
+        self.mode
+    }
+
+    pub fn min_share(&self) -> f64 {
+        self.min_share
+    }
+
+    pub fn health_policy(&self) -> AuthHealthPolicy {
+        self.health
+    }
+
+    /// Selects the credential index for the `ticket`-th document of a batch.
+    ///
+    /// A skipped credential is passed over for the next one in the weighted
+    /// order that can take the document. When every credential is skipped the
+    /// routing mode's choice is kept, so the document fails with the
+    /// authentication error rather than waiting.
+    pub fn select(&self, ticket: usize) -> usize {
+        let preferred = match self.mode {
+            RoutingMode::RoundRobin => self.schedule[ticket % self.schedule.len()],
+            RoutingMode::LatencyAware => self.select_by_latency(ticket),
+        };
+        let index = if self.claim(preferred) {
+            preferred
+        } else {
+            self.next_available(ticket, &[preferred]).unwrap_or(preferred)
+        };
+        self.counters[index].requests.fetch_add(1, Ordering::Relaxed);
+        index
+    }
+
+    /// A credential to retry the `ticket`-th document on after the ones in
+    /// `tried` rejected it, or None if every other credential is skipped.
+    pub fn failover(&self, ticket: usize, tried: &[usize]) -> Option<usize> {
+        let index = self.next_available(ticket, tried)?;
+        self.counters[index].requests.fetch_add(1, Ordering::Relaxed);
+        Some(index)
+    }
+
+    /// The first credential after `ticket` in the weighted order that is not in
+    /// `excluded` and can take a document.
+    fn next_available(&self, ticket: usize, excluded: &[usize]) -> Option<usize> {
+        let len = self.schedule.len();
+        (1..=len)
+            .map(|step| self.schedule[(ticket + step) % len])
+            .find(|index| !excluded.contains(index) && self.claim(*index))
+    }
+
+    /// Whether credential `index` can take a document: it is healthy, or its
+    /// probe is due. A due probe is claimed for the caller by pushing the next
+    /// one back a full interval, so only one document probes at a time.
+    fn claim(&self, index: usize) -> bool {
+        let skipped_until = &self.counters[index].skipped_until_ms;
+        let until = skipped_until.load(Ordering::Relaxed);
+        if until == 0 {
+            return true;
+        }
+        let now = self.now_ms();
+        now >= until
+            && skipped_until
+                .compare_exchange(until, self.skip_deadline(now), Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+    }
+
+    /// Counts a 401/403 response against credential `index`, skipping it once
+    /// `failure_threshold` of them arrive in a row.
+    pub fn record_auth_failure(&self, index: usize, message: String) {
+        let counters = &self.counters[index];
+        let failures = counters.auth_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        *counters.last_error.lock().unwrap() = Some(message);
+        if failures < self.health.failure_threshold {
+            return;
+        }
+        let previous = counters.skipped_until_ms.swap(self.skip_deadline(self.now_ms()), Ordering::Relaxed);
+        if previous == 0 {
+            warn!(
+                credential_index = index,
+                consecutive_failures = failures,
+                probe_in_secs = self.health.probe_interval.as_secs_f64(),
+                "Credential failed to authenticate, skipping it until the next probe"
+            );
+        }
+    }
+
+    /// Records that credential `index` got a document accepted, making it
+    /// healthy again if it was skipped.
+    pub fn record_success(&self, index: usize) {
+        let counters = &self.counters[index];
+        counters.auth_failures.store(0, Ordering::Relaxed);
+        if counters.skipped_until_ms.swap(0, Ordering::Relaxed) != 0 {
+            info!(credential_index = index, "Credential authenticated again, back in rotation");
+        }
+    }
+
+    fn now_ms(&self) -> u64 {
+        self.created.elapsed().as_millis() as u64
+    }
+
+    /// When a credential skipped at `now_ms` is next probed; never zero, which
+    /// marks a healthy credential.
+    fn skip_deadline(&self, now_ms: u64) -> u64 {
+        (now_ms + self.health.probe_interval.as_millis() as u64).max(1)
+    }
+
+    fn select_by_latency(&self, ticket: usize) -> usize {
+        let count = self.counters.len();
+        let latencies: Vec<Option<f64>> = self.counters.iter().map(|c| c.latency_ms()).collect();
+        let measured: Vec<f64> = latencies.iter().flatten().copied().collect();
+        if measured.is_empty() {
+            return self.schedule[ticket % self.schedule.len()];
+        }
+        // Unmeasured credentials are treated as the fastest seen so they get probed.
+        let fastest = measured.iter().copied().fold(f64::INFINITY, f64::min);
+        // A credential's speed is scaled by its weight, so of two equally fast
+        // endpoints the one with more quota gets more traffic.
+        let speeds: Vec<f64> = latencies
+            .iter()
+            .zip(&self.weights)
+            .map(|(latency, &weight)| weight as f64 / latency.unwrap_or(fastest).max(1.0))
+            .collect();
+        let total_speed: f64 = speeds.iter().sum();
+        let total_weight: f64 = self.weights.iter().map(|&weight| weight as f64).sum();
+
+        // A deterministic, evenly spread point in [0, 1) for this ticket.
+        let point = (ticket as f64 * GOLDEN_RATIO_FRACTION).fract();
+        let mut cumulative = 0.0;
+        for (index, (speed, &weight)) in speeds.iter().zip(&self.weights).enumerate() {
+            cumulative += self.min_share * weight as f64 / total_weight
+                + (1.0 - self.min_share) * speed / total_speed;
+            if point < cumulative {
+                return index;
+            }
+        }
+        count - 1
+    }
+
+    /// Folds a submission latency measurement into the credential's EWMA.
+    pub fn record_latency(&self, index: usize, latency: Duration) {
+        let sample = latency.as_secs_f64() * 1000.0;
+        let _ = self.counters[index].latency_ewma_ms.fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |bits| {
+                let updated = if bits == 0 {
+                    sample
+                } else {
+                    LATENCY_ALPHA * sample + (1.0 - LATENCY_ALPHA) * f64::from_bits(bits)
+                };
+                Some(updated.max(f64::MIN_POSITIVE).to_bits())
+            },
+        );
+    }
+
+    /// Records a submission that failed or timed out after `elapsed` as a slow
+    /// one, pushing latency-aware routing away from the credential.
+    pub fn record_failed_submission(&self, index: usize, elapsed: Duration) {
+        self.record_latency(index, elapsed.max(FAILED_SUBMISSION_LATENCY));
+    }
+
+    pub fn health(&self, endpoints: impl Iterator<Item = String>) -> Vec<CredentialHealth> {
+        endpoints
+            .zip(&self.counters)
+            .map(|(endpoint, counters)| CredentialHealth {
+                endpoint,
+                healthy: counters.skipped_until_ms.load(Ordering::Relaxed) == 0,
+                consecutive_failures: counters.auth_failures.load(Ordering::Relaxed),
+                last_error: counters.last_error.lock().unwrap().clone(),
+            })
+            .collect()
+    }
+
+    pub fn stats(&self, endpoints: impl Iterator<Item = String>) -> Vec<CredentialStats> {
+        endpoints
+            .zip(&self.counters)
+            .map(|(endpoint, counters)| CredentialStats {
+                endpoint,
+                requests: counters.requests.load(Ordering::Relaxed),
+                latency_ms: counters.latency_ms(),
+            })
+            .collect()
+    }
+}
+
+/// One period of the smooth weighted round-robin order (as in nginx): each
+/// step every credential's current weight grows by its weight, the largest
+/// (earliest on ties) is picked and lowered by the total. Heavier credentials
+/// come up proportionally more often without being picked in long runs.
+///
+/// Weights are divided by their greatest common divisor first, so the period
+/// is as short as possible; with equal weights it is `0, 1, ..., n - 1`.
+/// Precomputing the period keeps each selection a lock-free index by ticket.
+fn smooth_weighted_schedule(weights: &[u32]) -> Vec<usize> {
+    let divisor = weights.iter().copied().fold(0, gcd).max(1);
+    let weights: Vec<i64> = weights.iter().map(|&weight| (weight / divisor) as i64).collect();
+    let total: i64 = weights.iter().sum();
+    let mut current = vec![0i64; weights.len()];
+    (0..total)
+        .map(|_| {
+            for (value, weight) in current.iter_mut().zip(&weights) {
+                *value += weight;
+            }
+            let picked = (0..current.len())
+                .rev()
+                .max_by_key(|&index| current[index])
+                .unwrap_or_default();
+            current[picked] -= total;
+            picked
+        })
+        .collect()
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn router(weights: Vec<u32>) -> CredentialRouter {
+        with_probe_interval(weights, Duration::from_secs(60))
+    }
+
+    fn with_probe_interval(weights: Vec<u32>, probe_interval: Duration) -> CredentialRouter {
+        let health = AuthHealthPolicy { failure_threshold: 3, probe_interval };
+        CredentialRouter::new(weights, RoutingMode::RoundRobin, 0.2, health)
+    }
+
+    fn healthy(router: &CredentialRouter) -> Vec<bool> {
+        let endpoints = (0..router.weights.len()).map(|index| index.to_string());
+        router.health(endpoints).into_iter().map(|health| health.healthy).collect()
+    }
+
+    #[test]
+    fn equal_weights_cycle_in_credential_order() {
+        assert_eq!(smooth_weighted_schedule(&[1, 1, 1]), [0, 1, 2]);
+        assert_eq!(smooth_weighted_schedule(&[4, 4]), [0, 1]);
+    }
+
+    #[test]
+    fn weighted_schedule_interleaves_heavier_credentials() {
+        assert_eq!(smooth_weighted_schedule(&[5, 1, 1]), [0, 0, 1, 0, 2, 0, 0]);
+        assert_eq!(smooth_weighted_schedule(&[2, 1]), [0, 1, 0]);
+        // Weights are reduced by their common divisor before scheduling.
+        assert_eq!(smooth_weighted_schedule(&[20, 10]), smooth_weighted_schedule(&[2, 1]));
+    }
+
+    #[test]
+    fn selection_shares_follow_the_weights() {
+        let router = router(vec![10, 1, 3]);
+        let mut counts = [0usize; 3];
+        for ticket in 0..1_400 {
+            counts[router.select(ticket)] += 1;
+        }
+        assert_eq!(counts, [1_000, 100, 300]);
+
+        let stats = router.stats(["a", "b", "c"].map(String::from).into_iter());
+        assert_eq!(stats.iter().map(|stat| stat.requests).collect::<Vec<_>>(), [1_000, 100, 300]);
+    }
+
+    #[test]
+    fn heavier_credentials_are_not_picked_in_long_runs() {
+        let schedule = smooth_weighted_schedule(&[3, 1]);
+        assert_eq!(schedule, [0, 0, 1, 0]);
+        // Across period boundaries too: at most three picks of 0 in a row.
+        let cycled: Vec<usize> = schedule.iter().cycle().take(schedule.len() * 3).copied().collect();
+        assert!(cycled.windows(4).all(|window| window.contains(&1)), "{:?}", cycled);
+    }
+
+    #[test]
+    fn credentials_are_skipped_after_the_failure_threshold() {
+        let router = router(vec![1, 1]);
+        router.record_auth_failure(1, "401 Unauthorized".to_string());
+        router.record_auth_failure(1, "401 Unauthorized".to_string());
+        assert_eq!(healthy(&router), [true, true]);
+        assert_eq!(router.select(1), 1);
+
+        router.record_auth_failure(1, "403 Forbidden".to_string());
+        assert_eq!(healthy(&router), [true, false]);
+        assert!((0..4).all(|ticket| router.select(ticket) == 0));
+
+        let status = &router.health(["a", "b"].map(String::from).into_iter())[1];
+        assert_eq!(status.consecutive_failures, 3);
+        assert_eq!(status.last_error.as_deref(), Some("403 Forbidden"));
+    }
+
+    #[test]
+    fn a_success_resets_the_consecutive_failures() {
+        let router = router(vec![1, 1]);
+        router.record_auth_failure(1, "401 Unauthorized".to_string());
+        router.record_auth_failure(1, "401 Unauthorized".to_string());
+        router.record_success(1);
+        router.record_auth_failure(1, "401 Unauthorized".to_string());
+        assert_eq!(healthy(&router), [true, true]);
+    }
+
+    #[test]
+    fn skipped_credentials_are_probed_and_re_enabled() {
+        let router = with_probe_interval(vec![1, 1], Duration::from_millis(50));
+        for _ in 0..3 {
+            router.record_auth_failure(1, "401 Unauthorized".to_string());
+        }
+        assert_eq!(router.select(1), 0);
+
+        std::thread::sleep(Duration::from_millis(60));
+        // Once the interval has passed, exactly one document probes the credential.
+        assert_eq!(router.select(1), 1);
+        assert_eq!(router.select(3), 0);
+        assert_eq!(healthy(&router), [true, false]);
+
+        router.record_success(1);
+        assert_eq!(healthy(&router), [true, true]);
+        assert_eq!(router.select(3), 1);
+    }
+
+    #[test]
+    fn failover_runs_out_only_when_every_credential_is_skipped() {
+        let router = router(vec![1, 1, 1]);
+        for _ in 0..3 {
+            router.record_auth_failure(1, "401 Unauthorized".to_string());
+        }
+        assert_eq!(router.failover(0, &[0]), Some(2));
+        assert_eq!(router.failover(0, &[0, 2]), None);
+
+        for index in [0, 2] {
+            for _ in 0..3 {
+                router.record_auth_failure(index, "401 Unauthorized".to_string());
+            }
+        }
+        assert_eq!(healthy(&router), [false, false, false]);
+        // With nothing healthy the document keeps its credential and fails on it.
+        assert_eq!(router.select(2), 2);
+        assert_eq!(router.failover(2, &[2]), None);
+    }
+}
@@ -0,0 +1,81 @@
+use std::{
+    pin::pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Notify;
+
+/// Tracks the batches running on a client, tells them to stop submitting new
+/// documents once the client is closing, and to abort the documents they still
+/// have in flight once the grace period is over.
+#[derive(Default)]
+pub struct Shutdown {
+    closing: AtomicBool,
+    cancelled: AtomicBool,
+    cancel_notify: Notify,
+    active: AtomicUsize,
+}
+
+impl Shutdown {
+    pub fn is_closing(&self) -> bool {
+        self.closing.load(Ordering::SeqCst)
+    }
+
+    /// Registers a batch, or returns `None` if the client is closing.
+    pub fn enter(self: &Arc<Self>) -> Option<ActiveBatch> {
+        // Count first, then check, so `close` never misses a batch that slipped in.
+        self.active.fetch_add(1, Ordering::SeqCst);
+        let guard = ActiveBatch(self.clone());
+        (!self.is_closing()).then_some(guard)
+    }
+
+    pub fn close(&self) {
+        self.closing.store(true, Ordering::SeqCst);
+    }
+
+    /// Tells active batches to abort their in-flight documents.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.cancel_notify.notify_waiters();
+    }
+
+    /// Resolves once `cancel` has been called.
+    pub async fn cancelled(&self) {
+        loop {
+            // Register before checking the flag so a concurrent `cancel` is not missed.
+            let mut notified = pin!(self.cancel_notify.notified());
+            notified.as_mut().enable();
+            if self.cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Waits up to `grace` for every active batch to finish. Returns whether
+    /// they all did.
+    pub fn wait_idle(&self, grace: Duration) -> bool {
+        let deadline = Instant::now() + grace;
+        while self.active.load(Ordering::SeqCst) > 0 {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        true
+    }
+}
+
+/// Marks a batch as running until dropped.
+pub struct ActiveBatch(Arc<Shutdown>);
+
+impl Drop for ActiveBatch {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
@@ -1,8 +1,12 @@
 mod clients;
 mod models;
+#[cfg(test)]
+mod test_support;
 mod utils;
+use crate::clients::rate_limit::rate_limiter_groups;
 use crate::models::*;
 use crate::utils::logger::init_tracing;
+use crate::utils::tiff::tiff_page_count;
 
 use pyo3::prelude::*;
 
@@ -29,15 +33,40 @@ fn rusty_di_runner(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Money pieces
     m.add_class::<RustyAnalysisClient>()?;
     m.add_class::<Credentials>()?;
+    m.add_class::<BatchProcessingConfig>()?;
+    m.add("AnalysisError", m.py().get_type::<AnalysisError>())?;
+    m.add("DocumentAnalysisError", m.py().get_type::<DocumentAnalysisError>())?;
+    m.add("RateLimitError", m.py().get_type::<RateLimitError>())?;
+    m.add("AuthenticationError", m.py().get_type::<AuthenticationError>())?;
+    m.add("InvalidRequestError", m.py().get_type::<InvalidRequestError>())?;
+    m.add("AnalysisFailedError", m.py().get_type::<AnalysisFailedError>())?;
+    m.add("PollTimeoutError", m.py().get_type::<PollTimeoutError>())?;
+    m.add_function(wrap_pyfunction!(rate_limiter_groups, m)?)?;
+    m.add_function(wrap_pyfunction!(tiff_page_count, m)?)?;
 
     // Model classes
     m.add_class::<AnalyzeResult>()?;
     m.add_class::<DocumentPage>()?;
     m.add_class::<DocumentLine>()?;
     m.add_class::<DocumentWord>()?;
+    m.add_class::<DocumentSelectionMark>()?;
+    m.add_class::<DocumentBarcode>()?;
+    m.add_class::<DocumentFormula>()?;
     m.add_class::<DocumentParagraph>()?;
     m.add_class::<DocumentTable>()?;
+    m.add_class::<DocumentCaption>()?;
+    m.add_class::<DocumentFigure>()?;
+    m.add_class::<DocumentSection>()?;
     m.add_class::<DocumentSpan>()?;
+    m.add_class::<AnalyzedDocument>()?;
+    m.add_class::<DocumentField>()?;
+    m.add_class::<DocumentKeyValuePair>()?;
+    m.add_class::<DocumentStyle>()?;
+    m.add_class::<DocumentEntity>()?;
+    m.add_class::<DocumentKeyValueElement>()?;
+    m.add_class::<ConfidenceFilteredContent>()?;
+    m.add_class::<PageRangeAnalysis>()?;
+    m.add_class::<CheckpointedResult>()?;
 
     Ok(())
 }
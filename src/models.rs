@@ -1,9 +1,39 @@
 pub mod analysis_client;
+pub mod analyze_options;
 pub mod analyze_result;
+pub mod batch_config_file;
+pub mod batch_processing_config;
+pub mod checkpointed_result;
+pub mod cloud;
+pub mod credential_check;
 pub mod credentials;
+pub mod document_bytes;
+pub mod errors;
+pub mod exceptions;
+pub mod file_read_retry;
+pub mod http_retry;
+pub mod image_input;
+pub mod integrations;
+pub mod polling_config;
+pub mod projection;
 pub mod status_response;
+pub mod timeouts;
 
 pub use analysis_client::RustyAnalysisClient;
+pub use analyze_options::{AnalyzeOptions, BatchSettings};
 pub use analyze_result::*;
+pub use batch_processing_config::BatchProcessingConfig;
+pub use checkpointed_result::CheckpointedResult;
+pub use cloud::AzureCloud;
+pub use credential_check::CredentialCheck;
 pub use credentials::Credentials;
-pub use status_response::StatusResponse;
+pub use document_bytes::DocumentBytes;
+pub use exceptions::{
+    AnalysisError, AnalysisFailedError, AuthenticationError, DocumentAnalysisError,
+    InvalidRequestError, PollTimeoutError, RateLimitError,
+};
+pub use file_read_retry::FileReadRetry;
+pub use http_retry::{HttpRetry, RetryOverrides};
+pub use polling_config::PollingConfig;
+pub use status_response::{ErrorDetail, ErrorResponse, StatusResponse};
+pub use timeouts::Timeouts;
@@ -1,12 +1,48 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyBaseException, PyIOError, PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use pythonize::pythonize;
+use tokio::runtime::{Handle, Runtime};
+use tracing::{info, warn};
 
 use crate::Credentials;
+use crate::PollingConfig;
+use crate::Timeouts;
+use crate::clients::base::{
+    AbortOnDrop, BatchSource, DocumentMetrics, DocumentOutcome, InFlightGauges, PagedSource, StateNotifier, StatusNotifier,
+};
+use crate::clients::checkpoint::{Checkpoint, CheckpointEntry};
+use crate::clients::document_intelligence::API_VERSION;
+use crate::clients::rate_limit::{TokenBucket, shared_bucket};
+use crate::clients::routing::{AuthHealthPolicy, CredentialRouter, RoutingMode};
+use crate::clients::shutdown::{ActiveBatch, Shutdown};
 use crate::init_tracing;
 use crate::models::analyze_result::AnalyzeResult;
-use tokio::runtime::Runtime;
+use crate::models::batch_config_file::{BatchConfigFile, BatchConfigInputs};
+use crate::models::batch_processing_config::BatchProcessingConfig;
+use crate::models::errors::{DocumentAnalysisError, analysis_error};
+use crate::models::image_input::{ImageEncoding, encode_image};
+use crate::models::projection::{Projection, parse_result};
+use crate::models::{
+    AnalyzeOptions, BatchSettings, CheckpointedResult, DocumentBytes, FileReadRetry, HttpRetry,
+    RetryOverrides,
+};
+use crate::utils::archive::read_zip_documents;
+use crate::utils::tiff::count_tiff_pages;
+use crate::utils::logger::tracing_initialized;
+use crate::utils::report::{BatchReport, ReportFailure, ReportFormat, ReportTiming};
+use crate::utils::{UNSUPPORTED_CONTENT_TYPE, get_content_type, long_path};
 
 /// A client for analyzing documents using Azure Document Intelligence API.
 ///
@@ -23,9 +59,41 @@ use tokio::runtime::Runtime;
 ///     ... )
 #[pyclass]
 pub struct RustyAnalysisClient {
-    runtime: Runtime,
+    /// Taken by `close()`; batches run through `handle` so they never hold it.
+    runtime: Mutex<Option<Runtime>>,
+    handle: Handle,
+    pub(crate) shutdown: Arc<Shutdown>,
+    shutdown_grace: Duration,
     pub(crate) credentials: Vec<Credentials>,
+    pub(crate) polling: PollingConfig,
+    pub(crate) timeouts: Timeouts,
+    pub(crate) file_read: FileReadRetry,
+    pub(crate) gauges: Arc<InFlightGauges>,
+    pub(crate) router: Arc<CredentialRouter>,
+    /// Shared per-credential token buckets when `rate_limiter_group` is set,
+    /// aligned with `credentials`; empty otherwise.
+    pub(crate) rate_limiters: Arc<Vec<Arc<TokenBucket>>>,
+    /// Whether a model supports `outputContentFormat=markdown`, for models
+    /// where that is known. Unlisted models are assumed to support it.
+    markdown_support: HashMap<String, bool>,
+    rate_limiter_group: Option<String>,
+    logs_enabled: bool,
+    /// Documents currently held by a worker, split by URL and uploaded input.
+    pub(crate) active_permits_url: Arc<AtomicUsize>,
+    pub(crate) active_permits_file: Arc<AtomicUsize>,
+    /// Documents taken by a worker, and how many of those failed, over the
+    /// client's lifetime.
+    pub(crate) total_requests: Arc<AtomicU64>,
+    pub(crate) total_errors: Arc<AtomicU64>,
 }
+/// Models that reject `outputContentFormat=markdown` with a 400.
+const MARKDOWN_UNSUPPORTED_MODELS: [&str; 2] = ["prebuilt-read", "prebuilt-invoice"];
+/// How long `close()` waits for batches to return once their in-flight
+/// documents have been aborted.
+const CANCEL_WAIT: Duration = Duration::from_secs(1);
+/// Concurrent requests per credential when a batch call doesn't set `max_rps`.
+pub(crate) const DEFAULT_MAX_RPS: usize = 15;
+
 #[derive(Clone, Debug, PartialEq, Default)]
 pub enum OutputContentFormat{
     #[default]
@@ -57,6 +125,153 @@ impl std::fmt::Display for OutputContentFormat {
     }
 }
 
+/// A batch input: a bare URL or file path, or a `(source, priority)` tuple.
+#[derive(FromPyObject)]
+pub enum BatchInput<T> {
+    Plain(T),
+    Prioritized(T, i64),
+}
+
+/// An in-memory document: bare `bytes`, `(name, bytes)` or
+/// `(name, bytes, content_type)`.
+#[derive(FromPyObject)]
+pub enum BytesInput<'py> {
+    Plain(Bound<'py, PyBytes>),
+    Named(String, Bound<'py, PyBytes>),
+    Typed(String, Bound<'py, PyBytes>, String),
+}
+
+impl BytesInput<'_> {
+    fn into_document(self, index: usize) -> PyResult<DocumentBytes> {
+        Ok(match self {
+            BytesInput::Plain(data) => DocumentBytes::from_bytes(None, data.as_bytes().to_vec(), None, index),
+            BytesInput::Named(name, data) => {
+                DocumentBytes::from_bytes(Some(name), data.as_bytes().to_vec(), None, index)
+            }
+            BytesInput::Typed(name, data, content_type) => {
+                let content_type = DocumentBytes::validate_content_type(&content_type, index)?;
+                DocumentBytes::from_bytes(Some(name), data.as_bytes().to_vec(), Some(content_type), index)
+            }
+        })
+    }
+}
+
+/// The per-call arguments shared by the URL and file path batch methods, so
+/// callers that only set a few of them can leave the rest at their defaults.
+#[derive(Default)]
+struct BatchArgs {
+    features: Option<Vec<String>>,
+    output_format: Option<String>,
+    max_rps: Option<usize>,
+    timeout_secs: Option<f64>,
+    poll_interval: Option<f64>,
+    priorities: Option<Vec<i64>>,
+    checkpoint_path: Option<PathBuf>,
+    output_dir: Option<PathBuf>,
+    select: Option<Vec<String>>,
+    strict: bool,
+    /// Only read by the file path methods.
+    skip_unsupported: bool,
+    max_retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+    pages: Option<String>,
+    locale: Option<String>,
+    query_fields: Option<Vec<String>>,
+    config: Option<BatchProcessingConfig>,
+    retry_policy: Option<HashMap<String, i64>>,
+}
+
+/// A batch whose arguments have been validated and resolved, ready to run.
+struct PreparedBatch<T> {
+    inputs: Vec<T>,
+    settings: BatchSettings,
+    /// The `source` of every input, including ones later skipped.
+    sources: Vec<String>,
+    projection: Option<Projection>,
+    checkpoint_path: Option<PathBuf>,
+    output_dir: Option<PathBuf>,
+}
+
+/// Separates batch inputs from their priorities. Priorities come either from
+/// the `priorities` argument or from `(source, priority)` tuples, not both.
+fn split_priorities<T>(
+    inputs: Vec<BatchInput<T>>,
+    priorities: Option<Vec<i64>>,
+) -> PyResult<(Vec<T>, Option<Vec<i64>>)> {
+    let has_tuples = inputs
+        .iter()
+        .any(|input| matches!(input, BatchInput::Prioritized(..)));
+    if let Some(priorities) = &priorities {
+        if has_tuples {
+            return Err(PyValueError::new_err(
+                "Pass priorities either as (source, priority) tuples or via `priorities`, not both",
+            ));
+        }
+        if priorities.len() != inputs.len() {
+            return Err(PyValueError::new_err(format!(
+                "priorities has {} entries but there are {} inputs",
+                priorities.len(),
+                inputs.len()
+            )));
+        }
+    }
+
+    let (sources, tuple_priorities): (Vec<T>, Vec<i64>) = inputs
+        .into_iter()
+        .map(|input| match input {
+            BatchInput::Plain(source) => (source, 0),
+            BatchInput::Prioritized(source, priority) => (source, priority),
+        })
+        .unzip();
+    Ok((sources, priorities.or(has_tuples.then_some(tuple_priorities))))
+}
+
+/// Keeps the first credential for each endpoint, warning about the rest.
+/// Endpoints are already normalized; only the endpoint is compared, never the key.
+fn dedupe_endpoints(credentials: Vec<Credentials>) -> Vec<Credentials> {
+    let mut seen = HashSet::new();
+    credentials
+        .into_iter()
+        .filter(|creds| {
+            let unique = seen.insert(creds.endpoint.to_ascii_lowercase());
+            if !unique {
+                warn!(
+                    endpoint = creds.endpoint.as_str(),
+                    "Ignoring duplicate credential for endpoint; pass allow_duplicate_endpoints=True to keep it"
+                );
+            }
+            unique
+        })
+        .collect()
+}
+
+/// Combines a batch method's `max_retries` and `initial_backoff_ms` arguments
+/// with its `retry_policy` dict. Each setting may be given in only one of them.
+fn retry_overrides(
+    max_retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+    retry_policy: Option<HashMap<String, i64>>,
+) -> PyResult<RetryOverrides> {
+    let arguments = RetryOverrides {
+        max_retries,
+        initial_backoff: initial_backoff_ms.map(Duration::from_millis),
+        max_backoff: None,
+    };
+    let policy = match &retry_policy {
+        Some(policy) => RetryOverrides::from_policy(policy).map_err(PyValueError::new_err)?,
+        None => RetryOverrides::default(),
+    };
+    arguments.merge(policy).map_err(|name| {
+        PyValueError::new_err(format!("{} was given both as an argument and in retry_policy", name))
+    })
+}
+
+/// Converts a seconds argument into a `Duration`, naming the argument on error.
+fn duration_from_secs(name: &str, secs: f64) -> PyResult<Duration> {
+    Duration::try_from_secs_f64(secs)
+        .map_err(|_| PyValueError::new_err(format!("{} must be a non-negative number", name)))
+}
+
 #[pymethods]
 impl RustyAnalysisClient {
     /// Create a new RustyAnalysisClient instance.
@@ -64,10 +279,89 @@ impl RustyAnalysisClient {
     /// Args:
     ///     credentials (list[Credentials]): List of Credentials objects containing
     ///     endpoint URLs and API keys for Azure Document Intelligence services
+    ///     enable_logs (bool): Whether to emit tracing logs to stderr
+    ///     max_retry_after_secs (float): Upper bound on the `Retry-After` delay honored
+    ///         between status polls. Defaults to 30.
+    ///     max_result_bytes (int | None): Largest analysis result, in bytes, accepted
+    ///         per document. Larger results fail that document with an error instead
+    ///         of exhausting memory. Defaults to None (no limit).
+    ///     on_state_change (Callable[[dict], None] | None): Optional callback invoked with
+    ///         the same dict as `in_flight()` whenever a document changes phase.
+    ///         Called from a background thread, never from the runtime's workers.
+    ///     max_state_change_rate (float): Maximum number of `on_state_change` calls per
+    ///         second; transitions in between are coalesced into one call with the
+    ///         latest counts once the interval has passed. Defaults to 10.
+    ///     routing (str): How documents are assigned to credentials. Valid values are:
+    ///         - 'round_robin' (default): documents cycle through the credentials in
+    ///           proportion to their `weight`; with equal weights document i goes
+    ///           to credential i % n
+    ///         - 'latency': bias towards credentials with lower measured submission
+    ///           latency (EWMA), see `credential_stats()`
+    ///     routing_min_share (float): In 'latency' mode, the fraction of documents
+    ///         spread across all credentials by weight so none goes stale. Latency
+    ///         shares are scaled by weight too. Defaults to 0.2.
+    ///     auth_failure_threshold (int): Consecutive 401/403 responses after which a
+    ///         credential is skipped, e.g. because its key was rotated. Documents it
+    ///         rejects are retried on another credential. See `credential_status()`.
+    ///         Defaults to 3.
+    ///     auth_probe_interval_secs (float): How long a skipped credential is left
+    ///         alone before one document is sent to it as a probe; it rejoins the
+    ///         rotation once a document is accepted. Defaults to 60.
+    ///     timeout_secs (float | None): Default per-document timeout, counted from when
+    ///         the document starts submitting. Defaults to None (no timeout).
+    ///     batch_timeout_secs (float | None): Deadline for a whole batch, including
+    ///         time documents spend waiting for the rate limiter. Defaults to None.
+    ///     poll_interval (float): Default delay before the first status poll, in
+    ///         seconds. Without a `Retry-After` from the service, later polls back
+    ///         off exponentially from it. Defaults to 0.5.
+    ///     max_poll_interval (float): Cap on the backoff delay between polls, in
+    ///         seconds. Defaults to 10.
+    ///     poll_backoff (float): Factor the delay grows by after each poll. 1
+    ///         polls at a fixed `poll_interval`. Defaults to 1.5.
+    ///     poll_jitter (float): Fraction of each delay that is randomized, from 0
+    ///         to 1, so documents submitted together don't poll in lockstep.
+    ///         Defaults to 0.1.
+    ///     rate_limiter_group (str | None): Name of a process-wide rate limiter. All
+    ///         clients with the same group share one token bucket per endpoint, so
+    ///         their combined submissions stay under `rate_limiter_rps`. See
+    ///         `rate_limiter_groups()`. Defaults to None (no shared limit).
+    ///     rate_limiter_rps (float): Submissions per second allowed per endpoint in
+    ///         the group. The first client to create a group's bucket sets its rate.
+    ///         Defaults to 15.
+    ///     shutdown_grace_secs (float): How long `close()` waits for in-flight
+    ///         documents to finish before cancelling them. Defaults to 5.
+    ///     file_read_attempts (int): Attempts at reading each local file before the
+    ///         document fails. Only interrupted, timed-out and low-level I/O (EIO)
+    ///         errors are retried; missing files and permission errors fail at once.
+    ///         Defaults to 3.
+    ///     file_read_retry_delay_secs (float): Delay between file read attempts.
+    ///         Defaults to 0.5.
+    ///     markdown_models (dict[str, bool] | None): Model IDs mapped to whether they
+    ///         support markdown output, overriding the built-in table (which marks
+    ///         'prebuilt-read' and 'prebuilt-invoice' as unsupported). Models not in
+    ///         the table are assumed to support it. Defaults to None.
+    ///     allow_duplicate_endpoints (bool): Keep credentials that share an endpoint,
+    ///         e.g. two keys of one resource used for key rotation. By default only
+    ///         the first credential per endpoint is kept and a warning is logged, so
+    ///         a copy-pasted entry does not double a resource's share of traffic.
+    ///         Defaults to False.
+    ///     on_status (Callable[[str, str, int | None, int], None] | None): Optional
+    ///         callback invoked after every status poll of a document with its source,
+    ///         the status ('notStarted', 'running', 'succeeded' or 'failed'), the
+    ///         service's `percentCompleted` (None when not reported) and the poll
+    ///         number, starting at 1. Called from a background thread while holding
+    ///         the GIL; the document waits for it to return. Defaults to None.
+    ///     on_status_error (str): What happens when `on_status` raises. 'log'
+    ///         (default) logs a warning and keeps polling; 'raise' fails that
+    ///         document with the callback's error.
     ///
     /// Returns:
     ///     RustyAnalysisClient: A new client instance configured with the provided credentials
     ///
+    /// Raises:
+    ///     ValueError: If `credentials` is empty or an option is out of range
+    ///     RuntimeError: If the async runtime cannot be started
+    ///
     /// Example:
     ///     >>> from rusty_di_runner import RustyAnalysisClient, Credentials
     ///     >>> creds = [
@@ -78,18 +372,523 @@ impl RustyAnalysisClient {
     ///     ... ]
     ///     >>> client = RustyAnalysisClient(credentials=creds)
     #[new]
-    #[pyo3(signature = (credentials, enable_logs))]
-    pub fn new(credentials: Vec<Credentials>, enable_logs: bool) -> PyResult<Self> {
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (
+        credentials,
+        enable_logs,
+        max_retry_after_secs=30.0,
+        max_result_bytes=None,
+        on_state_change=None,
+        max_state_change_rate=10.0,
+        routing=None,
+        routing_min_share=0.2,
+        timeout_secs=None,
+        batch_timeout_secs=None,
+        poll_interval=0.5,
+        rate_limiter_group=None,
+        rate_limiter_rps=15.0,
+        shutdown_grace_secs=5.0,
+        file_read_attempts=3,
+        file_read_retry_delay_secs=0.5,
+        markdown_models=None,
+        allow_duplicate_endpoints=false,
+        on_status=None,
+        on_status_error="log",
+        max_poll_interval=10.0,
+        poll_backoff=1.5,
+        poll_jitter=0.1,
+        auth_failure_threshold=3,
+        auth_probe_interval_secs=60.0
+    ))]
+    pub fn new(
+        credentials: Vec<Credentials>,
+        enable_logs: bool,
+        max_retry_after_secs: f64,
+        max_result_bytes: Option<usize>,
+        on_state_change: Option<Bound<'_, PyAny>>,
+        max_state_change_rate: f64,
+        routing: Option<String>,
+        routing_min_share: f64,
+        timeout_secs: Option<f64>,
+        batch_timeout_secs: Option<f64>,
+        poll_interval: f64,
+        rate_limiter_group: Option<String>,
+        rate_limiter_rps: f64,
+        shutdown_grace_secs: f64,
+        file_read_attempts: u32,
+        file_read_retry_delay_secs: f64,
+        markdown_models: Option<HashMap<String, bool>>,
+        allow_duplicate_endpoints: bool,
+        on_status: Option<Bound<'_, PyAny>>,
+        on_status_error: &str,
+        max_poll_interval: f64,
+        poll_backoff: f64,
+        poll_jitter: f64,
+        auth_failure_threshold: u32,
+        auth_probe_interval_secs: f64,
+    ) -> PyResult<Self> {
         // Initialize Tracing
         if enable_logs{
             init_tracing();
         }
 
+        // Endpoints were validated when each `Credentials` was created.
+        if credentials.is_empty() {
+            return Err(PyValueError::new_err("credentials must contain at least one Credentials"));
+        }
+        let credentials = if allow_duplicate_endpoints {
+            credentials
+        } else {
+            dedupe_endpoints(credentials)
+        };
+
+        if let Some(first) = credentials.first()
+            && credentials.iter().any(|creds| creds.cloud != first.cloud)
+        {
+            let clouds: Vec<String> = credentials.iter().map(|creds| creds.cloud.to_string()).collect();
+            warn!(clouds = ?clouds, "Credentials target more than one Azure cloud");
+        }
+
+        if poll_backoff.is_nan() || poll_backoff < 1.0 {
+            return Err(PyValueError::new_err("poll_backoff must be at least 1"));
+        }
+        if !(0.0..=1.0).contains(&poll_jitter) {
+            return Err(PyValueError::new_err("poll_jitter must be between 0 and 1"));
+        }
+        let polling = PollingConfig {
+            interval: duration_from_secs("poll_interval", poll_interval)?,
+            max_interval: duration_from_secs("max_poll_interval", max_poll_interval)?,
+            multiplier: poll_backoff,
+            jitter: poll_jitter,
+            max_retry_after: duration_from_secs("max_retry_after_secs", max_retry_after_secs)?,
+            max_result_bytes,
+            retry: HttpRetry::default(),
+        };
+        let timeouts = Timeouts {
+            document: timeout_secs
+                .map(|secs| duration_from_secs("timeout_secs", secs))
+                .transpose()?,
+            batch: batch_timeout_secs
+                .map(|secs| duration_from_secs("batch_timeout_secs", secs))
+                .transpose()?,
+        };
+        timeouts.validate().map_err(PyValueError::new_err)?;
+        if file_read_attempts == 0 {
+            return Err(PyValueError::new_err("file_read_attempts must be at least 1"));
+        }
+        let file_read = FileReadRetry {
+            attempts: file_read_attempts,
+            delay: duration_from_secs("file_read_retry_delay_secs", file_read_retry_delay_secs)?,
+        };
+
+        let notifier = match on_state_change {
+            Some(callback) if !callback.is_callable() => {
+                return Err(PyTypeError::new_err("on_state_change must be callable"));
+            }
+            Some(_) if max_state_change_rate.is_nan() || max_state_change_rate <= 0.0 => {
+                return Err(PyValueError::new_err("max_state_change_rate must be positive"));
+            }
+            Some(callback) => Some(StateNotifier::new(callback.unbind(), max_state_change_rate)),
+            None => None,
+        };
+        let raise_status_errors = match on_status_error {
+            "log" => false,
+            "raise" => true,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid on_status_error: '{}'. Expected 'log' or 'raise'.",
+                    other
+                )));
+            }
+        };
+        let status_notifier = match on_status {
+            Some(callback) if !callback.is_callable() => {
+                return Err(PyTypeError::new_err("on_status must be callable"));
+            }
+            Some(callback) => Some(StatusNotifier::new(callback.unbind(), raise_status_errors)),
+            None => None,
+        };
+
+        let routing_mode = match routing {
+            Some(s) => RoutingMode::from_str(&s)?,
+            None => RoutingMode::default(),
+        };
+        if !(0.0..=1.0).contains(&routing_min_share) {
+            return Err(PyValueError::new_err("routing_min_share must be between 0 and 1"));
+        }
+        if auth_failure_threshold == 0 {
+            return Err(PyValueError::new_err("auth_failure_threshold must be at least 1"));
+        }
+        let health = AuthHealthPolicy {
+            failure_threshold: auth_failure_threshold,
+            probe_interval: duration_from_secs("auth_probe_interval_secs", auth_probe_interval_secs)?,
+        };
+        let weights = credentials.iter().map(|creds| creds.weight).collect();
+        let router = CredentialRouter::new(weights, routing_mode, routing_min_share, health);
+
+        let rate_limiters = match &rate_limiter_group {
+            Some(_) if rate_limiter_rps.is_nan() || rate_limiter_rps <= 0.0 => {
+                return Err(PyValueError::new_err("rate_limiter_rps must be positive"));
+            }
+            Some(group) => credentials
+                .iter()
+                .map(|creds| shared_bucket(group, &creds.endpoint, rate_limiter_rps))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut markdown_support: HashMap<String, bool> = MARKDOWN_UNSUPPORTED_MODELS
+            .iter()
+            .map(|model| (model.to_string(), false))
+            .collect();
+        markdown_support.extend(markdown_models.unwrap_or_default());
+
+        let runtime = Runtime::new().map_err(|e| {
+            PyRuntimeError::new_err(format!("Failed to start the async runtime: {}", e))
+        })?;
         Ok(Self {
             credentials,
-            runtime: Runtime::new().unwrap(),
+            handle: runtime.handle().clone(),
+            runtime: Mutex::new(Some(runtime)),
+            shutdown: Arc::new(Shutdown::default()),
+            shutdown_grace: duration_from_secs("shutdown_grace_secs", shutdown_grace_secs)?,
+            polling,
+            timeouts,
+            file_read,
+            gauges: Arc::new(InFlightGauges::new(notifier, status_notifier)),
+            router: Arc::new(router),
+            rate_limiters: Arc::new(rate_limiters),
+            markdown_support,
+            rate_limiter_group,
+            logs_enabled: enable_logs,
+            active_permits_url: Arc::default(),
+            active_permits_file: Arc::default(),
+            total_requests: Arc::default(),
+            total_errors: Arc::default(),
         })
     }
+    /// Snapshot of how many documents are in each phase right now.
+    ///
+    /// Returns:
+    ///     dict: `{"waiting": int, "submitting": int, "polling": int}`. All counts
+    ///         are zero once every batch on this client has completed.
+    pub fn in_flight(&self, py: Python) -> PyResult<Py<PyAny>> {
+        Ok(self.gauges.to_dict(py)?.into_any().unbind())
+    }
+
+    /// Internal counters for this client's runtime and batches.
+    ///
+    /// Returns:
+    ///     dict: `tokio_threads` (runtime worker threads), `active_permits_url` and
+    ///         `active_permits_file` (documents from URLs and from uploaded files or
+    ///         bytes currently held by a worker), `total_requests` (documents
+    ///         taken by a worker so far) and `total_errors` (how many of them
+    ///         failed or timed out)
+    pub fn get_runtime_stats(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let stats = PyDict::new(py);
+        stats.set_item("tokio_threads", self.handle.metrics().num_workers())?;
+        stats.set_item("active_permits_url", self.active_permits_url.load(Ordering::Relaxed))?;
+        stats.set_item("active_permits_file", self.active_permits_file.load(Ordering::Relaxed))?;
+        stats.set_item("total_requests", self.total_requests.load(Ordering::Relaxed))?;
+        stats.set_item("total_errors", self.total_errors.load(Ordering::Relaxed))?;
+        Ok(stats.into_any().unbind())
+    }
+
+    /// Per-credential routing statistics accumulated over this client's lifetime.
+    ///
+    /// Returns:
+    ///     list[dict]: One entry per credential, in order, with `endpoint`,
+    ///         `requests` (documents routed to it) and `latency_ms` (EWMA of the
+    ///         submission latency, or None before the first measurement). A
+    ///         submission that fails or times out counts as taking at least 10
+    ///         seconds.
+    pub fn credential_stats(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let stats = self
+            .router
+            .stats(self.credentials.iter().map(|creds| creds.endpoint.clone()));
+        Ok(pythonize(py, &stats)?.unbind())
+    }
+
+    /// Authentication health of each credential.
+    ///
+    /// A credential that gets `auth_failure_threshold` 401/403 responses in a
+    /// row is skipped by routing until a probe document sent to it every
+    /// `auth_probe_interval_secs` is accepted. Documents only fail with an
+    /// authentication error when every credential rejects them or is skipped.
+    ///
+    /// Returns:
+    ///     list[dict]: One entry per credential, in order, with `endpoint`,
+    ///         `healthy` (False while skipped), `consecutive_failures` (401/403
+    ///         responses since a document was last accepted) and `last_error`
+    ///         (message of the latest 401/403, or None)
+    pub fn credential_status(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let health = self
+            .router
+            .health(self.credentials.iter().map(|creds| creds.endpoint.clone()));
+        Ok(pythonize(py, &health)?.unbind())
+    }
+
+    /// Summarize a finished batch as a Markdown or HTML report.
+    ///
+    /// Covers the document count, success rate, pages analyzed, throttling
+    /// events, failures grouped by category (`AnalysisError.kind`, or the
+    /// exception type) with each failed document's source and message, the
+    /// slowest documents, and how the batch's documents were spread over the
+    /// credentials. Everything is taken from the results' `elapsed_secs`,
+    /// `throttled` and `endpoint` attributes, so the report describes that batch
+    /// only.
+    ///
+    /// Args:
+    ///     results (list | dict): Results returned by one of the batch methods
+    ///     format (str): 'markdown' (default) or 'html'
+    ///     path (str | os.PathLike | None): Write the report to this file instead
+    ///         of returning it. Defaults to None.
+    ///     redact_urls (bool): Drop the query string of every URL, since it often
+    ///         carries a SAS token. The report states whether this was on.
+    ///         Defaults to True.
+    ///
+    /// Returns:
+    ///     str | None: The report, or None when it was written to `path`
+    ///
+    /// Raises:
+    ///     ValueError: If `format` is not recognized
+    ///     TypeError: If an item is not an AnalyzeResult, CheckpointedResult or
+    ///         Exception
+    ///     IOError: If `path` cannot be written
+    ///
+    /// Example:
+    ///     >>> results = client.process_batch_documents_from_urls("prebuilt-layout", urls)
+    ///     >>> client.generate_report(results, format="html", path="report.html")
+    #[pyo3(signature = (results, format="markdown", path=None, redact_urls=true))]
+    pub fn generate_report(
+        &self,
+        py: Python,
+        results: Bound<'_, PyAny>,
+        format: &str,
+        path: Option<PathBuf>,
+        redact_urls: bool,
+    ) -> PyResult<Option<String>> {
+        let format = ReportFormat::from_str(format)?;
+        let items = match results.cast::<PyDict>() {
+            Ok(dict) => dict.values().into_any(),
+            Err(_) => results.clone(),
+        };
+
+        let mut report = BatchReport {
+            documents: 0,
+            succeeded: 0,
+            pages: 0,
+            failures: Vec::new(),
+            timings: Vec::new(),
+            redact_urls,
+        };
+        for item in items.try_iter()? {
+            let item = item?;
+            report.documents += 1;
+            let source = match item.getattr_opt("source")? {
+                Some(source) if !source.is_none() => Some(source.str()?.to_string()),
+                _ => None,
+            };
+            if let Some(elapsed) = item.getattr_opt("elapsed_secs")?.filter(|elapsed| !elapsed.is_none()) {
+                report.timings.push(ReportTiming {
+                    source: source.clone(),
+                    elapsed_secs: elapsed.extract()?,
+                    throttled: item.getattr("throttled")?.extract::<Option<u32>>()?.unwrap_or(0),
+                    endpoint: item.getattr("endpoint")?.extract()?,
+                });
+            }
+            if let Ok(result) = item.cast::<AnalyzeResult>() {
+                report.succeeded += 1;
+                report.pages += result.borrow().pages.len();
+            } else if item.is_instance_of::<CheckpointedResult>() {
+                report.succeeded += 1;
+            } else if item.is_instance_of::<PyBaseException>() {
+                let category = match item.getattr_opt("kind")? {
+                    Some(kind) if !kind.is_none() => kind.str()?.to_string(),
+                    _ => item.get_type().name()?.to_string(),
+                };
+                report.failures.push(ReportFailure { source, category, message: item.str()?.to_string() });
+            } else {
+                return Err(PyTypeError::new_err(format!(
+                    "results[{}] is not an AnalyzeResult, CheckpointedResult or Exception",
+                    report.documents - 1
+                )));
+            }
+        }
+
+        let text = report.render(format);
+        match path {
+            Some(path) => {
+                py.detach(|| std::fs::write(long_path(&path), text)).map_err(|e| {
+                    PyIOError::new_err(format!("Failed to write report {}: {}", path.display(), e))
+                })?;
+                Ok(None)
+            }
+            None => Ok(Some(text)),
+        }
+    }
+
+    /// Describe this client's configuration and counters for bug reports.
+    ///
+    /// Safe to call at any time, including after `close()`. Secrets are never
+    /// included; credentials are listed by endpoint, cloud and auth kind only. The result
+    /// contains only plain types, so `json.dumps(client.info())` always works.
+    ///
+    /// Returns:
+    ///     dict: `version`, `api_version`, `credentials`, `runtime`, `rate_limits`,
+    ///         `routing`, `retry_policy`, `timeouts`, `logging`, `markdown_models`,
+    ///         `metrics` (per-credential request counts and latencies) and
+    ///         `in_flight`
+    ///
+    /// Example:
+    ///     >>> import json
+    ///     >>> print(json.dumps(client.info(), indent=2))
+    pub fn info(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let endpoints = || self.credentials.iter().map(|creds| creds.endpoint.clone());
+        let credentials: Vec<serde_json::Value> = self
+            .credentials
+            .iter()
+            .map(|creds| serde_json::json!({
+                "endpoint": creds.endpoint,
+                "cloud": creds.cloud.to_string(),
+                "auth": if creds.is_bearer_token() { "bearer_token" } else { "api_key" },
+                "weight": creds.weight,
+                "api_key": "<redacted>",
+            }))
+            .collect();
+        let shared_rates: Vec<serde_json::Value> = endpoints()
+            .zip(self.rate_limiters.iter())
+            .map(|(endpoint, bucket)| serde_json::json!({ "endpoint": endpoint, "rps": bucket.rate() }))
+            .collect();
+        let stats = self.router.stats(endpoints());
+        let documents_routed: u64 = stats.iter().map(|stat| stat.requests).sum();
+        let mut markdown_models: Vec<(&String, &bool)> = self.markdown_support.iter().collect();
+        markdown_models.sort();
+
+        let info = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "api_version": API_VERSION,
+            "credentials": credentials,
+            "runtime": {
+                "flavor": format!("{:?}", self.handle.runtime_flavor()),
+                "worker_threads": self.handle.metrics().num_workers(),
+                "closed": self.shutdown.is_closing(),
+                "shutdown_grace_secs": self.shutdown_grace.as_secs_f64(),
+            },
+            "rate_limits": {
+                "default_max_rps": DEFAULT_MAX_RPS,
+                "rate_limiter_group": self.rate_limiter_group,
+                "shared_buckets": shared_rates,
+            },
+            "routing": {
+                "mode": self.router.mode().to_string(),
+                "min_share": self.router.min_share(),
+                "auth_failure_threshold": self.router.health_policy().failure_threshold,
+                "auth_probe_interval_secs": self.router.health_policy().probe_interval.as_secs_f64(),
+            },
+            "retry_policy": {
+                "max_retry_after_secs": self.polling.max_retry_after.as_secs_f64(),
+                "file_read_attempts": self.file_read.attempts,
+                "file_read_retry_delay_secs": self.file_read.delay.as_secs_f64(),
+                "http_max_retries": self.polling.retry.max_retries,
+                "http_initial_backoff_ms": self.polling.retry.initial_backoff.as_millis() as u64,
+            },
+            "timeouts": {
+                "poll_interval_secs": self.polling.interval.as_secs_f64(),
+                "max_poll_interval_secs": self.polling.max_interval.as_secs_f64(),
+                "poll_backoff": self.polling.multiplier,
+                "poll_jitter": self.polling.jitter,
+                "document_timeout_secs": self.timeouts.document.map(|timeout| timeout.as_secs_f64()),
+                "batch_timeout_secs": self.timeouts.batch.map(|timeout| timeout.as_secs_f64()),
+                "max_result_bytes": self.polling.max_result_bytes,
+            },
+            "logging": {
+                "enabled": self.logs_enabled,
+                "subscriber_installed": tracing_initialized(),
+                "rust_log": std::env::var("RUST_LOG").ok(),
+            },
+            "markdown_models": markdown_models
+                .into_iter()
+                .map(|(model, supported)| (model.clone(), serde_json::Value::Bool(*supported)))
+                .collect::<serde_json::Map<String, serde_json::Value>>(),
+            "metrics": {
+                "documents_routed": documents_routed,
+                "credentials": stats,
+            },
+        });
+
+        let dict = pythonize(py, &info)?;
+        dict.cast::<PyDict>()?
+            .set_item("in_flight", self.gauges.to_dict(py)?)?;
+        Ok(dict.unbind())
+    }
+
+    /// Shut the client down, letting in-flight documents finish first.
+    ///
+    /// Running batches stop submitting new documents immediately (those report
+    /// a "closed" error); documents already submitted get up to the grace period
+    /// to complete, after which those still in flight are aborted and reported
+    /// with `kind == "cancelled"`, and the runtime is shut down. Further calls on
+    /// the client raise `RuntimeError`. Calling `close()` again is a no-op.
+    ///
+    /// Args:
+    ///     grace_period_secs (float | None): Overrides the client's
+    ///         `shutdown_grace_secs`. Defaults to None.
+    #[pyo3(signature = (grace_period_secs=None))]
+    pub fn close(&self, py: Python, grace_period_secs: Option<f64>) -> PyResult<()> {
+        let grace = match grace_period_secs {
+            Some(secs) => duration_from_secs("grace_period_secs", secs)?,
+            None => self.shutdown_grace,
+        };
+        py.detach(|| self.shutdown_runtime(grace));
+        Ok(())
+    }
+
+    /// Check that every credential can reach its endpoint and authenticate.
+    ///
+    /// Issues one lightweight authenticated GET per credential, all in parallel.
+    /// API keys are never logged or included in the report.
+    ///
+    /// Args:
+    ///     timeout_secs (float): Per-request timeout. Defaults to 10.
+    ///     raise_on_failure (bool): Raise `RuntimeError` if any credential is not
+    ///         `ok`, instead of only reporting it. Defaults to False.
+    ///
+    /// Returns:
+    ///     list[dict]: One entry per credential, in order, with `endpoint`, `status`
+    ///         (`ok`, `auth_failed`, `throttled`, `unreachable` or `error`),
+    ///         `http_status` and `detail`
+    ///
+    /// Example:
+    ///     >>> for check in client.validate_credentials(timeout_secs=5):
+    ///     ...     print(check["endpoint"], check["status"])
+    #[pyo3(signature = (timeout_secs=10.0, raise_on_failure=false))]
+    pub fn validate_credentials(
+        &self,
+        py: Python,
+        timeout_secs: f64,
+        raise_on_failure: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let timeout = duration_from_secs("timeout_secs", timeout_secs)?;
+        let report = py.detach(|| self.block_on(self.validate_credentials_async(timeout)))?;
+
+        if raise_on_failure {
+            let failed: Vec<String> = report
+                .iter()
+                .filter(|check| check.status != "ok")
+                .map(|check| format!("{} ({})", check.endpoint, check.status))
+                .collect();
+            if !failed.is_empty() {
+                return Err(PyRuntimeError::new_err(format!(
+                    "Credential validation failed for: {}",
+                    failed.join(", ")
+                )));
+            }
+        }
+
+        Ok(pythonize(py, &report)?.unbind())
+    }
+
     /// Process multiple documents from URLs concurrently.
     ///
     /// Analyzes a batch of documents accessible via URLs using the specified
@@ -106,11 +905,70 @@ impl RustyAnalysisClient {
     ///         - 'text' (default): Plain text representation with line breaks
     ///         - 'markdown': Markdown formatted output preserving document structure
     ///         Defaults to 'text' if not specified.
+    ///     max_rps (int): Maximum concurrent requests per resource. Defaults to 15.
+    ///     timeout_secs (float | None): Per-document timeout for this call, overriding
+    ///         the client's `timeout_secs`. Must not exceed the client's
+    ///         `batch_timeout_secs`. Defaults to None (use the client setting).
+    ///     poll_interval (float | None): Initial delay between status polls for this call,
+    ///         overriding the client's `poll_interval`. Defaults to None.
+    ///     priorities (list[int] | None): Submission priority per URL, aligned with
+    ///         `document_urls`; higher values are submitted first and equal values
+    ///         keep their order. Items of `document_urls` may instead be
+    ///         `(url, priority)` tuples. Defaults to None.
+    ///     checkpoint_path (str | os.PathLike | None): JSON Lines file recording each finished
+    ///         document. URLs that already succeeded in a previous call with the same
+    ///         checkpoint, model, features and query options are not resubmitted.
+    ///         Defaults to None.
+    ///     output_dir (str | os.PathLike | None): With `checkpoint_path`, directory where each
+    ///         successful result is saved so skipped documents can be returned in
+    ///         full. Without it, skipped documents are returned as
+    ///         `CheckpointedResult` markers. Defaults to None.
+    ///     select (list[str] | None): Result sections to keep, any of 'content',
+    ///         'pages', 'paragraphs', 'tables', 'languages', 'documents',
+    ///         'key_value_pairs', 'styles', 'figures' and 'sections'. The
+    ///         rest are skipped without being parsed and left out of `to_dict`.
+    ///         Defaults to None (keep everything).
+    ///     strict (bool): Raise `ValueError` when markdown output is requested from a
+    ///         model known not to support it. Otherwise a warning is logged and
+    ///         `outputContentFormat` is left out of the request. Defaults to False.
+    ///     max_retries (int | None): Retries of the analyze request and of each
+    ///         status poll on 429, 500, 502, 503 and 504 responses. Retries wait
+    ///         for `Retry-After` when sent, else back off exponentially with jitter.
+    ///         A retrying document keeps its concurrency slot. Defaults to None (3).
+    ///     initial_backoff_ms (int | None): Delay before the first retry without a
+    ///         `Retry-After`, doubling on each further retry. Defaults to None (500).
+    ///     pages (str | None): Pages to analyze, as 1-based numbers and ranges, e.g.
+    ///         '1-3,5'. Sent as the `pages` query parameter; malformed values raise
+    ///         `ValueError` before anything is submitted. Defaults to None (all
+    ///         pages).
+    ///     locale (str | None): Language hint for OCR, e.g. 'en-US' or 'de', sent
+    ///         as the `locale` query parameter. Defaults to None (the service
+    ///         detects the language).
+    ///     query_fields (list[str] | None): Extra fields to extract, e.g.
+    ///         ['Name', 'Address'], sent as the `queryFields` query parameter. The
+    ///         `queryFields` feature is added when missing; the values come back
+    ///         as fields of `documents`. Defaults to None.
+    ///     config (BatchProcessingConfig | None): Settings built with
+    ///         `BatchProcessingConfig`; those set on it override the matching
+    ///         arguments above. Defaults to None.
+    ///     retry_policy (dict[str, int] | None): HTTP retry settings for this call,
+    ///         with any of 'max_retries', 'initial_backoff_ms' and 'max_backoff_ms'
+    ///         (cap on the backoff delay; 30000 by default). Each may be given here
+    ///         or as an argument, not both. Settings on `config` come first, then
+    ///         these, then the client's. Unknown keys and negative values raise
+    ///         `ValueError`. Defaults to None.
     ///
     /// Returns:
     ///     list: List of results where each item is either:
     ///         - dict: Successfully analyzed document result with full analyzeResult
-    ///         - Exception: Error object if processing failed for that document
+    ///         - DocumentAnalysisError: Error object if processing failed for that
+    ///           document; the subclass (RateLimitError, AuthenticationError, ...)
+    ///           says why
+    ///     Results are always in input order, regardless of priority. Every item,
+    ///     success or failure, has `source` (the input) and `index` (its position
+    ///     in the input list) attributes, and `elapsed_secs`, `throttled` (429
+    ///     responses retried) and `endpoint` (the credential used) for the
+    ///     documents that were started.
     ///
     /// Example:
     ///     >>> urls = [
@@ -129,57 +987,133 @@ impl RustyAnalysisClient {
     ///     ...         print(f"Document {i} failed: {result}")
     ///     ...     else:
     ///     ...         print(f"Document {i} content: {result.get('content', '')[:100]}")
-    #[pyo3(signature = (model_id, document_urls, features=None, output_format= None, max_rps=15), text_signature = "(self, model_id, document_urls, features=None, max_rps=15)")]
+    #[pyo3(signature = (model_id, document_urls, features=None, output_format= None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, poll_interval=None, priorities=None, checkpoint_path=None, output_dir=None, select=None, strict=false, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None), text_signature = "(self, model_id, document_urls, features=None, output_format=None, max_rps=15, timeout_secs=None, poll_interval=None, priorities=None, checkpoint_path=None, output_dir=None, select=None, strict=False, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None)")]
+    #[allow(clippy::too_many_arguments)]
     pub fn process_batch_documents_from_urls(
         &self,
         py: Python,
         model_id: String,
-        document_urls: Vec<String>,
+        document_urls: Vec<BatchInput<String>>,
         features: Option<Vec<String>>,
         output_format: Option<String>,
         max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        priorities: Option<Vec<i64>>,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+        select: Option<Vec<String>>,
+        strict: bool,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+        pages: Option<String>,
+        locale: Option<String>,
+        query_fields: Option<Vec<String>>,
+        config: Option<BatchProcessingConfig>,
+        retry_policy: Option<HashMap<String, i64>>,
     ) -> PyResult<Vec<Py<PyAny>>> {
+        let args = BatchArgs {
+            features, output_format, max_rps, timeout_secs, poll_interval, priorities, checkpoint_path, output_dir,
+            select, strict, skip_unsupported: false, max_retries, initial_backoff_ms, pages, locale, query_fields,
+            config, retry_policy,
+        };
+        self.run_url_batch(py, model_id, document_urls, args)
+    }
 
+    /// Process documents from URLs, then find named entities in each result's
+    /// content with Azure AI Language.
+    ///
+    /// The documents are analyzed as by `process_batch_documents_from_urls`.
+    /// The content of every successful result is then sent to the Language
+    /// resource's entity recognition, split into chunks within the service's
+    /// per-document limit, and the entities are stored on the result (see
+    /// `AnalyzeResult.get_entities`).
+    ///
+    /// Args:
+    ///     model_id (str): The Document Intelligence model ID
+    ///     document_urls (list[str]): Document URLs, optionally as
+    ///         `(url, priority)` tuples
+    ///     language_endpoint (str): Endpoint of the Azure AI Language resource
+    ///     language_api_key (str): API key of the Language resource
+    ///     features (list[str] | None): Analysis features, as for
+    ///         `process_batch_documents_from_urls`. Defaults to None.
+    ///     output_format (str | None): 'text' (default) or 'markdown'
+    ///     max_rps (int): Maximum concurrent requests per resource, also used as
+    ///         the number of concurrent Language requests. Defaults to 15.
+    ///     timeout_secs (float | None): Per-document analysis timeout, overriding
+    ///         the client's. Defaults to None.
+    ///     language (str | None): Language of the documents for entity
+    ///         recognition, e.g. 'en'. Defaults to None (the service default).
+    ///
+    /// Returns:
+    ///     list: `AnalyzeResult` objects with `entities` set, or
+    ///         `DocumentAnalysisError` instances for documents whose analysis
+    ///         or entity recognition failed, in input order
+    ///
+    /// Raises:
+    ///     ValueError: If the Language endpoint is invalid
+    ///
+    /// Example:
+    ///     >>> results = client.process_batch_documents_from_urls_with_entities(
+    ///     ...     "prebuilt-layout",
+    ///     ...     urls,
+    ///     ...     language_endpoint="https://my-language.cognitiveservices.azure.com",
+    ///     ...     language_api_key="language-key",
+    ///     ... )
+    ///     >>> for entity in results[0].get_entities():
+    ///     ...     print(entity["category"], entity["text"])
+    #[pyo3(signature = (model_id, document_urls, language_endpoint, language_api_key, features=None, output_format=None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, language=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_batch_documents_from_urls_with_entities(
+        &self,
+        py: Python,
+        model_id: String,
+        document_urls: Vec<BatchInput<String>>,
+        language_endpoint: String,
+        language_api_key: String,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        language: Option<String>,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let language_creds = Credentials::new(language_endpoint, Some(language_api_key), false, None, None, 1)?;
+        let args = BatchArgs { features, output_format, max_rps, timeout_secs, ..BatchArgs::default() };
+        let mut results = self.run_url_batch(py, model_id, document_urls, args)?;
 
-        let semaphore_size: usize = max_rps.unwrap_or(15) * self.credentials.len();
-        let format_enum = match output_format {
-            Some(s) => OutputContentFormat::from_str(&s)?, // Use our impl
-            None => OutputContentFormat::default(),
-        };
-        let output_format = format_enum.to_string();
-        let rust_results = py.detach(move || {
-            self.runtime.block_on(async {
-                self.process_documents_async_from_urls(
-                    &model_id,
-                    document_urls,
-                    features,
-                    &output_format,
-                    semaphore_size,
-                )
-                .await
+        let texts: Vec<Option<String>> = results
+            .iter()
+            .map(|result| {
+                result
+                    .bind(py)
+                    .cast::<AnalyzeResult>()
+                    .ok()
+                    .map(|result| result.borrow().content.clone())
             })
-        });
-        let mut py_results = Vec::new();
-        let py_exception = py.import("builtins")?.getattr("Exception")?;
-
-        for res in rust_results {
-            match res {
-                Ok(json_value) => match serde_json::from_value::<AnalyzeResult>(json_value) {
-                    Ok(analyze_result_struct) => {
-                        py_results.push(Py::new(py, analyze_result_struct)?.into_any());
-                    }
-                    Err(e) => {
-                        let msg = format!("Deserialization Error: {}", e);
-                        py_results.push(py_exception.call1((msg,))?.unbind());
+            .collect();
+        let concurrency = max_rps.unwrap_or(DEFAULT_MAX_RPS);
+        let polling = self.polling;
+        let entities = py.detach(|| {
+            self.block_on(self.recognize_entities_async(&language_creds, texts, language.as_deref(), concurrency, &polling))
+        })?;
+
+        for (result, entities) in results.iter_mut().zip(entities) {
+            match entities {
+                None => {}
+                Some(Ok(entities)) => {
+                    result.bind(py).cast::<AnalyzeResult>()?.borrow_mut().entities = Some(entities);
+                }
+                Some(Err(err)) => {
+                    let analyzed = result.bind(py);
+                    let error = DocumentAnalysisError::from_error(err).into_exception(py)?;
+                    for tag in ["source", "index", "elapsed_secs", "throttled", "endpoint"] {
+                        error.bind(py).setattr(tag, analyzed.getattr(tag)?)?;
                     }
-                },
-                Err(err_string) => {
-                    py_results.push(py_exception.call1((err_string,))?.unbind());
+                    *result = error;
                 }
             }
         }
-
-        Ok(py_results)
+        Ok(results)
     }
 
     /// Process multiple documents from local file paths concurrently.
@@ -190,18 +1124,83 @@ impl RustyAnalysisClient {
     /// Args:
     ///     model_id (str): The Document Intelligence model ID
     ///         (e.g., 'prebuilt-layout', 'prebuilt-invoice')
-    ///     file_paths (list[str]): List of local file paths to process
+    ///     file_paths (list[str | os.PathLike]): List of local file paths to process.
+    ///         Non-UTF-8 names are supported, and long paths on Windows get the
+    ///         `\\?\` prefix automatically.
     ///     features (list[str] | None): Optional list of analysis features to enable
     ///         (e.g., ['ocrHighResolution', 'formulas', 'styleFont']). Defaults to None.
     ///     output_format (str | None): Optional output content format. Valid values are:
     ///         - 'text' (default): Plain text representation with line breaks
     ///         - 'markdown': Markdown formatted output preserving document structure
     ///         Defaults to 'text' if not specified.
+    ///     max_rps (int): Maximum concurrent requests per resource. Defaults to 15.
+    ///     timeout_secs (float | None): Per-document timeout for this call, overriding
+    ///         the client's `timeout_secs`. Must not exceed the client's
+    ///         `batch_timeout_secs`. Defaults to None (use the client setting).
+    ///     poll_interval (float | None): Initial delay between status polls for this call,
+    ///         overriding the client's `poll_interval`. Defaults to None.
+    ///     priorities (list[int] | None): Submission priority per file, aligned with
+    ///         `file_paths`; higher values are submitted first and equal values keep
+    ///         their order. Items of `file_paths` may instead be `(path, priority)`
+    ///         tuples. Defaults to None.
+    ///     checkpoint_path (str | os.PathLike | None): JSON Lines file recording each finished
+    ///         document. Paths that already succeeded in a previous call with the
+    ///         same checkpoint, model, features and query options are not
+    ///         resubmitted. Defaults to None.
+    ///     output_dir (str | os.PathLike | None): With `checkpoint_path`, directory where each
+    ///         successful result is saved so skipped documents can be returned in
+    ///         full. Without it, skipped documents are returned as
+    ///         `CheckpointedResult` markers. Defaults to None.
+    ///     select (list[str] | None): Result sections to keep, any of 'content',
+    ///         'pages', 'paragraphs', 'tables', 'languages', 'documents',
+    ///         'key_value_pairs', 'styles', 'figures' and 'sections'. The
+    ///         rest are skipped without being parsed and left out of `to_dict`.
+    ///         Defaults to None (keep everything).
+    ///     strict (bool): Raise `ValueError` when markdown output is requested from a
+    ///         model known not to support it. Otherwise a warning is logged and
+    ///         `outputContentFormat` is left out of the request. Defaults to False.
+    ///     skip_unsupported (bool): Don't submit files whose extension the service
+    ///         does not accept (e.g. `.docx`); their result slot holds an
+    ///         `AnalysisError` with `kind == "unsupported_type"` instead. Defaults to
+    ///         False (submit them and let the service reject them).
+    ///     max_retries (int | None): Retries of the analyze request and of each
+    ///         status poll on 429, 500, 502, 503 and 504 responses. Retries wait
+    ///         for `Retry-After` when sent, else back off exponentially with jitter.
+    ///         A retrying document keeps its concurrency slot. Defaults to None (3).
+    ///     initial_backoff_ms (int | None): Delay before the first retry without a
+    ///         `Retry-After`, doubling on each further retry. Defaults to None (500).
+    ///     pages (str | None): Pages to analyze, as 1-based numbers and ranges, e.g.
+    ///         '1-3,5'. Sent as the `pages` query parameter; malformed values raise
+    ///         `ValueError` before anything is submitted. Defaults to None (all
+    ///         pages).
+    ///     locale (str | None): Language hint for OCR, e.g. 'en-US' or 'de', sent
+    ///         as the `locale` query parameter. Defaults to None (the service
+    ///         detects the language).
+    ///     query_fields (list[str] | None): Extra fields to extract, e.g.
+    ///         ['Name', 'Address'], sent as the `queryFields` query parameter. The
+    ///         `queryFields` feature is added when missing; the values come back
+    ///         as fields of `documents`. Defaults to None.
+    ///     config (BatchProcessingConfig | None): Settings built with
+    ///         `BatchProcessingConfig`; those set on it override the matching
+    ///         arguments above. Defaults to None.
+    ///     retry_policy (dict[str, int] | None): HTTP retry settings for this call,
+    ///         with any of 'max_retries', 'initial_backoff_ms' and 'max_backoff_ms'
+    ///         (cap on the backoff delay; 30000 by default). Each may be given here
+    ///         or as an argument, not both. Settings on `config` come first, then
+    ///         these, then the client's. Unknown keys and negative values raise
+    ///         `ValueError`. Defaults to None.
     ///
     /// Returns:
     ///     list: List of results where each item is either:
     ///         - dict: Successfully analyzed document result with full analyzeResult
-    ///         - Exception: Error object if processing failed for that document
+    ///         - DocumentAnalysisError: Error object if processing failed for that
+    ///           document; the subclass (RateLimitError, AuthenticationError, ...)
+    ///           says why
+    ///     Results are always in input order, regardless of priority. Every item,
+    ///     success or failure, has `source` (the input) and `index` (its position
+    ///     in the input list) attributes, and `elapsed_secs`, `throttled` (429
+    ///     responses retried) and `endpoint` (the credential used) for the
+    ///     documents that were started.
     ///
     /// Supported file formats:
     ///     PDF (.pdf), JPEG (.jpg, .jpeg), PNG (.png), TIFF (.tiff, .tif), BMP (.bmp)
@@ -227,54 +1226,1622 @@ impl RustyAnalysisClient {
     ///     ...     else:
     ///     ...         pages = result.get('pages', [])
     ///     ...         print(f"File {i} has {len(pages)} pages")
-    #[pyo3(signature=(model_id, file_paths, features=None, output_format=None, max_rps=15), text_signature = "(self, model_id, file_paths, features=None,  output_format='text', max_rps=15)")]
+    #[pyo3(signature=(model_id, file_paths, features=None, output_format=None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, poll_interval=None, priorities=None, checkpoint_path=None, output_dir=None, select=None, strict=false, skip_unsupported=false, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None), text_signature = "(self, model_id, file_paths, features=None, output_format=None, max_rps=15, timeout_secs=None, poll_interval=None, priorities=None, checkpoint_path=None, output_dir=None, select=None, strict=False, skip_unsupported=False, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None)")]
+    #[allow(clippy::too_many_arguments)]
     fn process_batch_documents_from_file_paths(
         &self,
         py: Python,
         model_id: String,
-        file_paths: Vec<String>,
+        file_paths: Vec<BatchInput<PathBuf>>,
         features: Option<Vec<String>>,
         output_format: Option<String>,
         max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        priorities: Option<Vec<i64>>,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+        select: Option<Vec<String>>,
+        strict: bool,
+        skip_unsupported: bool,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+        pages: Option<String>,
+        locale: Option<String>,
+        query_fields: Option<Vec<String>>,
+        config: Option<BatchProcessingConfig>,
+        retry_policy: Option<HashMap<String, i64>>,
     ) -> PyResult<Vec<Py<PyAny>>> {
-        let semaphore_size = max_rps.unwrap_or(15) * self.credentials.len();
-        let format_enum = match output_format {
-            Some(s) => OutputContentFormat::from_str(&s)?, // Use our impl
-            None => OutputContentFormat::default(),
+        let args = BatchArgs {
+            features, output_format, max_rps, timeout_secs, poll_interval, priorities, checkpoint_path, output_dir,
+            select, strict, skip_unsupported, max_retries, initial_backoff_ms, pages, locale, query_fields, config,
+            retry_policy,
         };
-        let output_format = format_enum.to_string();
-        let rust_results = py.detach(move || {
-            self.runtime.block_on(async {
-                self.process_documents_async_from_file_paths(
-                    &model_id,
-                    file_paths,
-                    features,
-                    &output_format,
-                    semaphore_size,
-                )
-                .await
+        self.run_file_path_batch(py, model_id, file_paths, args)
+    }
+
+    /// Coroutine version of `process_batch_documents_from_urls`, for use from
+    /// `async def` code such as FastAPI handlers.
+    ///
+    /// Takes the same arguments and returns the same list. Awaiting it does not
+    /// block the event loop: the documents are processed on the client's
+    /// worker threads and the coroutine resumes when the batch is done.
+    /// Cancelling the coroutine aborts the documents still in flight.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the client has been closed
+    ///
+    /// Example:
+    ///     >>> results = await client.process_batch_documents_from_urls_async(
+    ///     ...     "prebuilt-layout", urls
+    ///     ... )
+    #[pyo3(signature = (model_id, document_urls, features=None, output_format=None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, poll_interval=None, priorities=None, checkpoint_path=None, output_dir=None, select=None, strict=false, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None), text_signature = "(self, model_id, document_urls, features=None, output_format=None, max_rps=15, timeout_secs=None, poll_interval=None, priorities=None, checkpoint_path=None, output_dir=None, select=None, strict=False, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None)")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn process_batch_documents_from_urls_async(
+        &self,
+        model_id: String,
+        document_urls: Vec<BatchInput<String>>,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        priorities: Option<Vec<i64>>,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+        select: Option<Vec<String>>,
+        strict: bool,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+        pages: Option<String>,
+        locale: Option<String>,
+        query_fields: Option<Vec<String>>,
+        config: Option<BatchProcessingConfig>,
+        retry_policy: Option<HashMap<String, i64>>,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let args = BatchArgs {
+            features, output_format, max_rps, timeout_secs, poll_interval, priorities, checkpoint_path, output_dir,
+            select, strict, skip_unsupported: false, max_retries, initial_backoff_ms, pages, locale, query_fields,
+            config, retry_policy,
+        };
+        let batch = self.prepare_batch(model_id, document_urls, args)?;
+        self.in_runtime(async move {
+            let PreparedBatch { inputs, mut settings, sources, projection, checkpoint_path, output_dir } = batch;
+            let (pending, completed, settings) = run_blocking(move || {
+                let (pending, completed) = plan_checkpoint(inputs, &mut settings, checkpoint_path, output_dir)?;
+                Ok((pending, completed, settings))
             })
-        });
-        let mut py_results = Vec::new();
-        let py_exception = py.import("builtins")?.getattr("Exception")?;
-
-        for res in rust_results {
-            match res {
-                Ok(json_value) => match serde_json::from_value::<AnalyzeResult>(json_value) {
-                    Ok(analyze_result_struct) => {
-                        py_results.push(Py::new(py, analyze_result_struct)?.into_any());
-                    }
-                    Err(e) => {
-                        let msg = format!("Deserialization Error: {}", e);
-                        py_results.push(py_exception.call1((msg,))?.unbind());
-                    }
-                },
-                Err(err_string) => {
-                    py_results.push(py_exception.call1((err_string,))?.unbind());
-                }
+            .await?;
+            let rust_results = self.process_documents_async_from_urls(pending, settings).await;
+            run_blocking(move || {
+                Python::attach(|py| {
+                    let results = merge_checkpointed(py, completed, rust_results, projection.as_ref())?;
+                    tag_results(py, &results, &sources)?;
+                    Ok(results)
+                })
+            })
+            .await
+        })?
+        .await
+    }
+
+    /// Coroutine version of `process_batch_documents_from_file_paths`, for use
+    /// from `async def` code such as FastAPI handlers.
+    ///
+    /// Takes the same arguments and returns the same list. Awaiting it does not
+    /// block the event loop: files are read and processed on the client's
+    /// worker threads and the coroutine resumes when the batch is done.
+    /// Cancelling the coroutine aborts the documents still in flight.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the client has been closed
+    ///
+    /// Example:
+    ///     >>> results = await client.process_batch_documents_from_file_paths_async(
+    ///     ...     "prebuilt-invoice", ["/documents/invoice1.pdf"]
+    ///     ... )
+    #[pyo3(signature = (model_id, file_paths, features=None, output_format=None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, poll_interval=None, priorities=None, checkpoint_path=None, output_dir=None, select=None, strict=false, skip_unsupported=false, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None), text_signature = "(self, model_id, file_paths, features=None, output_format=None, max_rps=15, timeout_secs=None, poll_interval=None, priorities=None, checkpoint_path=None, output_dir=None, select=None, strict=False, skip_unsupported=False, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None)")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn process_batch_documents_from_file_paths_async(
+        &self,
+        model_id: String,
+        file_paths: Vec<BatchInput<PathBuf>>,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        priorities: Option<Vec<i64>>,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+        select: Option<Vec<String>>,
+        strict: bool,
+        skip_unsupported: bool,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+        pages: Option<String>,
+        locale: Option<String>,
+        query_fields: Option<Vec<String>>,
+        config: Option<BatchProcessingConfig>,
+        retry_policy: Option<HashMap<String, i64>>,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let args = BatchArgs {
+            features, output_format, max_rps, timeout_secs, poll_interval, priorities, checkpoint_path, output_dir,
+            select, strict, skip_unsupported, max_retries, initial_backoff_ms, pages, locale, query_fields, config,
+            retry_policy,
+        };
+        let batch = self.prepare_batch(model_id, file_paths, args)?;
+        self.in_runtime(async move {
+            let PreparedBatch { inputs, mut settings, sources, projection, checkpoint_path, output_dir } = batch;
+            let (submitted, priorities, skipped) =
+                split_unsupported(inputs, settings.priorities.take(), skip_unsupported);
+            settings.priorities = priorities;
+            let (pending, completed, settings) = run_blocking(move || {
+                let (pending, completed) = plan_checkpoint(submitted, &mut settings, checkpoint_path, output_dir)?;
+                Ok((pending, completed, settings))
+            })
+            .await?;
+            let rust_results = self.process_documents_async_from_file_paths(pending, settings).await;
+            run_blocking(move || {
+                Python::attach(|py| {
+                    let mut results = merge_checkpointed(py, completed, rust_results, projection.as_ref())?;
+                    insert_skipped(py, &mut results, skipped)?;
+                    tag_results(py, &results, &sources)?;
+                    Ok(results)
+                })
+            })
+            .await
+        })?
+        .await
+    }
+
+    /// Process named documents from URLs, returning results by name.
+    ///
+    /// Like `process_batch_documents_from_urls`, but the inputs are a dict from
+    /// caller-chosen names to URLs, and so is the output, so results never have to be
+    /// matched to inputs by position.
+    ///
+    /// Args:
+    ///     model_id (str): The Document Intelligence model ID
+    ///     documents (dict[str, str]): Document names mapped to URLs.
+    ///         Values may also be `(value, priority)` tuples.
+    ///     features, output_format, max_rps, timeout_secs, poll_interval,
+    ///     checkpoint_path, output_dir, select, strict, max_retries,
+    ///     initial_backoff_ms, pages, locale, query_fields, config, retry_policy: As for
+    ///         `process_batch_documents_from_urls`.
+    ///
+    /// Returns:
+    ///     dict[str, AnalyzeResult | Exception]: The result or error of every
+    ///         document under its name, in the order of `documents`
+    ///
+    /// Example:
+    ///     >>> results = client.process_batch_documents_from_urls_named(
+    ///     ...     "prebuilt-invoice",
+    ///     ...     {"march": "https://example.com/inv-03.pdf", "april": "https://example.com/inv-04.pdf"},
+    ///     ... )
+    ///     >>> print(results["march"].content[:100])
+    #[pyo3(signature = (model_id, documents, features=None, output_format=None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, poll_interval=None, checkpoint_path=None, output_dir=None, select=None, strict=false, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None), text_signature = "(self, model_id, documents, features=None, output_format=None, max_rps=15, timeout_secs=None, poll_interval=None, checkpoint_path=None, output_dir=None, select=None, strict=False, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None)")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_batch_documents_from_urls_named<'py>(
+        &self,
+        py: Python<'py>,
+        model_id: String,
+        documents: Bound<'py, PyDict>,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+        select: Option<Vec<String>>,
+        strict: bool,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+        pages: Option<String>,
+        locale: Option<String>,
+        query_fields: Option<Vec<String>>,
+        config: Option<BatchProcessingConfig>,
+        retry_policy: Option<HashMap<String, i64>>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let (names, inputs): (Vec<String>, Vec<BatchInput<String>>) = documents
+            .iter()
+            .map(|(name, input)| Ok((name.extract()?, input.extract()?)))
+            .collect::<PyResult<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        let positions = input_positions(&names);
+        let args = BatchArgs {
+            features, output_format, max_rps, timeout_secs, poll_interval, checkpoint_path, output_dir, select,
+            strict, max_retries, initial_backoff_ms, pages, locale, query_fields, config, retry_policy,
+            ..BatchArgs::default()
+        };
+        let results = self.run_url_batch(py, model_id, inputs, args)?;
+        results_by_name(py, names, &positions, results)
+    }
+
+    /// Process named documents from local file paths, returning results by name.
+    ///
+    /// Like `process_batch_documents_from_file_paths`, but the inputs are a dict from
+    /// caller-chosen names to paths, and so is the output, so results never have to be
+    /// matched to inputs by position.
+    ///
+    /// Args:
+    ///     model_id (str): The Document Intelligence model ID
+    ///     documents (dict[str, str | os.PathLike]): Document names mapped to file paths.
+    ///         Values may also be `(value, priority)` tuples.
+    ///     features, output_format, max_rps, timeout_secs, poll_interval,
+    ///     checkpoint_path, output_dir, select, strict, max_retries,
+    ///     initial_backoff_ms, pages, locale, query_fields, config, retry_policy: As for
+    ///         `process_batch_documents_from_file_paths`.
+    ///     skip_unsupported (bool): As for `process_batch_documents_from_file_paths`.
+    ///         Defaults to False.
+    ///
+    /// Returns:
+    ///     dict[str, AnalyzeResult | Exception]: The result or error of every
+    ///         document under its name, in the order of `documents`
+    ///
+    /// Example:
+    ///     >>> results = client.process_batch_documents_from_file_paths_named(
+    ///     ...     "prebuilt-receipt",
+    ///     ...     {"lunch": "/receipts/lunch.jpg", "taxi": "/receipts/taxi.png"},
+    ///     ... )
+    ///     >>> failed = [name for name, r in results.items() if isinstance(r, Exception)]
+    #[pyo3(signature = (model_id, documents, features=None, output_format=None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, poll_interval=None, checkpoint_path=None, output_dir=None, select=None, strict=false, skip_unsupported=false, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None), text_signature = "(self, model_id, documents, features=None, output_format=None, max_rps=15, timeout_secs=None, poll_interval=None, checkpoint_path=None, output_dir=None, select=None, strict=False, skip_unsupported=False, max_retries=None, initial_backoff_ms=None, pages=None, locale=None, query_fields=None, config=None, retry_policy=None)")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_batch_documents_from_file_paths_named<'py>(
+        &self,
+        py: Python<'py>,
+        model_id: String,
+        documents: Bound<'py, PyDict>,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+        select: Option<Vec<String>>,
+        strict: bool,
+        skip_unsupported: bool,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+        pages: Option<String>,
+        locale: Option<String>,
+        query_fields: Option<Vec<String>>,
+        config: Option<BatchProcessingConfig>,
+        retry_policy: Option<HashMap<String, i64>>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let (names, inputs): (Vec<String>, Vec<BatchInput<PathBuf>>) = documents
+            .iter()
+            .map(|(name, input)| Ok((name.extract()?, input.extract()?)))
+            .collect::<PyResult<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        let positions = input_positions(&names);
+        let args = BatchArgs {
+            features, output_format, max_rps, timeout_secs, poll_interval, checkpoint_path, output_dir, select,
+            strict, skip_unsupported, max_retries, initial_backoff_ms, pages, locale, query_fields, config,
+            retry_policy,
+            ..BatchArgs::default()
+        };
+        let results = self.run_file_path_batch(py, model_id, inputs, args)?;
+        results_by_name(py, names, &positions, results)
+    }
+
+    /// Process a batch described by a JSON config file.
+    ///
+    /// The file holds an object with `model_id` and exactly one of
+    /// `document_urls` and `file_paths`, plus optional `features` and
+    /// `output_format`; unknown keys are rejected. Relative `file_paths` are
+    /// resolved against the config file's directory. The batch then runs as
+    /// `process_batch_documents_from_urls` or
+    /// `process_batch_documents_from_file_paths` would with those arguments.
+    ///
+    /// Args:
+    ///     config_path (str | os.PathLike): Path to the JSON config file
+    ///
+    /// Returns:
+    ///     list: One result or `DocumentAnalysisError` per document, as returned
+    ///         by the batch method the config maps to
+    ///
+    /// Raises:
+    ///     IOError: If the config file cannot be read
+    ///     ValueError: If the file is not a valid config
+    ///
+    /// Example:
+    ///     >>> # batch.json: {"model_id": "prebuilt-layout",
+    ///     >>> #              "file_paths": ["invoices/a.pdf", "invoices/b.pdf"],
+    ///     >>> #              "output_format": "markdown"}
+    ///     >>> results = client.process_batch_from_config_file("batch.json")
+    pub fn process_batch_from_config_file(&self, py: Python, config_path: PathBuf) -> PyResult<Vec<Py<PyAny>>> {
+        let (config, inputs) = py.detach(|| BatchConfigFile::load(&config_path))?;
+        let args = BatchArgs {
+            features: config.features,
+            output_format: config.output_format,
+            ..BatchArgs::default()
+        };
+        match inputs {
+            BatchConfigInputs::Urls(urls) => {
+                self.run_url_batch(py, config.model_id, urls.into_iter().map(BatchInput::Plain).collect(), args)
+            }
+            BatchConfigInputs::FilePaths(paths) => {
+                self.run_file_path_batch(py, config.model_id, paths.into_iter().map(BatchInput::Plain).collect(), args)
             }
         }
+    }
+
+    /// Process every supported document inside a ZIP archive concurrently.
+    ///
+    /// The archive is read into memory up front; entries with a supported extension
+    /// are uploaded as raw bytes, everything else (directories, unknown file types)
+    /// is skipped.
+    ///
+    /// Args:
+    ///     model_id (str): The Document Intelligence model ID
+    ///         (e.g., 'prebuilt-layout', 'prebuilt-invoice')
+    ///     zip_path (str | os.PathLike): Path to a local ZIP archive
+    ///     features (list[str] | None): Optional list of analysis features to enable.
+    ///         Defaults to None.
+    ///     output_format (str | None): 'text' (default) or 'markdown'
+    ///     max_rps (int): Maximum concurrent requests per resource. Defaults to 15.
+    ///     timeout_secs (float | None): Per-document timeout for this call, overriding
+    ///         the client's `timeout_secs`. Defaults to None (use the client setting).
+    ///     poll_interval (float | None): Initial delay between status polls for this call,
+    ///         overriding the client's `poll_interval`. Defaults to None.
+    ///     select (list[str] | None): Result sections to keep; see
+    ///         `process_batch_documents_from_urls`. Defaults to None.
+    ///     strict (bool): Raise `ValueError` when markdown output is requested from a
+    ///         model known not to support it. Otherwise a warning is logged and
+    ///         `outputContentFormat` is left out of the request. Defaults to False.
+    ///     max_retries (int | None): Retries on 429 and transient 5xx responses;
+    ///         see `process_batch_documents_from_urls`. Defaults to None (3).
+    ///     initial_backoff_ms (int | None): Delay before the first retry without a
+    ///         `Retry-After`. Defaults to None (500).
+    ///     retry_policy (dict[str, int] | None): HTTP retry settings for this call;
+    ///         see `process_batch_documents_from_urls`. Defaults to None.
+    ///     checkpoint_path (str | os.PathLike | None): JSON Lines file recording each finished
+    ///         document; see `process_batch_documents_from_urls`. Entries are
+    ///         matched by their path and content. Defaults to None.
+    ///     output_dir (str | os.PathLike | None): With `checkpoint_path`, directory where each
+    ///         successful result is saved; see `process_batch_documents_from_urls`.
+    ///         Defaults to None.
+    ///
+    /// Returns:
+    ///     dict[str, AnalyzeResult | Exception]: Results keyed by the entry's path
+    ///         inside the archive
+    ///
+    /// Raises:
+    ///     IOError: If the archive cannot be opened, an entry cannot be extracted or
+    ///         exceeds 500 MB, or two entries share the same path
+    ///
+    /// Supported file formats:
+    ///     PDF (.pdf), JPEG (.jpg, .jpeg), PNG (.png), TIFF (.tiff, .tif), BMP (.bmp)
+    ///
+    /// Example:
+    ///     >>> results = client.process_batch_documents_from_zip(
+    ///     ...     "prebuilt-layout", "/documents/invoices.zip"
+    ///     ... )
+    ///     >>> for name, result in results.items():
+    ///     ...     if isinstance(result, Exception):
+    ///     ...         print(f"{name} failed: {result}")
+    #[pyo3(signature=(model_id, zip_path, features=None, output_format=None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, poll_interval=None, select=None, strict=false, max_retries=None, initial_backoff_ms=None, retry_policy=None, checkpoint_path=None, output_dir=None), text_signature = "(self, model_id, zip_path, features=None, output_format=None, max_rps=15, timeout_secs=None, poll_interval=None, select=None, strict=False, max_retries=None, initial_backoff_ms=None, retry_policy=None, checkpoint_path=None, output_dir=None)")]
+    #[allow(clippy::too_many_arguments)]
+    fn process_batch_documents_from_zip(
+        &self,
+        py: Python,
+        model_id: String,
+        zip_path: PathBuf,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        select: Option<Vec<String>>,
+        strict: bool,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+        retry_policy: Option<HashMap<String, i64>>,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+    ) -> PyResult<Py<PyAny>> {
+        let projection = select.as_deref().map(Projection::new).transpose()?;
+        let settings = self.batch_settings(model_id, features, output_format, max_rps, timeout_secs, poll_interval, strict, retry_overrides(max_retries, initial_backoff_ms, retry_policy)?)?;
+        let documents = py
+            .detach(|| read_zip_documents(&zip_path))
+            .map_err(|e| PyIOError::new_err(e.to_string()))?;
+        let names: Vec<String> = documents.iter().map(|document| document.name.clone()).collect();
+
+        let converted = self.run_bytes(py, documents, settings, checkpoint_path, output_dir, projection.as_ref())?;
+        let results = PyDict::new(py);
+        for (name, result) in names.into_iter().zip(converted) {
+            results.set_item(name, result)?;
+        }
+        Ok(results.into_any().unbind())
+    }
+
+    /// Process multiple in-memory file-like objects or images concurrently.
+    ///
+    /// Accepts any object with a binary `read(size)` method, such as open files,
+    /// `zipfile.ZipFile.open()` entries or S3 streaming bodies, so nothing has to
+    /// be written to disk first. Each object is read once, in bounded chunks,
+    /// before the batch is submitted; it is not closed afterwards.
+    ///
+    /// PIL images and uint8 image arrays (numpy-style `(H, W)`, `(H, W, 3)` or
+    /// `(H, W, 4)`) are also accepted and encoded in memory with Pillow.
+    ///
+    /// Args:
+    ///     model_id (str): The Document Intelligence model ID
+    ///         (e.g., 'prebuilt-layout', 'prebuilt-invoice')
+    ///     files (list): File-like objects, PIL images or image arrays. A file's
+    ///         `name` attribute, if present, is used to detect the content type
+    ///         from its extension.
+    ///     features (list[str] | None): Optional list of analysis features to enable.
+    ///         Defaults to None.
+    ///     output_format (str | None): 'text' (default) or 'markdown'
+    ///     max_rps (int): Maximum concurrent requests per resource. Defaults to 15.
+    ///     timeout_secs (float | None): Per-document timeout for this call, overriding
+    ///         the client's `timeout_secs`. Defaults to None (use the client setting).
+    ///     poll_interval (float | None): Initial delay between status polls for this call,
+    ///         overriding the client's `poll_interval`. Defaults to None.
+    ///     select (list[str] | None): Result sections to keep; see
+    ///         `process_batch_documents_from_urls`. Defaults to None.
+    ///     image_format (str | None): Encoding for images and arrays, 'png' (default)
+    ///         or 'jpeg'.
+    ///     jpeg_quality (int): Quality from 1 to 100 when `image_format` is 'jpeg'.
+    ///         Defaults to 90.
+    ///     strict (bool): Raise `ValueError` when markdown output is requested from a
+    ///         model known not to support it. Otherwise a warning is logged and
+    ///         `outputContentFormat` is left out of the request. Defaults to False.
+    ///     max_retries (int | None): Retries on 429 and transient 5xx responses;
+    ///         see `process_batch_documents_from_urls`. Defaults to None (3).
+    ///     initial_backoff_ms (int | None): Delay before the first retry without a
+    ///         `Retry-After`. Defaults to None (500).
+    ///     retry_policy (dict[str, int] | None): HTTP retry settings for this call;
+    ///         see `process_batch_documents_from_urls`. Defaults to None.
+    ///     checkpoint_path (str | os.PathLike | None): JSON Lines file recording each finished
+    ///         document; see `process_batch_documents_from_urls`. Documents
+    ///         are matched by their name and content. Defaults to None.
+    ///     output_dir (str | os.PathLike | None): With `checkpoint_path`, directory where each
+    ///         successful result is saved; see `process_batch_documents_from_urls`.
+    ///         Defaults to None.
+    ///
+    /// Returns:
+    ///     list[AnalyzeResult | Exception]: Results in input order, each with
+    ///         `source` (the object's name) and `index` attributes
+    ///
+    /// Raises:
+    ///     TypeError: If an object is neither readable nor an image, its `read()`
+    ///         does not return bytes, or an array is not uint8; the message names
+    ///         its index
+    ///     ValueError: If an array has an unsupported shape, `image_format` is
+    ///         unknown, or `jpeg_quality` is outside 1-100 for 'jpeg'
+    ///     ImportError: If an array is given and Pillow is not installed
+    ///
+    /// Example:
+    ///     >>> with zipfile.ZipFile("invoices.zip") as archive:
+    ///     ...     files = [archive.open(name) for name in archive.namelist()]
+    ///     ...     results = client.process_batch_documents_from_file_objects(
+    ///     ...         "prebuilt-invoice", files
+    ///     ...     )
+    #[pyo3(signature=(model_id, files, features=None, output_format=None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, poll_interval=None, select=None, image_format=None, jpeg_quality=90, strict=false, max_retries=None, initial_backoff_ms=None, retry_policy=None, checkpoint_path=None, output_dir=None), text_signature = "(self, model_id, files, features=None, output_format=None, max_rps=15, timeout_secs=None, poll_interval=None, select=None, image_format=None, jpeg_quality=90, strict=False, max_retries=None, initial_backoff_ms=None, retry_policy=None, checkpoint_path=None, output_dir=None)")]
+    #[allow(clippy::too_many_arguments)]
+    fn process_batch_documents_from_file_objects(
+        &self,
+        py: Python,
+        model_id: String,
+        files: Vec<Bound<'_, PyAny>>,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        select: Option<Vec<String>>,
+        image_format: Option<String>,
+        jpeg_quality: i64,
+        strict: bool,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+        retry_policy: Option<HashMap<String, i64>>,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let projection = select.as_deref().map(Projection::new).transpose()?;
+        let encoding = ImageEncoding::new(image_format.as_deref(), jpeg_quality)?;
+        let settings = self.batch_settings(model_id, features, output_format, max_rps, timeout_secs, poll_interval, strict, retry_overrides(max_retries, initial_backoff_ms, retry_policy)?)?;
+        let documents = files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| match encode_image(file, index, encoding)? {
+                Some(image) => Ok(image),
+                None => DocumentBytes::from_file_object(file, index),
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        self.run_bytes(py, documents, settings, checkpoint_path, output_dir, projection.as_ref())
+    }
+
+    /// Process multiple in-memory documents concurrently.
+    ///
+    /// For documents generated or received in memory (e.g. web uploads), so they
+    /// don't have to be written to temporary files first. Documents are spread
+    /// over the credentials and rate-limited exactly like the other batch methods.
+    ///
+    /// Args:
+    ///     model_id (str): The Document Intelligence model ID
+    ///         (e.g., 'prebuilt-layout', 'prebuilt-invoice')
+    ///     documents (list): Items of `bytes`, `(name, bytes)` or
+    ///         `(name, bytes, content_type)`. The name appears in logs and errors,
+    ///         and its extension picks the content type when none is given;
+    ///         otherwise the type is sniffed from the data's magic bytes (PDF,
+    ///         JPEG, PNG, TIFF, BMP).
+    ///     features (list[str] | None): Optional list of analysis features to enable.
+    ///         Defaults to None.
+    ///     output_format (str | None): 'text' (default) or 'markdown'
+    ///     max_rps (int): Maximum concurrent requests per resource. Defaults to 15.
+    ///     timeout_secs (float | None): Per-document timeout for this call, overriding
+    ///         the client's `timeout_secs`. Defaults to None (use the client setting).
+    ///     poll_interval (float | None): Initial delay between status polls for this call,
+    ///         overriding the client's `poll_interval`. Defaults to None.
+    ///     select (list[str] | None): Result sections to keep; see
+    ///         `process_batch_documents_from_urls`. Defaults to None.
+    ///     strict (bool): Raise `ValueError` when markdown output is requested from a
+    ///         model known not to support it. Otherwise a warning is logged and
+    ///         `outputContentFormat` is left out of the request. Defaults to False.
+    ///     max_retries (int | None): Retries on 429 and transient 5xx responses;
+    ///         see `process_batch_documents_from_urls`. Defaults to None (3).
+    ///     initial_backoff_ms (int | None): Delay before the first retry without a
+    ///         `Retry-After`. Defaults to None (500).
+    ///     retry_policy (dict[str, int] | None): HTTP retry settings for this call;
+    ///         see `process_batch_documents_from_urls`. Defaults to None.
+    ///     checkpoint_path (str | os.PathLike | None): JSON Lines file recording each finished
+    ///         document; see `process_batch_documents_from_urls`. Documents are
+    ///         matched by their name and content. Defaults to None.
+    ///     output_dir (str | os.PathLike | None): With `checkpoint_path`, directory where each
+    ///         successful result is saved; see `process_batch_documents_from_urls`.
+    ///         Defaults to None.
+    ///
+    /// Returns:
+    ///     list[AnalyzeResult | Exception]: Results in input order, each with
+    ///         `source` (the document's name, or `document-{i}`) and `index`
+    ///         attributes
+    ///
+    /// Raises:
+    ///     ValueError: If a given content type is not one the service accepts
+    ///
+    /// Example:
+    ///     >>> results = client.process_batch_documents_from_bytes(
+    ///     ...     "prebuilt-layout",
+    ///     ...     [("upload.pdf", pdf_bytes), png_bytes],
+    ///     ... )
+    #[pyo3(signature=(model_id, documents, features=None, output_format=None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, poll_interval=None, select=None, strict=false, max_retries=None, initial_backoff_ms=None, retry_policy=None, checkpoint_path=None, output_dir=None), text_signature = "(self, model_id, documents, features=None, output_format=None, max_rps=15, timeout_secs=None, poll_interval=None, select=None, strict=False, max_retries=None, initial_backoff_ms=None, retry_policy=None, checkpoint_path=None, output_dir=None)")]
+    #[allow(clippy::too_many_arguments)]
+    fn process_batch_documents_from_bytes(
+        &self,
+        py: Python,
+        model_id: String,
+        documents: Vec<BytesInput<'_>>,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        select: Option<Vec<String>>,
+        strict: bool,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+        retry_policy: Option<HashMap<String, i64>>,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let projection = select.as_deref().map(Projection::new).transpose()?;
+        let settings = self.batch_settings(model_id, features, output_format, max_rps, timeout_secs, poll_interval, strict, retry_overrides(max_retries, initial_backoff_ms, retry_policy)?)?;
+        let documents = documents
+            .into_iter()
+            .enumerate()
+            .map(|(index, document)| document.into_document(index))
+            .collect::<PyResult<Vec<_>>>()?;
+        self.run_bytes(py, documents, settings, checkpoint_path, output_dir, projection.as_ref())
+    }
+
+    /// Process multiple in-memory documents, each with an explicit MIME type.
+    ///
+    /// The bytes are uploaded directly with the given `Content-Type`, skipping all
+    /// filesystem I/O. See `process_batch_documents_from_bytes` for named
+    /// documents and content type detection.
+    ///
+    /// Args:
+    ///     model_id (str): The Document Intelligence model ID
+    ///         (e.g., 'prebuilt-layout', 'prebuilt-invoice')
+    ///     documents (list[tuple[bytes, str]]): `(data, mime_type)` pairs. The MIME
+    ///         type must be one the service accepts: application/pdf, image/jpeg,
+    ///         image/png, image/tiff or image/bmp.
+    ///     features (list[str] | None): Optional list of analysis features to enable.
+    ///         Defaults to None.
+    ///     output_format (str | None): 'text' (default) or 'markdown'
+    ///     max_rps (int): Maximum concurrent requests per resource. Defaults to 15.
+    ///     timeout_secs (float | None): Per-document timeout for this call, overriding
+    ///         the client's `timeout_secs`. Defaults to None (use the client setting).
+    ///     poll_interval (float | None): Initial delay between status polls for this call,
+    ///         overriding the client's `poll_interval`. Defaults to None.
+    ///     select (list[str] | None): Result sections to keep; see
+    ///         `process_batch_documents_from_urls`. Defaults to None.
+    ///     strict (bool): Raise `ValueError` when markdown output is requested from a
+    ///         model known not to support it. Defaults to False.
+    ///     max_retries (int | None): Retries on 429 and transient 5xx responses;
+    ///         see `process_batch_documents_from_urls`. Defaults to None (3).
+    ///     initial_backoff_ms (int | None): Delay before the first retry without a
+    ///         `Retry-After`. Defaults to None (500).
+    ///     retry_policy (dict[str, int] | None): HTTP retry settings for this call;
+    ///         see `process_batch_documents_from_urls`. Defaults to None.
+    ///     checkpoint_path (str | os.PathLike | None): JSON Lines file recording each finished
+    ///         document; see `process_batch_documents_from_urls`. Documents are
+    ///         matched by their content. Defaults to None.
+    ///     output_dir (str | os.PathLike | None): With `checkpoint_path`, directory where each
+    ///         successful result is saved; see `process_batch_documents_from_urls`.
+    ///         Defaults to None.
+    ///
+    /// Returns:
+    ///     list[AnalyzeResult | Exception]: Results in input order, each with
+    ///         `source` (`document-{i}`) and `index` attributes
+    ///
+    /// Raises:
+    ///     ValueError: If a MIME type is not supported; the message names its index
+    #[pyo3(signature=(model_id, documents, features=None, output_format=None, max_rps=DEFAULT_MAX_RPS, timeout_secs=None, poll_interval=None, select=None, strict=false, max_retries=None, initial_backoff_ms=None, retry_policy=None, checkpoint_path=None, output_dir=None), text_signature = "(self, model_id, documents, features=None, output_format=None, max_rps=15, timeout_secs=None, poll_interval=None, select=None, strict=False, max_retries=None, initial_backoff_ms=None, retry_policy=None, checkpoint_path=None, output_dir=None)")]
+    #[allow(clippy::too_many_arguments)]
+    fn process_batch_from_bytes(
+        &self,
+        py: Python,
+        model_id: String,
+        documents: Vec<(Bound<'_, PyBytes>, String)>,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        select: Option<Vec<String>>,
+        strict: bool,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+        retry_policy: Option<HashMap<String, i64>>,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let projection = select.as_deref().map(Projection::new).transpose()?;
+        let settings = self.batch_settings(model_id, features, output_format, max_rps, timeout_secs, poll_interval, strict, retry_overrides(max_retries, initial_backoff_ms, retry_policy)?)?;
+        let documents = documents
+            .into_iter()
+            .enumerate()
+            .map(|(index, (data, mime_type))| {
+                let content_type = DocumentBytes::validate_content_type(&mime_type, index)?;
+                Ok(DocumentBytes::from_bytes(None, data.as_bytes().to_vec(), Some(content_type), index))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        self.run_bytes(py, documents, settings, checkpoint_path, output_dir, projection.as_ref())
+    }
+
+    /// Analyze one large document as several page ranges in parallel.
+    ///
+    /// The document is split into `pages` ranges of `pages_per_request` pages,
+    /// which are analyzed concurrently across the credential pool and merged into
+    /// a single result with document-wide page numbers and span offsets. A range
+    /// that fails with a retryable error is resubmitted on its own.
+    ///
+    /// Args:
+    ///     model_id (str): The Document Intelligence model ID
+    ///     url_or_path (str): A document URL (http/https) or a local file path
+    ///     pages_per_request (int): Pages per analyze request. Defaults to 100.
+    ///     total_pages (int | None): Page count of the document, if known. When None,
+    ///         a local TIFF is counted from its directory chain; for anything else
+    ///         ranges are submitted in waves of one per credential until the end
+    ///         of the document is found. Defaults to None.
+    ///     features (list[str] | None): Optional list of analysis features to enable.
+    ///         Defaults to None.
+    ///     output_format (str | None): 'text' (default) or 'markdown'
+    ///     max_retries (int): Resubmissions allowed per failed range. Defaults to 2.
+    ///     timeout_secs (float | None): Per-request timeout, overriding the client's
+    ///         `timeout_secs`. Defaults to None (use the client setting).
+    ///     poll_interval (float | None): Delay between status polls, overriding the
+    ///         client's `poll_interval`. Defaults to None.
+    ///     initial_backoff_ms (int | None): Delay before the first HTTP retry of a
+    ///         request on 429 or 5xx without a `Retry-After`, doubling on each
+    ///         further retry. Defaults to None (the client's setting).
+    ///     retry_policy (dict[str, int] | None): HTTP retry settings for each
+    ///         request, with any of 'max_retries' (HTTP retries, unlike the
+    ///         `max_retries` argument), 'initial_backoff_ms' and 'max_backoff_ms'.
+    ///         Unknown keys and negative values raise `ValueError`. Defaults to None.
+    ///
+    /// Returns:
+    ///     AnalyzeResult: The merged result. Its `page_ranges` lists every request
+    ///         that was made; each one is billed separately for its `page_count`.
+    ///
+    /// Raises:
+    ///     ValueError: If `pages_per_request` or `total_pages` is zero, a local TIFF
+    ///         is truncated or malformed, or `retry_policy` is invalid
+    ///     IOError: If the local file cannot be read
+    ///     RuntimeError: If a range still fails after `max_retries` resubmissions
+    ///
+    /// Example:
+    ///     >>> result = client.analyze_document_paged(
+    ///     ...     "prebuilt-layout", "/filings/10-k.pdf", pages_per_request=100
+    ///     ... )
+    ///     >>> print(len(result.pages), [r.pages for r in result.page_ranges])
+    #[pyo3(signature=(model_id, url_or_path, pages_per_request=100, total_pages=None, features=None, output_format=None, max_retries=2, timeout_secs=None, poll_interval=None, initial_backoff_ms=None, retry_policy=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_document_paged(
+        &self,
+        py: Python,
+        model_id: String,
+        url_or_path: String,
+        pages_per_request: usize,
+        total_pages: Option<usize>,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_retries: u32,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        initial_backoff_ms: Option<u64>,
+        retry_policy: Option<HashMap<String, i64>>,
+    ) -> PyResult<AnalyzeResult> {
+        if pages_per_request == 0 {
+            return Err(PyValueError::new_err("pages_per_request must be at least 1"));
+        }
+        if total_pages == Some(0) {
+            return Err(PyValueError::new_err("total_pages must be at least 1"));
+        }
+        let retry = retry_overrides(None, initial_backoff_ms, retry_policy)?;
+        let settings = self.batch_settings(model_id, features, output_format, None, timeout_secs, poll_interval, false, retry)?;
+
+        let mut total_pages = total_pages;
+        let source = if url_or_path.starts_with("http://") || url_or_path.starts_with("https://") {
+            PagedSource::Url(url_or_path)
+        } else {
+            let data = py
+                .detach(|| std::fs::read(long_path(Path::new(&url_or_path))))
+                .map_err(|e| PyIOError::new_err(format!("Failed to read file {}: {}", url_or_path, e)))?;
+            let name = Path::new(&url_or_path)
+                .file_name()
+                .map_or_else(|| url_or_path.clone(), |name| name.to_string_lossy().into_owned());
+            let content_type = get_content_type(&url_or_path);
+            // A TIFF's page count is known up front, so the ranges (and the
+            // requests billed for them) can be planned without probing.
+            if content_type == "image/tiff" && total_pages.is_none() {
+                let pages = count_tiff_pages(Cursor::new(&data[..]))
+                    .map_err(|e| PyValueError::new_err(format!("Invalid TIFF {}: {}", url_or_path, e)))?;
+                total_pages = Some(pages);
+            }
+            PagedSource::Bytes(DocumentBytes { name, data: data.into(), content_type: content_type.to_string() })
+        };
+
+        let (merged, page_ranges) = py
+            .detach(move || {
+                self.block_on(self.analyze_document_paged_async(
+                    source,
+                    settings,
+                    pages_per_request,
+                    total_pages,
+                    max_retries,
+                ))
+            })?
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+        let mut result: AnalyzeResult = serde_json::from_value(merged)
+            .map_err(|e| PyRuntimeError::new_err(format!("Deserialization Error: {}", e)))?;
+        result.page_ranges = Some(page_ranges);
+        Ok(result)
+    }
+}
+
+impl RustyAnalysisClient {
+    /// Runs `future` to completion on the client's runtime, failing if the
+    /// client has been closed.
+    pub(crate) fn block_on<F: Future>(&self, future: F) -> PyResult<F::Output> {
+        let _active = self
+            .shutdown
+            .enter()
+            .ok_or_else(|| PyRuntimeError::new_err("RustyAnalysisClient is closed"))?;
+        Ok(self.handle.block_on(future))
+    }
+
+    /// Stops new submissions, waits up to `grace` for running batches, aborts
+    /// the documents still in flight, then shuts the runtime down. Safe to call
+    /// more than once.
+    fn shutdown_runtime(&self, grace: Duration) {
+        self.shutdown.close();
+        let Some(runtime) = self.runtime.lock().unwrap().take() else {
+            return;
+        };
+        if Handle::try_current().is_ok() {
+            // Called from a task or callback running on this runtime: blocking
+            // here would wait on ourselves, so let the workers wind down alone.
+            self.shutdown.cancel();
+            runtime.shutdown_background();
+            return;
+        }
+        let started = Instant::now();
+        if !self.shutdown.wait_idle(grace) {
+            // Abort what is still in flight so batches return, reporting those
+            // documents as cancelled, while the runtime can still drive them.
+            self.shutdown.cancel();
+            self.shutdown.wait_idle(CANCEL_WAIT);
+        }
+        runtime.shutdown_timeout(grace.saturating_sub(started.elapsed()));
+    }
+
+    /// Resolves the settings for one batch call.
+    ///
+    /// Per-call arguments take precedence over the client configuration, which
+    /// takes precedence over the defaults. The batch deadline always comes from
+    /// the client, and a per-call document timeout may not exceed it.
+    ///
+    /// Markdown requested from a model known not to support it is dropped with a
+    /// warning, or rejected when `strict` is set. `retry` overrides the client's
+    /// HTTP retry policy.
+    #[allow(clippy::too_many_arguments)]
+    fn batch_settings(
+        &self,
+        model_id: String,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        strict: bool,
+        retry: RetryOverrides,
+    ) -> PyResult<BatchSettings> {
+        let format_enum = match output_format {
+            Some(s) => OutputContentFormat::from_str(&s)?, // Use our impl
+            None => OutputContentFormat::default(),
+        };
+        let output_format = match format_enum {
+            OutputContentFormat::Markdown if self.markdown_support.get(&model_id) == Some(&false) => {
+                if strict {
+                    return Err(PyValueError::new_err(format!(
+                        "Model '{}' does not support markdown output. Use output_format='text', \
+                         or declare support via the client's markdown_models.",
+                        model_id
+                    )));
+                }
+                warn!(
+                    model_id = model_id.as_str(),
+                    "Model does not support markdown output; omitting outputContentFormat"
+                );
+                None
+            }
+            format => Some(format.to_string()),
+        };
+
+        let mut polling = self.polling;
+        if let Some(secs) = poll_interval {
+            polling.interval = duration_from_secs("poll_interval", secs)?;
+        }
+        polling.retry = retry.apply(polling.retry).map_err(PyValueError::new_err)?;
+
+        let mut timeouts = self.timeouts;
+        if let Some(secs) = timeout_secs {
+            timeouts.document = Some(duration_from_secs("timeout_secs", secs)?);
+        }
+        timeouts.validate().map_err(PyValueError::new_err)?;
+
+        Ok(BatchSettings {
+            options: AnalyzeOptions {
+                model_id,
+                features,
+                output_format,
+                pages: None,
+                locale: None,
+                query_fields: None,
+            },
+            semaphore_size: max_rps.unwrap_or(DEFAULT_MAX_RPS) * self.credentials.len(),
+            polling,
+            timeouts,
+            file_read: self.file_read,
+            priorities: None,
+            checkpoint: None,
+        })
+    }
+
+    /// `batch_settings` with the settings made on `config` taking precedence
+    /// over the corresponding arguments.
+    #[allow(clippy::too_many_arguments)]
+    fn configured_batch_settings(
+        &self,
+        config: Option<BatchProcessingConfig>,
+        model_id: String,
+        features: Option<Vec<String>>,
+        output_format: Option<String>,
+        max_rps: Option<usize>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        strict: bool,
+        retry: RetryOverrides,
+    ) -> PyResult<BatchSettings> {
+        let config = config.unwrap_or_default();
+        let retry = RetryOverrides {
+            max_retries: config.max_retries.or(retry.max_retries),
+            initial_backoff: config.initial_backoff_ms.map(Duration::from_millis).or(retry.initial_backoff),
+            max_backoff: retry.max_backoff,
+        };
+        self.batch_settings(
+            model_id,
+            config.features.or(features),
+            config.output_format.or(output_format),
+            config.max_rps.or(max_rps),
+            config.timeout_secs.or(timeout_secs),
+            config.poll_interval.or(poll_interval),
+            strict,
+            retry,
+        )
+    }
+
+    /// Validates `args` and resolves them into the settings for a batch of
+    /// `inputs`, separating the inputs from their priorities.
+    fn prepare_batch<T: BatchSource>(
+        &self,
+        model_id: String,
+        inputs: Vec<BatchInput<T>>,
+        args: BatchArgs,
+    ) -> PyResult<PreparedBatch<T>> {
+        let projection = args.select.as_deref().map(Projection::new).transpose()?;
+        let (inputs, priorities) = split_priorities(inputs, args.priorities)?;
+        let retry = retry_overrides(args.max_retries, args.initial_backoff_ms, args.retry_policy)?;
+        let mut settings = self.configured_batch_settings(
+            args.config, model_id, args.features, args.output_format, args.max_rps, args.timeout_secs,
+            args.poll_interval, args.strict, retry,
+        )?;
+        apply_request_options(&mut settings, args.pages, args.locale, args.query_fields)?;
+        settings.priorities = priorities;
+        Ok(PreparedBatch {
+            sources: batch_sources(&inputs),
+            inputs,
+            settings,
+            projection,
+            checkpoint_path: args.checkpoint_path,
+            output_dir: args.output_dir,
+        })
+    }
+
+    /// Runs a batch of URLs as `process_batch_documents_from_urls` does.
+    fn run_url_batch(
+        &self,
+        py: Python,
+        model_id: String,
+        document_urls: Vec<BatchInput<String>>,
+        args: BatchArgs,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let batch = self.prepare_batch(model_id, document_urls, args)?;
+        let results = self.run_checkpointed(py, batch.inputs, batch.settings, batch.checkpoint_path, batch.output_dir, batch.projection.as_ref(), |client, inputs, settings| {
+            client.block_on(client.process_documents_async_from_urls(inputs, settings))
+        })?;
+        tag_results(py, &results, &batch.sources)?;
+        Ok(results)
+    }
+
+    /// Runs a batch of files as `process_batch_documents_from_file_paths` does.
+    fn run_file_path_batch(
+        &self,
+        py: Python,
+        model_id: String,
+        file_paths: Vec<BatchInput<PathBuf>>,
+        args: BatchArgs,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let skip_unsupported = args.skip_unsupported;
+        let mut batch = self.prepare_batch(model_id, file_paths, args)?;
+        let (submitted, priorities, skipped) =
+            split_unsupported(batch.inputs, batch.settings.priorities.take(), skip_unsupported);
+        batch.settings.priorities = priorities;
+
+        let mut results = self.run_checkpointed(py, submitted, batch.settings, batch.checkpoint_path, batch.output_dir, batch.projection.as_ref(), |client, inputs, settings| {
+            client.block_on(client.process_documents_async_from_file_paths(inputs, settings))
+        })?;
+        insert_skipped(py, &mut results, skipped)?;
+        tag_results(py, &results, &batch.sources)?;
+        Ok(results)
+    }
+
+    /// Runs a batch of in-memory documents and converts and tags its results.
+    fn run_bytes(
+        &self,
+        py: Python,
+        documents: Vec<DocumentBytes>,
+        settings: BatchSettings,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+        projection: Option<&Projection>,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let sources = batch_sources(&documents);
+        let results = self.run_checkpointed(py, documents, settings, checkpoint_path, output_dir, projection, |client, documents, settings| {
+            client.block_on(client.process_documents_async_from_bytes(documents, settings))
+        })?;
+        tag_results(py, &results, &sources)?;
+        Ok(results)
+    }
+
+    /// Runs a batch, skipping and filling in documents that `checkpoint_path`
+    /// records as already completed. Without a checkpoint this is just `run`.
+    #[allow(clippy::too_many_arguments)]
+    fn run_checkpointed<I: BatchSource + Send>(
+        &self,
+        py: Python,
+        inputs: Vec<I>,
+        mut settings: BatchSettings,
+        checkpoint_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+        projection: Option<&Projection>,
+        run: impl FnOnce(&Self, Vec<I>, BatchSettings) -> PyResult<Vec<DocumentOutcome>> + Send,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let (pending, completed) = plan_checkpoint(inputs, &mut settings, checkpoint_path, output_dir)?;
+        let rust_results = py.detach(|| run(self, pending, settings))?;
+        merge_checkpointed(py, completed, rust_results, projection)
+    }
+
+    /// Wraps `future` so it can be awaited from the asyncio event loop without
+    /// blocking it. Each poll enters the client's runtime, so timers and
+    /// connections created along the way are driven by its workers, which
+    /// also run the spawned document tasks. Fails if the client is closed.
+    fn in_runtime<F: Future>(&self, future: F) -> PyResult<InRuntime<F>> {
+        let active = self
+            .shutdown
+            .enter()
+            .ok_or_else(|| PyRuntimeError::new_err("RustyAnalysisClient is closed"))?;
+        Ok(InRuntime {
+            handle: self.handle.clone(),
+            future: Box::pin(future),
+            _active: active,
+        })
+    }
+}
+
+/// A future polled from outside the Tokio runtime (the asyncio event loop)
+/// that enters the runtime on every poll. Keeps its batch registered with
+/// `Shutdown` until dropped.
+struct InRuntime<F> {
+    handle: Handle,
+    future: Pin<Box<F>>,
+    _active: ActiveBatch,
+}
+
+impl<F: Future> Future for InRuntime<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = &mut *self;
+        let _runtime = this.handle.enter();
+        this.future.as_mut().poll(cx)
+    }
+}
+
+/// Runs blocking work (checkpoint file I/O, converting results under the GIL)
+/// on the runtime's blocking pool, so an async batch never stalls the event
+/// loop or a runtime worker with it.
+async fn run_blocking<T: Send + 'static>(work: impl FnOnce() -> PyResult<T> + Send + 'static) -> PyResult<T> {
+    AbortOnDrop(tokio::task::spawn_blocking(work))
+        .await
+        .map_err(|err| PyRuntimeError::new_err(format!("Batch task failed: {}", err)))?
+}
+
+/// Per input, the checkpoint entry of a document completed by an earlier run,
+/// or None if it is submitted in this one.
+type CheckpointedInputs = Vec<Option<(String, CheckpointEntry)>>;
+
+/// Opens `checkpoint_path` and removes the inputs it records as completed,
+/// along with their priorities. Returns the inputs left to submit and, with a
+/// checkpoint, what to fill the skipped slots with.
+fn plan_checkpoint<I: BatchSource>(
+    inputs: Vec<I>,
+    settings: &mut BatchSettings,
+    checkpoint_path: Option<PathBuf>,
+    output_dir: Option<PathBuf>,
+) -> PyResult<(Vec<I>, Option<CheckpointedInputs>)> {
+    let Some(checkpoint_path) = checkpoint_path else {
+        if output_dir.is_some() {
+            return Err(PyValueError::new_err("output_dir requires checkpoint_path"));
+        }
+        return Ok((inputs, None));
+    };
+    let checkpoint = Checkpoint::open(&checkpoint_path, output_dir, &settings.options)
+        .map_err(|e| PyIOError::new_err(e.to_string()))?;
+
+    let count = inputs.len();
+    let mut completed: CheckpointedInputs = Vec::with_capacity(count);
+    let mut pending = Vec::new();
+    let mut pending_priorities = Vec::new();
+    for (index, input) in inputs.into_iter().enumerate() {
+        match checkpoint.completed(&input.checkpoint_key()) {
+            Some(entry) => completed.push(Some((input.source().into_owned(), entry.clone()))),
+            None => {
+                completed.push(None);
+                if let Some(priorities) = &settings.priorities {
+                    pending_priorities.push(priorities[index]);
+                }
+                pending.push(input);
+            }
+        }
+    }
+    info!(
+        checkpoint = %checkpoint_path.display(),
+        skipped = count - pending.len(),
+        remaining = pending.len(),
+        "Resuming batch from checkpoint"
+    );
+    if settings.priorities.is_some() {
+        settings.priorities = Some(pending_priorities);
+    }
+    settings.checkpoint = Some(Arc::new(checkpoint));
+    Ok((pending, Some(completed)))
+}
+
+/// Converts the results of the submitted documents and, with a checkpoint,
+/// fills the skipped slots from the saved results (or `CheckpointedResult`
+/// markers when none were saved).
+fn merge_checkpointed(
+    py: Python,
+    completed: Option<CheckpointedInputs>,
+    rust_results: Vec<DocumentOutcome>,
+    projection: Option<&Projection>,
+) -> PyResult<Vec<Py<PyAny>>> {
+    let Some(completed) = completed else {
+        return convert_results(py, rust_results, projection);
+    };
+    let mut fresh = convert_results(py, rust_results, projection)?.into_iter();
+    completed
+        .into_iter()
+        .map(|entry| match entry {
+            None => Ok(fresh.next().expect("one result per pending document")),
+            Some((source, entry)) => match entry.result_path {
+                Some(path) => {
+                    let loaded = std::fs::read_to_string(long_path(Path::new(&path)))
+                        .map_err(|e| e.to_string())
+                        .and_then(|json| parse_result(&json, projection).map_err(|e| e.to_string()));
+                    match loaded {
+                        Ok(result) => Ok(Py::new(py, result)?.into_any()),
+                        Err(err) => {
+                            let msg = format!("Failed to load checkpointed result {}: {}", path, err);
+                            DocumentAnalysisError::Other(msg).into_exception(py)
+                        }
+                    }
+                }
+                None => Ok(Py::new(py, CheckpointedResult { source, status: entry.status, index: None })?.into_any()),
+            },
+        })
+        .collect()
+}
+
+/// Files left out of a batch for their type, with their input index.
+type SkippedFiles = Vec<(usize, PathBuf)>;
+
+/// Splits off the files whose extension the service does not accept when
+/// `skip_unsupported` is set. Returns the files to submit with their
+/// priorities, and the skipped files with their input index.
+fn split_unsupported(
+    file_paths: Vec<PathBuf>,
+    priorities: Option<Vec<i64>>,
+    skip_unsupported: bool,
+) -> (Vec<PathBuf>, Option<Vec<i64>>, SkippedFiles) {
+    let unsupported: Vec<bool> = file_paths
+        .iter()
+        .map(|path| skip_unsupported && get_content_type(path) == UNSUPPORTED_CONTENT_TYPE)
+        .collect();
+    let priorities = priorities.map(|priorities| {
+        priorities
+            .into_iter()
+            .zip(&unsupported)
+            .filter_map(|(priority, &skip)| (!skip).then_some(priority))
+            .collect()
+    });
+    let mut skipped = Vec::new();
+    let mut submitted = Vec::new();
+    for (index, path) in file_paths.into_iter().enumerate() {
+        if unsupported[index] {
+            skipped.push((index, path));
+        } else {
+            submitted.push(path);
+        }
+    }
+    (submitted, priorities, skipped)
+}
+
+/// Puts an `unsupported_type` error back in the slot of every skipped file.
+fn insert_skipped(py: Python, results: &mut Vec<Py<PyAny>>, skipped: SkippedFiles) -> PyResult<()> {
+    for (index, path) in skipped {
+        let message = format!("Unsupported file type: {}", path.display());
+        results.insert(index, analysis_error(py, "unsupported_type", message)?);
+    }
+    Ok(())
+}
+
+impl Drop for RustyAnalysisClient {
+    fn drop(&mut self) {
+        self.shutdown_runtime(self.shutdown_grace);
+    }
+}
+
+/// Sets the per-call `pages`, `locale` and `query_fields` options, validating
+/// `pages` and stripping its spaces.
+fn apply_request_options(
+    settings: &mut BatchSettings,
+    pages: Option<String>,
+    locale: Option<String>,
+    query_fields: Option<Vec<String>>,
+) -> PyResult<()> {
+    if let Some(pages) = pages {
+        AnalyzeOptions::validate_pages(&pages).map_err(PyValueError::new_err)?;
+        settings.options.pages = Some(pages.replace(' ', ""));
+    }
+    settings.options.locale = locale;
+    if let Some(query_fields) = query_fields {
+        settings.options.set_query_fields(query_fields).map_err(PyValueError::new_err)?;
+    }
+    Ok(())
+}
+
+/// The `source` of each batch input, in input order.
+fn batch_sources<I: BatchSource>(inputs: &[I]) -> Vec<String> {
+    inputs.iter().map(|input| input.source().into_owned()).collect()
+}
+
+/// The input index of each named document.
+fn input_positions(names: &[String]) -> HashMap<String, usize> {
+    names.iter().enumerate().map(|(index, name)| (name.clone(), index)).collect()
+}
+
+/// Keys batch results by document name, in the order of `names`. Each result
+/// is matched through the input `index` it was tagged with, not its position
+/// in `results`.
+fn results_by_name<'py>(
+    py: Python<'py>,
+    names: Vec<String>,
+    positions: &HashMap<String, usize>,
+    results: Vec<Py<PyAny>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let mut by_index = results
+        .into_iter()
+        .map(|result| Ok((result.bind(py).getattr("index")?.extract::<usize>()?, result)))
+        .collect::<PyResult<HashMap<_, _>>>()?;
+    let named = PyDict::new(py);
+    for name in names {
+        let result = by_index
+            .remove(&positions[&name])
+            .ok_or_else(|| PyRuntimeError::new_err(format!("No result for document '{}'", name)))?;
+        named.set_item(name, result)?;
+    }
+    Ok(named)
+}
+
+/// Sets `source` and `index` on every batch result, successes and failures
+/// alike, so a result can be matched to its input without relying on position.
+fn tag_results(py: Python, results: &[Py<PyAny>], sources: &[String]) -> PyResult<()> {
+    for (index, (result, source)) in results.iter().zip(sources).enumerate() {
+        let result = result.bind(py);
+        result.setattr("source", source)?;
+        result.setattr("index", index)?;
+    }
+    Ok(())
+}
+
+/// Converts raw batch results into `AnalyzeResult` objects, or
+/// `DocumentAnalysisError` instances (typed by failure) for documents that
+/// failed or could not be deserialized. Only the sections kept by `projection`
+/// are deserialized.
+fn convert_results(
+    py: Python,
+    rust_results: Vec<DocumentOutcome>,
+    projection: Option<&Projection>,
+) -> PyResult<Vec<Py<PyAny>>> {
+    let mut py_results = Vec::new();
+
+    for DocumentOutcome { result, metrics } in rust_results {
+        let converted = match result {
+            Ok(raw_result) => match parse_result(raw_result.get(), projection) {
+                Ok(analyze_result_struct) => Py::new(py, analyze_result_struct)?.into_any(),
+                Err(e) => {
+                    let failure = DocumentAnalysisError::DeserializationError(e.to_string());
+                    failure.into_exception(py)?
+                }
+            },
+            Err(failure) => failure.into_exception(py)?,
+        };
+        if metrics.elapsed.is_some() {
+            tag_metrics(converted.bind(py), &metrics)?;
+        }
+        py_results.push(converted);
+    }
+
+    Ok(py_results)
+}
+
+/// Sets `elapsed_secs`, `throttled` and `endpoint` on a batch result.
+fn tag_metrics(result: &Bound<'_, PyAny>, metrics: &DocumentMetrics) -> PyResult<()> {
+    result.setattr("elapsed_secs", metrics.elapsed.map(|elapsed| elapsed.as_secs_f64()))?;
+    result.setattr("throttled", metrics.throttled)?;
+    result.setattr("endpoint", metrics.endpoint.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, ClientOptions, MockServer, Response, client, credentials, try_client};
+
+    fn test_client(timeout_secs: Option<f64>, batch_timeout_secs: Option<f64>) -> RustyAnalysisClient {
+        let options = ClientOptions { timeout_secs, batch_timeout_secs, ..ClientOptions::default() };
+        client(vec![credentials("https://a.example.com", 1)], options)
+    }
+
+    fn settings(
+        client: &RustyAnalysisClient,
+        config: Option<BatchProcessingConfig>,
+        timeout_secs: Option<f64>,
+        poll_interval: Option<f64>,
+        retry: PyResult<RetryOverrides>,
+    ) -> PyResult<BatchSettings> {
+        client.configured_batch_settings(
+            config,
+            "prebuilt-layout".to_string(),
+            None,
+            None,
+            None,
+            timeout_secs,
+            poll_interval,
+            false,
+            retry?,
+        )
+    }
+
+    fn policy(entries: &[(&str, i64)]) -> Option<HashMap<String, i64>> {
+        Some(entries.iter().map(|(key, value)| (key.to_string(), *value)).collect())
+    }
+
+    fn error_message<T>(result: PyResult<T>) -> String {
+        Python::initialize();
+        Python::attach(|py| result.err().expect("expected an error").value(py).to_string())
+    }
+
+    #[test]
+    fn duplicate_endpoints_collapse_unless_allowed() {
+        // Same endpoint spelled two ways and with different keys, then a distinct one.
+        let credentials = || {
+            [("https://a.example.com", "key-1", 1), ("A.EXAMPLE.COM/documentintelligence/", "key-2", 2), ("https://b.example.com", "key-1", 3)]
+                .map(|(endpoint, key, weight)| {
+                    Credentials::new(endpoint.to_string(), Some(key.to_string()), false, None, None, weight).unwrap()
+                })
+                .to_vec()
+        };
+        let kept = |allow_duplicate_endpoints| {
+            let options = ClientOptions { allow_duplicate_endpoints, ..ClientOptions::default() };
+            client(credentials(), options)
+                .credentials
+                .iter()
+                .map(|creds| (creds.endpoint.clone(), creds.weight))
+                .collect::<Vec<_>>()
+        };
+
+        let a = "https://a.example.com".to_string();
+        let b = "https://b.example.com".to_string();
+        assert_eq!(kept(false), [(a.clone(), 1), (b.clone(), 3)]);
+        assert_eq!(kept(true), [(a.clone(), 1), (a, 2), (b, 3)]);
+    }
+
+    #[test]
+    fn per_call_timeouts_override_the_client_and_keep_its_batch_deadline() {
+        let client = test_client(Some(30.0), Some(120.0));
+
+        let defaults = settings(&client, None, None, None, Ok(RetryOverrides::default())).unwrap();
+        assert_eq!(defaults.timeouts.document, Some(Duration::from_secs(30)));
+        assert_eq!(defaults.polling.interval, Duration::from_millis(10));
+
+        let overridden = settings(&client, None, Some(90.0), Some(2.0), Ok(RetryOverrides::default())).unwrap();
+        assert_eq!(overridden.timeouts.document, Some(Duration::from_secs(90)));
+        assert_eq!(overridden.timeouts.batch, Some(Duration::from_secs(120)));
+        assert_eq!(overridden.polling.interval, Duration::from_secs(2));
+
+        let config = BatchProcessingConfig::new().with_timeout_per_document(60.0).unwrap();
+        let configured = settings(&client, Some(config), Some(90.0), None, Ok(RetryOverrides::default())).unwrap();
+        assert_eq!(configured.timeouts.document, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn per_call_timeout_cannot_exceed_the_batch_deadline() {
+        let client = test_client(None, Some(120.0));
+        let message = error_message(settings(&client, None, Some(121.0), None, Ok(RetryOverrides::default())));
+        assert!(message.contains("cannot exceed the batch timeout"), "{}", message);
+        assert!(settings(&client, None, Some(120.0), None, Ok(RetryOverrides::default())).is_ok());
+    }
+
+    #[test]
+    fn retry_policy_fills_in_below_arguments_and_config() {
+        let client = test_client(None, None);
+
+        let from_policy = retry_overrides(None, None, policy(&[("max_retries", 7), ("max_backoff_ms", 2_000)]));
+        let resolved = settings(&client, None, None, None, from_policy).unwrap().polling.retry;
+        assert_eq!(resolved.max_retries, 7);
+        assert_eq!(resolved.initial_backoff, HttpRetry::default().initial_backoff);
+        assert_eq!(resolved.max_backoff, Duration::from_secs(2));
+
+        let mixed = retry_overrides(Some(1), None, policy(&[("initial_backoff_ms", 100)]));
+        let resolved = settings(&client, None, None, None, mixed).unwrap().polling.retry;
+        assert_eq!((resolved.max_retries, resolved.initial_backoff), (1, Duration::from_millis(100)));
+
+        let config = BatchProcessingConfig::new().with_polling_config(None, Some(0), None).unwrap();
+        let from_config = retry_overrides(None, None, policy(&[("max_retries", 7)]));
+        let resolved = settings(&client, Some(config), None, None, from_config).unwrap().polling.retry;
+        assert_eq!(resolved.max_retries, 0);
+    }
+
+    #[test]
+    fn invalid_retry_policies_are_rejected() {
+        let client = test_client(None, None);
+        let cases = [
+            (retry_overrides(Some(1), None, policy(&[("max_retries", 2)])), "given both"),
+            (retry_overrides(None, None, policy(&[("retries", 2)])), "Unknown retry_policy key"),
+            (retry_overrides(None, None, policy(&[("max_retries", -1)])), "non-negative"),
+            (
+                retry_overrides(None, Some(5_000), policy(&[("max_backoff_ms", 1_000)])),
+                "cannot exceed max_backoff_ms",
+            ),
+        ];
+        for (retry, expected) in cases {
+            let message = error_message(settings(&client, None, None, None, retry));
+            assert!(message.contains(expected), "{}", message);
+        }
+    }
+
+    #[test]
+    fn close_cancels_documents_still_in_flight_after_the_grace_period() {
+        let server = MockServer::start(|_| Response::new(202).delay(Duration::from_secs(5)));
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+        let urls = vec!["https://x/a.pdf".to_string(), "https://x/b.pdf".to_string()];
+
+        let started = Instant::now();
+        let results = std::thread::scope(|scope| {
+            let batch = scope.spawn(|| {
+                client.block_on(client.process_documents_async_from_urls(urls, test_support::settings(2)))
+            });
+            while server.requests().len() < 2 {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            client.shutdown_runtime(Duration::from_millis(100));
+            batch.join().unwrap().unwrap()
+        });
+
+        assert!(started.elapsed() < Duration::from_secs(3));
+        for outcome in results {
+            assert!(matches!(outcome.result, Err(DocumentAnalysisError::Cancelled(_))), "{:?}", outcome.result);
+        }
+    }
+
+    #[test]
+    fn cancelling_an_async_batch_aborts_its_documents() {
+        let server = MockServer::start(|_| Response::new(202).delay(Duration::from_secs(5)));
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+        let urls = vec![BatchInput::Plain("https://x/a.pdf".to_string()), BatchInput::Plain("https://x/b.pdf".to_string())];
+
+        let mut batch = Box::pin(client.process_batch_documents_from_urls_async(
+            "prebuilt-layout".to_string(), urls, None, None, Some(2), None, None, None, None, None, None, false,
+            None, None, None, None, None, None, None,
+        ));
+        futures::executor::block_on(std::future::poll_fn(|cx| {
+            assert!(batch.as_mut().poll(cx).is_pending());
+            if server.requests().len() == 2 {
+                return Poll::Ready(());
+            }
+            std::thread::sleep(Duration::from_millis(5));
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }));
+        // Dropping the future is what cancelling the coroutine does.
+        drop(batch);
+
+        Python::initialize();
+        let submitting = || {
+            Python::attach(|py| {
+                let in_flight = client.in_flight(py).unwrap().into_bound(py);
+                in_flight.get_item("submitting").unwrap().extract::<usize>().unwrap()
+            })
+        };
+        let started = Instant::now();
+        while submitting() > 0 {
+            assert!(started.elapsed() < Duration::from_secs(2), "documents kept running after cancellation");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn named_results_keep_their_names_whatever_the_completion_order() {
+        let server = MockServer::start(|request| match request.method.as_str() {
+            "POST" => {
+                let url = request.url_source().unwrap();
+                let id = url.trim_start_matches("https://x/").trim_end_matches(".pdf").to_string();
+                Response::accepted(request, &format!("/operations/{}", id))
+            }
+            _ => {
+                let id = request.target.trim_start_matches("/operations/").split('?').next().unwrap().to_string();
+                // Earlier documents finish last.
+                let delay = Duration::from_millis(match id.as_str() { "a" => 150, "b" => 75, _ => 0 });
+                Response::succeeded(serde_json::json!({
+                    "apiVersion": API_VERSION,
+                    "modelId": "prebuilt-layout",
+                    "content": id,
+                }))
+                .delay(delay)
+            }
+        });
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+
+        Python::initialize();
+        Python::attach(|py| {
+            let documents = PyDict::new(py);
+            for (name, id) in [("march", "a"), ("april", "b"), ("may", "c")] {
+                documents.set_item(name, format!("https://x/{}.pdf", id)).unwrap();
+            }
+            let results = client
+                .process_batch_documents_from_urls_named(
+                    py, "prebuilt-layout".to_string(), documents, None, None, Some(3), None, None, None, None, None,
+                    false, None, None, None, None, None, None, None,
+                )
+                .unwrap();
+
+            let names: Vec<String> = results.keys().extract().unwrap();
+            assert_eq!(names, ["march", "april", "may"]);
+            for (name, id) in [("march", "a"), ("april", "b"), ("may", "c")] {
+                let result = results.get_item(name).unwrap().unwrap();
+                assert_eq!(result.cast::<AnalyzeResult>().unwrap().borrow().content, id);
+            }
+        });
+    }
+
+    #[test]
+    fn new_rejects_an_empty_credentials_list() {
+        let message = error_message(try_client(Vec::new(), ClientOptions::default()));
+        assert!(message.contains("at least one Credentials"), "{}", message);
+    }
+
+    #[test]
+    fn malformed_endpoints_are_rejected_before_a_client_is_built() {
+        let cases = [
+            ("ftp://a.example.com", "scheme must be http or https"),
+            ("https://", "Invalid endpoint"),
+            ("not a url", "bare resource name"),
+        ];
+        for (endpoint, expected) in cases {
+            let creds = Credentials::new(endpoint.to_string(), Some("key".to_string()), false, None, None, 1);
+            let message = error_message(creds);
+            assert!(message.contains(expected), "{}: {}", endpoint, message);
+        }
+    }
+
+    #[test]
+    fn a_single_valid_credential_builds_a_client() {
+        let client = try_client(vec![credentials("https://a.example.com/", 1)], ClientOptions::default()).unwrap();
+        assert_eq!(client.credentials.len(), 1);
+        assert_eq!(client.credentials[0].endpoint, "https://a.example.com");
+    }
+
+    #[test]
+    fn config_file_batches_resolve_relative_paths_and_pass_features() {
+        let server = MockServer::start(|request| match request.method.as_str() {
+            "POST" => Response::accepted(request, "/operations/1"),
+            _ => Response::succeeded(serde_json::json!({
+                "apiVersion": API_VERSION,
+                "modelId": "prebuilt-layout",
+                "content": "done",
+            })),
+        });
+        let client = client(vec![credentials(&server.url, 1)], ClientOptions::default());
+        let dir = std::env::temp_dir().join(format!("rusty-di-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.pdf"), b"%PDF-1.7").unwrap();
+        let config_path = dir.join("batch.json");
+        let config = r#"{"model_id": "prebuilt-layout", "file_paths": ["a.pdf"], "features": ["barcodes"]}"#;
+        std::fs::write(&config_path, config).unwrap();
+
+        Python::initialize();
+        let results = Python::attach(|py| {
+            let results = client.process_batch_from_config_file(py, config_path).unwrap();
+            results.into_iter().map(|result| result.bind(py).cast::<AnalyzeResult>().unwrap().borrow().content.clone()).collect::<Vec<_>>()
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
 
-        Ok(py_results)
+        assert_eq!(results, ["done"]);
+        let submit = &server.requests()[0];
+        assert!(submit.target.contains("features=barcodes"), "{}", submit.target);
     }
 }
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use crate::clients::checkpoint::Checkpoint;
+use crate::models::{FileReadRetry, PollingConfig, Timeouts};
+
+/// Model and query options shared by every document in a batch.
+#[derive(Clone, Debug)]
+pub struct AnalyzeOptions {
+    pub model_id: String,
+    pub features: Option<Vec<String>>,
+    /// Sent as `outputContentFormat`; `None` leaves the parameter out.
+    pub output_format: Option<String>,
+    /// Page range sent as the `pages` query parameter, e.g. `"1-100"`.
+    pub pages: Option<String>,
+    /// OCR language hint sent as the `locale` query parameter, e.g. `"de"`.
+    pub locale: Option<String>,
+    /// Field names sent as the `queryFields` query parameter; requires the
+    /// `queryFields` feature.
+    pub query_fields: Option<Vec<String>>,
+}
+
+/// Everything a batch run needs besides its inputs.
+#[derive(Clone)]
+pub struct BatchSettings {
+    pub options: AnalyzeOptions,
+    /// Maximum number of documents in flight at once.
+    pub semaphore_size: usize,
+    pub polling: PollingConfig,
+    pub timeouts: Timeouts,
+    pub file_read: FileReadRetry,
+    /// Submission priority per input (higher first), aligned with the inputs.
+    pub priorities: Option<Vec<i64>>,
+    /// Log each finished document's outcome here, if set.
+    pub checkpoint: Option<Arc<Checkpoint>>,
+}
+
+impl AnalyzeOptions {
+    /// Sets `query_fields`, adding the `queryFields` feature they need if it is
+    /// not already requested. Field names must be non-empty and free of commas.
+    pub fn set_query_fields(&mut self, query_fields: Vec<String>) -> Result<(), String> {
+        if let Some(name) = query_fields
+            .iter()
+            .find(|name| name.trim().is_empty() || name.contains(','))
+        {
+            return Err(format!(
+                "Invalid query field name '{}': must be non-empty and contain no commas",
+                name
+            ));
+        }
+        if query_fields.is_empty() {
+            return Ok(());
+        }
+        let features = self.features.get_or_insert_with(Vec::new);
+        if !features.iter().any(|feature| feature == "queryFields") {
+            features.push("queryFields".to_string());
+        }
+        self.query_fields = Some(query_fields.into_iter().map(|name| name.trim().to_string()).collect());
+        Ok(())
+    }
+
+    /// Checks a `pages` value such as `"1-3,5"`: comma-separated 1-based page
+    /// numbers or `first-last` ranges with `first <= last`.
+    pub fn validate_pages(pages: &str) -> Result<(), String> {
+        let invalid = || {
+            format!(
+                "Invalid pages '{}': expected comma-separated page numbers or ranges, e.g. '1-3,5'",
+                pages
+            )
+        };
+        let page_number = |text: &str| -> Result<u32, String> {
+            if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            match text.parse::<u32>() {
+                Ok(0) | Err(_) => Err(invalid()),
+                Ok(page) => Ok(page),
+            }
+        };
+        for item in pages.split(',') {
+            match item.trim().split_once('-') {
+                Some((first, last)) => {
+                    if page_number(first.trim())? > page_number(last.trim())? {
+                        return Err(invalid());
+                    }
+                }
+                None => {
+                    page_number(item.trim())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
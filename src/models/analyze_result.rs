@@ -1,7 +1,19 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
 use crate::impl_to_dict;
+use crate::models::projection::unselected_sections;
+use crate::utils::geometry::{polygon_bounds, polygon_centroid};
+use crate::utils::text::{OffsetMap, is_punctuation, is_stop_word, remove_spans, span_text};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString};
 use pythonize::pythonize;
 use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Written in place of each word dropped by `remove_low_confidence_words`.
+const LOW_CONFIDENCE_MARKER: &str = "[LOW_CONF]";
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[pyclass]
@@ -12,8 +24,11 @@ pub struct AnalyzeResult {
     #[pyo3(get, set)]
     pub model_id: String,
     #[pyo3(get, set)]
+    #[serde(default)]
     pub content: String,
-    #[pyo3(get, set)]
+    /// Read through the `pages` getter, which attaches each page's table bounds.
+    #[pyo3(set)]
+    #[serde(default)]
     pub pages: Vec<DocumentPage>,
     #[pyo3(get, set)]
     pub paragraphs: Option<Vec<DocumentParagraph>>,
@@ -21,9 +36,1075 @@ pub struct AnalyzeResult {
     pub tables: Option<Vec<DocumentTable>>,
     #[pyo3(get, set)]
     pub languages: Option<Vec<DocumentLanguage>>,
-    // Add styles, documents, etc. if needed
+    #[pyo3(get, set)]
+    pub documents: Option<Vec<AnalyzedDocument>>,
+    #[pyo3(get, set)]
+    pub key_value_pairs: Option<Vec<DocumentKeyValuePair>>,
+    #[pyo3(get, set)]
+    pub styles: Option<Vec<DocumentStyle>>,
+    #[pyo3(get, set)]
+    pub figures: Option<Vec<DocumentFigure>>,
+    #[pyo3(get, set)]
+    pub sections: Option<Vec<DocumentSection>>,
+    /// Set by `process_batch_documents_from_urls_with_entities`: named entities
+    /// found in `content` by Azure AI Language.
+    #[pyo3(get, set)]
+    #[serde(skip_deserializing)]
+    pub entities: Option<Vec<DocumentEntity>>,
+    /// Set by `analyze_document_paged`: one entry per analyze request the result
+    /// was merged from.
+    #[pyo3(get, set)]
+    #[serde(skip_deserializing)]
+    pub page_ranges: Option<Vec<PageRangeAnalysis>>,
+    /// Set by the batch methods: the input this result was produced from and
+    /// its position in the input list.
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub source: Option<String>,
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub index: Option<usize>,
+    /// Set by the batch methods: seconds from the document leaving the queue to
+    /// its result, 429 responses it was retried on, and the endpoint of the
+    /// credential that analyzed it.
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub elapsed_secs: Option<f64>,
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub throttled: Option<u32>,
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub endpoint: Option<String>,
+    /// Set when the result was parsed with `select`: the sections kept. The
+    /// others are omitted from `to_dict` rather than reported as empty.
+    #[serde(skip)]
+    pub(crate) selected: Option<Vec<&'static str>>,
+}
+
+#[pymethods]
+impl AnalyzeResult {
+    /// Convert the result to a dict. Sections left out by `select` are omitted.
+    pub fn to_dict(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let dict = pythonize(py, self)?;
+        if let Some(selected) = &self.selected {
+            let dict = dict.cast::<PyDict>()?;
+            for section in unselected_sections(selected) {
+                dict.del_item(section)?;
+            }
+        }
+        Ok(dict.unbind())
+    }
+
+    /// Return the text of a single page.
+    ///
+    /// Args:
+    ///     page_number (int): 1-based page number
+    ///
+    /// Returns:
+    ///     str: The content covered by the page's spans
+    ///
+    /// Raises:
+    ///     ValueError: If the result has no page with that number
+    pub fn content_by_page(&self, page_number: i32) -> PyResult<String> {
+        let page = self
+            .pages
+            .iter()
+            .find(|page| page.page_number == page_number)
+            .ok_or_else(|| PyValueError::new_err(format!("Page {} not found", page_number)))?;
+        let chars: Vec<char> = self.content.chars().collect();
+        Ok(page_text(&chars, page))
+    }
+
+    /// Return a normalized copy of the content for NLP preprocessing.
+    ///
+    /// Args:
+    ///     lowercase (bool): Lowercase every character
+    ///     remove_punctuation (bool): Drop Unicode punctuation characters
+    ///
+    /// Returns:
+    ///     str: The normalized content
+    #[pyo3(signature = (lowercase=true, remove_punctuation=true))]
+    pub fn normalize_content(&self, lowercase: bool, remove_punctuation: bool) -> String {
+        let mut normalized = String::with_capacity(self.content.len());
+        for c in self.content.chars() {
+            if remove_punctuation && is_punctuation(c) {
+                continue;
+            }
+            if lowercase {
+                normalized.extend(c.to_lowercase());
+            } else {
+                normalized.push(c);
+            }
+        }
+        normalized
+    }
+
+    /// Find the words near a given word on the same page.
+    ///
+    /// Distance is measured between polygon centroids. The page is located by
+    /// matching the word's span against each page's spans.
+    ///
+    /// Args:
+    ///     word (DocumentWord): The reference word, taken from this result
+    ///     max_distance (float): Maximum centroid distance, in the page's unit
+    ///
+    /// Returns:
+    ///     list[DocumentWord]: Words within `max_distance`, excluding `word` itself
+    pub fn find_adjacent_words(&self, word: &DocumentWord, max_distance: f32) -> Vec<DocumentWord> {
+        let Some((x, y)) = word.polygon.as_deref().and_then(polygon_centroid) else {
+            return Vec::new();
+        };
+        let Some(page) = self.pages.iter().find(|page| page.contains_offset(word.span.offset)) else {
+            return Vec::new();
+        };
+
+        page.words
+            .iter()
+            .flatten()
+            .filter(|candidate| candidate.span != word.span)
+            .filter(|candidate| {
+                candidate
+                    .polygon
+                    .as_deref()
+                    .and_then(polygon_centroid)
+                    .is_some_and(|(cx, cy)| (cx - x).hypot(cy - y) <= max_distance)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Return the paragraphs re-ordered top-to-bottom, left-to-right.
+    ///
+    /// Paragraphs are sorted by page number, then by the y and x coordinates of
+    /// their first bounding region's centroid. Paragraphs without a bounding
+    /// region keep their relative order at the end.
+    ///
+    /// Returns:
+    ///     str: Paragraph contents joined by newlines
+    pub fn get_reading_order_content(&self) -> String {
+        let mut positioned: Vec<((i32, f32, f32), &DocumentParagraph)> = Vec::new();
+        let mut unpositioned = Vec::new();
+        for paragraph in self.paragraphs.iter().flatten() {
+            let position = paragraph
+                .bounding_regions
+                .as_deref()
+                .and_then(|regions| regions.first())
+                .and_then(|region| {
+                    polygon_centroid(&region.polygon).map(|(x, y)| (region.page_number, y, x))
+                });
+            match position {
+                Some(position) => positioned.push((position, paragraph)),
+                None => unpositioned.push(paragraph),
+            }
+        }
+
+        positioned.sort_by(|(a, _), (b, _)| {
+            a.0.cmp(&b.0)
+                .then(a.1.total_cmp(&b.1))
+                .then(a.2.total_cmp(&b.2))
+        });
+
+        positioned
+            .into_iter()
+            .map(|(_, paragraph)| paragraph)
+            .chain(unpositioned)
+            .map(|paragraph| paragraph.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Names of the fields extracted into the first analyzed document.
+    ///
+    /// Only structured models (e.g. `prebuilt-invoice` or custom models) populate
+    /// `documents`.
+    ///
+    /// Returns:
+    ///     list[str]: Sorted field names, or an empty list if there are no documents
+    pub fn field_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .documents
+            .iter()
+            .flatten()
+            .next()
+            .map(|document| document.fields.keys().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    /// All fields extracted into the first analyzed document.
+    ///
+    /// Returns:
+    ///     dict[str, DocumentField]: Field name to field
+    ///
+    /// Raises:
+    ///     ValueError: If the result contains no documents
+    pub fn fields(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let document = self
+            .documents
+            .iter()
+            .flatten()
+            .next()
+            .ok_or_else(|| PyValueError::new_err("Result contains no documents"))?;
+        let dict = PyDict::new(py);
+        for (name, field) in &document.fields {
+            dict.set_item(name, field.clone())?;
+        }
+        Ok(dict.into_any().unbind())
+    }
+
+    /// Return a dict mapping every page number to that page's text.
+    ///
+    /// Equivalent to `{p.page_number: result.content_by_page(p.page_number) for p in result.pages}`
+    /// in a single call.
+    ///
+    /// Returns:
+    ///     dict[int, str]: Page number to page content
+    pub fn get_page_content_map(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let chars: Vec<char> = self.content.chars().collect();
+        let dict = PyDict::new(py);
+        for page in &self.pages {
+            dict.set_item(page.page_number, page_text(&chars, page))?;
+        }
+        Ok(dict.into_any().unbind())
+    }
+
+    /// Rebuild the content without words whose confidence falls below a threshold.
+    ///
+    /// Words are located through their spans, so the surrounding text (line breaks,
+    /// punctuation, markdown) is kept intact and adjacent words are never merged.
+    ///
+    /// Args:
+    ///     threshold (float): Minimum confidence a word needs to be kept
+    ///     replacement (str): Marker inserted in place of each dropped word
+    ///         (e.g. "\ufffd"). Defaults to "", which removes the word entirely.
+    ///
+    /// Returns:
+    ///     ConfidenceFilteredContent: The filtered overall and per-page content,
+    ///         along with the number of words dropped on each page
+    #[pyo3(signature = (threshold, replacement=""))]
+    pub fn content_above_confidence(
+        &self,
+        threshold: f32,
+        replacement: &str,
+    ) -> ConfidenceFilteredContent {
+        let chars: Vec<char> = self.content.chars().collect();
+        let mut all_dropped = Vec::new();
+        let mut pages = HashMap::new();
+        let mut dropped_words = HashMap::new();
+
+        for page in &self.pages {
+            let mut dropped: Vec<(usize, usize)> = page
+                .words
+                .iter()
+                .flatten()
+                .filter(|word| word.confidence < threshold)
+                .map(|word| (word.span.offset, word.span.length))
+                .collect();
+            dropped.sort_unstable();
+
+            let page_content = page
+                .spans
+                .iter()
+                .map(|span| {
+                    remove_spans(&chars, span.offset, span.offset + span.length, &dropped, replacement)
+                })
+                .collect::<Vec<_>>()
+                .join("");
+
+            dropped_words.insert(page.page_number, dropped.len());
+            pages.insert(page.page_number, page_content);
+            all_dropped.extend(dropped);
+        }
+        all_dropped.sort_unstable();
+
+        ConfidenceFilteredContent {
+            content: remove_spans(&chars, 0, chars.len(), &all_dropped, replacement),
+            pages,
+            dropped_words,
+        }
+    }
+
+    /// Copy of the result with every low-confidence word replaced by `"[LOW_CONF]"`.
+    ///
+    /// The marker replaces the word both in `content` and in the word itself.
+    /// Since the marker's length differs from the word's, every span in the copy
+    /// (pages, lines, words, paragraphs, tables and cells, languages and
+    /// key-value pairs) is shifted to point into the rewritten content, and the
+    /// `content` of each line, paragraph, cell or key-value element containing a
+    /// replaced word is rebuilt from it.
+    ///
+    /// Args:
+    ///     threshold (float): Words with a confidence below this are replaced
+    ///
+    /// Returns:
+    ///     AnalyzeResult: The rewritten copy; this result is left unchanged
+    pub fn remove_low_confidence_words(&self, threshold: f32) -> AnalyzeResult {
+        let mut edits: Vec<(usize, usize)> = self
+            .pages
+            .iter()
+            .flat_map(|page| page.words.iter().flatten())
+            .filter(|word| word.confidence < threshold)
+            .map(|word| (word.span.offset, word.span.length))
+            .collect();
+        edits.sort_unstable();
+        edits.dedup();
+
+        let mut result = self.clone();
+        if edits.is_empty() {
+            return result;
+        }
+        let chars: Vec<char> = self.content.chars().collect();
+        result.content = remove_spans(&chars, 0, chars.len(), &edits, LOW_CONFIDENCE_MARKER);
+        let chars: Vec<char> = result.content.chars().collect();
+        let map = OffsetMap::new(&edits, LOW_CONFIDENCE_MARKER.chars().count());
+
+        for page in &mut result.pages {
+            remap_spans(&mut page.spans, &map);
+            for line in page.lines.iter_mut().flatten() {
+                remap_element(&mut line.content, &mut line.spans, &map, &chars);
+            }
+            for word in page.words.iter_mut().flatten() {
+                if word.confidence < threshold {
+                    word.content = LOW_CONFIDENCE_MARKER.to_string();
+                }
+                remap_spans(std::slice::from_mut(&mut word.span), &map);
+            }
+            for mark in page.selection_marks.iter_mut().flatten() {
+                remap_spans(std::slice::from_mut(&mut mark.span), &map);
+            }
+            for barcode in page.barcodes.iter_mut().flatten() {
+                remap_spans(std::slice::from_mut(&mut barcode.span), &map);
+            }
+            for formula in page.formulas.iter_mut().flatten() {
+                remap_spans(std::slice::from_mut(&mut formula.span), &map);
+            }
+        }
+        for paragraph in result.paragraphs.iter_mut().flatten() {
+            remap_element(&mut paragraph.content, &mut paragraph.spans, &map, &chars);
+        }
+        for table in result.tables.iter_mut().flatten() {
+            remap_spans(&mut table.spans, &map);
+            for cell in &mut table.cells {
+                remap_element(&mut cell.content, &mut cell.spans, &map, &chars);
+            }
+            if let Some(caption) = &mut table.caption {
+                remap_element(&mut caption.content, &mut caption.spans, &map, &chars);
+            }
+        }
+        for language in result.languages.iter_mut().flatten() {
+            remap_spans(&mut language.spans, &map);
+        }
+        for document in result.documents.iter_mut().flatten() {
+            remap_spans(&mut document.spans, &map);
+            for field in document.fields.values_mut() {
+                remap_field(field, &map, &chars);
+            }
+        }
+        for style in result.styles.iter_mut().flatten() {
+            remap_spans(&mut style.spans, &map);
+        }
+        for figure in result.figures.iter_mut().flatten() {
+            remap_spans(&mut figure.spans, &map);
+            if let Some(caption) = &mut figure.caption {
+                remap_element(&mut caption.content, &mut caption.spans, &map, &chars);
+            }
+        }
+        for section in result.sections.iter_mut().flatten() {
+            remap_spans(&mut section.spans, &map);
+        }
+        for entity in result.entities.iter_mut().flatten() {
+            (entity.offset, entity.length) = map.map_span(entity.offset, entity.length);
+        }
+        for pair in result.key_value_pairs.iter_mut().flatten() {
+            for element in std::iter::once(&mut pair.key).chain(pair.value.as_mut()) {
+                remap_element(&mut element.content, &mut element.spans, &map, &chars);
+            }
+        }
+        result
+    }
+
+    /// The text split into sentences with the Unicode sentence boundary rules
+    /// (UAX #29), which keep abbreviations and decimal numbers intact better
+    /// than splitting on `". "`.
+    ///
+    /// Paragraphs are split one at a time when the result has them, so
+    /// headings and list items never run into the next sentence. Otherwise
+    /// `content` is used as a whole. Line breaks inside the text are treated as
+    /// spaces, since they come from the page layout rather than the prose.
+    ///
+    /// Returns:
+    ///     list[str]: Sentences in reading order, trimmed, without empty ones
+    pub fn get_sentences(&self) -> Vec<String> {
+        let texts: Vec<&str> = match &self.paragraphs {
+            Some(paragraphs) if !paragraphs.is_empty() => {
+                paragraphs.iter().map(|paragraph| paragraph.content.as_str()).collect()
+            }
+            _ => vec![self.content.as_str()],
+        };
+        texts
+            .into_iter()
+            .flat_map(|text| {
+                let text = text.replace(['\r', '\n'], " ");
+                text.unicode_sentences()
+                    .map(str::trim)
+                    .filter(|sentence| !sentence.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Extractive summary built from the highest-ranked sentences.
+    ///
+    /// Paragraph text is split into sentences on `". "`. Each sentence is scored by
+    /// its paragraph's mean word confidence plus a bonus for sitting close below a
+    /// title or section heading; earlier sentences win ties. Headings, page
+    /// headers, footers and page numbers are never selected.
+    ///
+    /// Args:
+    ///     max_sentences (int): Maximum number of sentences to return
+    ///
+    /// Returns:
+    ///     str: The selected sentences in document order, separated by spaces
+    pub fn summary(&self, max_sentences: usize) -> String {
+        let mut words: Vec<(usize, f32)> = self
+            .pages
+            .iter()
+            .flat_map(|page| page.words.iter().flatten())
+            .map(|word| (word.span.offset, word.confidence))
+            .collect();
+        words.sort_unstable_by_key(|(offset, _)| *offset);
+
+        // (score, position, sentence); position is the sentence's index in document order.
+        let mut candidates: Vec<(f32, usize, String)> = Vec::new();
+        let mut since_heading: Option<usize> = None;
+        for paragraph in self.paragraphs.iter().flatten() {
+            match paragraph.role.as_deref() {
+                Some("title" | "sectionHeading") => {
+                    since_heading = Some(0);
+                    continue;
+                }
+                Some("pageHeader" | "pageFooter" | "pageNumber") => continue,
+                _ => {}
+            }
+
+            let confidence = mean_confidence(&words, &paragraph.spans);
+            let heading_bonus = since_heading.map_or(0.0, |distance| 1.0 / (1 + distance) as f32);
+            since_heading = since_heading.map(|distance| distance + 1);
+
+            for sentence in paragraph.content.split(". ") {
+                let sentence = sentence.trim();
+                if sentence.is_empty() {
+                    continue;
+                }
+                let sentence = if sentence.ends_with(['.', '!', '?']) {
+                    sentence.to_string()
+                } else {
+                    format!("{}.", sentence)
+                };
+                candidates.push((confidence + heading_bonus, candidates.len(), sentence));
+            }
+        }
+
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        candidates.truncate(max_sentences);
+        candidates.sort_by_key(|(_, position, _)| *position);
+        candidates
+            .into_iter()
+            .map(|(_, _, sentence)| sentence)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Word frequencies for a word cloud.
+    ///
+    /// `content` is lowercased and split on anything that is not a letter, digit
+    /// or apostrophe. Tokens without a letter and common English stop words are
+    /// dropped.
+    ///
+    /// Args:
+    ///     top_n (int): Maximum number of words to return
+    ///     min_word_length (int): Shortest word kept, in characters
+    ///
+    /// Returns:
+    ///     list[tuple[str, int]]: `(word, frequency)` pairs, most frequent first,
+    ///         ties broken alphabetically
+    #[pyo3(signature = (top_n=50, min_word_length=3))]
+    pub fn word_cloud_data(&self, top_n: usize, min_word_length: usize) -> Vec<(String, usize)> {
+        let lowercase = self.content.to_lowercase();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for token in lowercase.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '\u{2019}') {
+            let word = token.trim_matches(|c| c == '\'' || c == '\u{2019}');
+            if word.chars().count() < min_word_length
+                || !word.chars().any(char::is_alphabetic)
+                || is_stop_word(word)
+            {
+                continue;
+            }
+            *counts.entry(word).or_default() += 1;
+        }
+
+        let mut frequencies: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(word, count)| (word.to_string(), count))
+            .collect();
+        frequencies.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        frequencies.truncate(top_n);
+        frequencies
+    }
+
+    /// Key-value pairs whose key or value lies on the given page.
+    ///
+    /// Args:
+    ///     page_number (int): 1-based page number
+    ///
+    /// Returns:
+    ///     list[DocumentKeyValuePair]: Matching pairs in document order, or an empty
+    ///         list if none fall on the page (or the result has no pairs)
+    pub fn get_key_value_pairs_on_page(&self, page_number: i32) -> Vec<DocumentKeyValuePair> {
+        self.key_value_pairs
+            .iter()
+            .flatten()
+            .filter(|pair| {
+                std::iter::once(&pair.key)
+                    .chain(pair.value.as_ref())
+                    .flat_map(|element| element.bounding_regions.iter().flatten())
+                    .any(|region| region.page_number == page_number)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// The analyzed pages. Each carries the bounds of the result's tables on it,
+    /// so `DocumentPage.page_type()` can measure table coverage.
+    ///
+    /// Returns:
+    ///     list[DocumentPage]: Pages in order
+    #[getter]
+    pub fn pages(&self) -> Vec<DocumentPage> {
+        self.pages
+            .iter()
+            .map(|page| DocumentPage { table_bounds: self.table_bounds(page.page_number), ..page.clone() })
+            .collect()
+    }
+
+    /// Classify every page, as `DocumentPage.page_type()` does for one.
+    ///
+    /// Returns:
+    ///     dict[int, str]: Page number to `"table"`, `"image"`, `"text"` or `"mixed"`
+    pub fn page_types(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let dict = PyDict::new(py);
+        for page in &self.pages {
+            dict.set_item(page.page_number, page.classify(&self.table_bounds(page.page_number)))?;
+        }
+        Ok(dict.into_any().unbind())
+    }
+
+    /// Diff the tables of two structurally similar results cell by cell.
+    ///
+    /// Tables are paired by index and cells by `(row_index, column_index)`. A cell
+    /// or table present in only one result is reported with None on the other
+    /// side. Cell contents are compared exactly.
+    ///
+    /// Args:
+    ///     other (AnalyzeResult): The newer result
+    ///
+    /// Returns:
+    ///     list[dict]: One `{"table_index", "row", "col", "old_value", "new_value"}`
+    ///         dict per differing cell, ordered by table, row and column
+    pub fn compare_tables(&self, other: &AnalyzeResult, py: Python) -> PyResult<Py<PyAny>> {
+        let old_tables = self.tables.as_deref().unwrap_or_default();
+        let new_tables = other.tables.as_deref().unwrap_or_default();
+        let changes = PyList::empty(py);
+        for table_index in 0..old_tables.len().max(new_tables.len()) {
+            let old_cells = table_cells(old_tables.get(table_index));
+            let new_cells = table_cells(new_tables.get(table_index));
+            let positions: BTreeSet<&(i32, i32)> = old_cells.keys().chain(new_cells.keys()).collect();
+            for position in positions {
+                let old_value = old_cells.get(position).copied();
+                let new_value = new_cells.get(position).copied();
+                if old_value == new_value {
+                    continue;
+                }
+                let change = PyDict::new(py);
+                change.set_item("table_index", table_index)?;
+                change.set_item("row", position.0)?;
+                change.set_item("col", position.1)?;
+                change.set_item("old_value", old_value)?;
+                change.set_item("new_value", new_value)?;
+                changes.append(change)?;
+            }
+        }
+        Ok(changes.into_any().unbind())
+    }
+
+    /// A short overview of every table, without cell contents.
+    ///
+    /// Returns:
+    ///     list[dict]: One dict per table, in document order, with `table_index`,
+    ///         `page_numbers` (sorted, from the bounding regions), `row_count`,
+    ///         `column_count`, `has_merged_cells` (a cell spans several rows or
+    ///         columns), `has_header_row` (a `columnHeader` cell exists) and
+    ///         `caption` (None when the table has none)
+    pub fn tables_summary(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let summaries = PyList::empty(py);
+        for (table_index, table) in self.tables.iter().flatten().enumerate() {
+            let summary = PyDict::new(py);
+            summary.set_item("table_index", table_index)?;
+            summary.set_item("page_numbers", region_pages(table.bounding_regions.iter().flatten()))?;
+            summary.set_item("row_count", table.row_count)?;
+            summary.set_item("column_count", table.column_count)?;
+            summary.set_item(
+                "has_merged_cells",
+                table
+                    .cells
+                    .iter()
+                    .any(|cell| cell.row_span.unwrap_or(1) > 1 || cell.column_span.unwrap_or(1) > 1),
+            )?;
+            summary.set_item("has_header_row", table.detect_header_row().is_some())?;
+            summary.set_item("caption", table.caption.as_ref().map(|caption| caption.content.as_str()))?;
+            summaries.append(summary)?;
+        }
+        Ok(summaries.into_any().unbind())
+    }
+
+    /// The document's main language.
+    ///
+    /// Returns:
+    ///     str: Locale of the language covering the most characters, e.g. 'en',
+    ///         or 'unknown' if the result has no language information (analyze
+    ///         with the `languages` feature)
+    pub fn detect_language(&self) -> String {
+        self.language_coverage()
+            .first()
+            .map_or_else(|| "unknown".to_string(), |(locale, _)| locale.to_string())
+    }
+
+    /// Whether the document is written in more than one language.
+    ///
+    /// Args:
+    ///     threshold (float): Minimum fraction of the content (0 to 1) a language
+    ///         must cover to count
+    ///
+    /// Returns:
+    ///     bool: True if at least two locales each cover more than `threshold`
+    ///         of the content
+    #[pyo3(signature = (threshold=0.1))]
+    pub fn is_multilingual(&self, threshold: f64) -> bool {
+        self.language_coverage()
+            .iter()
+            .filter(|(_, fraction)| *fraction > threshold)
+            .count()
+            > 1
+    }
+
+    /// Spans of the content the service marked as handwritten (analyze with
+    /// the `styleFont` feature, or a model that reports styles).
+    ///
+    /// Returns:
+    ///     list[DocumentSpan]: Spans of every style with `is_handwritten` set,
+    ///         sorted by offset
+    pub fn handwritten_spans(&self) -> Vec<DocumentSpan> {
+        let mut spans: Vec<DocumentSpan> = self
+            .styles
+            .iter()
+            .flatten()
+            .filter(|style| style.is_handwritten == Some(true))
+            .flat_map(|style| style.spans.iter().cloned())
+            .collect();
+        spans.sort_by_key(|span| span.offset);
+        spans
+    }
+
+    /// Named entities found in the content, when the result comes from
+    /// `process_batch_documents_from_urls_with_entities`.
+    ///
+    /// Returns:
+    ///     list[dict]: One dict per entity, by offset, with `text`, `category`,
+    ///         `subcategory` (None when the category has none), `offset` and
+    ///         `length` (characters into `content`) and `confidence_score`.
+    ///         Empty if entities were not requested.
+    pub fn get_entities(&self, py: Python) -> PyResult<Py<PyAny>> {
+        Ok(pythonize(py, self.entities.as_deref().unwrap_or_default())?.unbind())
+    }
+
+    /// Page numbers containing at least one table.
+    ///
+    /// Returns:
+    ///     list[int]: Sorted, deduplicated page numbers taken from the tables'
+    ///         bounding regions
+    pub fn pages_with_tables(&self) -> Vec<i32> {
+        region_pages(
+            self.tables
+                .iter()
+                .flatten()
+                .flat_map(|table| table.bounding_regions.iter().flatten()),
+        )
+    }
+
+    /// Page numbers containing at least one formula (requires the `formulas`
+    /// feature).
+    ///
+    /// Returns:
+    ///     list[int]: Sorted, deduplicated numbers of the pages with formulas
+    pub fn pages_with_formulas(&self) -> Vec<i32> {
+        self.pages
+            .iter()
+            .filter(|page| page.formulas.as_ref().is_some_and(|formulas| !formulas.is_empty()))
+            .map(|page| page.page_number)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// The document title, as detected by the layout model.
+    ///
+    /// Returns:
+    ///     str | None: Content of the first paragraph with role `title`, or None
+    ///         if there is none
+    pub fn get_title(&self) -> Option<String> {
+        self.paragraphs_with_role("title")
+            .next()
+            .map(|paragraph| paragraph.content.clone())
+    }
+
+    /// Section headings in reading order.
+    ///
+    /// Returns:
+    ///     list[str]: Content of every paragraph with role `sectionHeading`
+    pub fn get_section_headings(&self) -> Vec<String> {
+        self.paragraphs_with_role("sectionHeading")
+            .map(|paragraph| paragraph.content.clone())
+            .collect()
+    }
+
+    /// Footnotes in reading order.
+    ///
+    /// Returns:
+    ///     list[DocumentParagraph]: Every paragraph with role `footnote`
+    pub fn get_footnotes(&self) -> Vec<DocumentParagraph> {
+        self.paragraphs_with_role("footnote").cloned().collect()
+    }
+
+    /// Page numbers as printed on the pages.
+    ///
+    /// Returns:
+    ///     list[str]: Content of every paragraph with role `pageNumber`
+    pub fn get_page_numbers(&self) -> Vec<String> {
+        self.paragraphs_with_role("pageNumber")
+            .map(|paragraph| paragraph.content.clone())
+            .collect()
+    }
+
+    /// Running headers at the top of pages.
+    ///
+    /// Returns:
+    ///     list[DocumentParagraph]: Every paragraph with role `pageHeader`
+    pub fn get_page_headers(&self) -> Vec<DocumentParagraph> {
+        self.paragraphs_with_role("pageHeader").cloned().collect()
+    }
+
+    /// Running footers at the bottom of pages.
+    ///
+    /// Returns:
+    ///     list[DocumentParagraph]: Every paragraph with role `pageFooter`
+    pub fn get_page_footers(&self) -> Vec<DocumentParagraph> {
+        self.paragraphs_with_role("pageFooter").cloned().collect()
+    }
+
+    /// The title and section headings with the page each starts on.
+    ///
+    /// Headings without a bounding region are skipped.
+    ///
+    /// Returns:
+    ///     list[tuple[str, int]]: `(content, page_number)` pairs sorted by page,
+    ///         in reading order within a page
+    pub fn get_table_of_contents(&self) -> Vec<(String, i32)> {
+        let mut entries: Vec<(String, i32)> = self
+            .paragraphs
+            .iter()
+            .flatten()
+            .filter(|paragraph| matches!(paragraph.role.as_deref(), Some("title" | "sectionHeading")))
+            .filter_map(|paragraph| {
+                let page = paragraph
+                    .bounding_regions
+                    .iter()
+                    .flatten()
+                    .map(|region| region.page_number)
+                    .min()?;
+                Some((paragraph.content.clone(), page))
+            })
+            .collect();
+        entries.sort_by_key(|(_, page)| *page);
+        entries
+    }
+
+    /// `DocumentPage.get_text_blocks` for every page.
+    ///
+    /// Args:
+    ///     line_gap_threshold (float): Largest vertical gap between lines, in
+    ///         each page's unit, that keeps them in the same block
+    ///
+    /// Returns:
+    ///     list[tuple[int, str]]: `(page_number, block_text)` pairs in page
+    ///         order, blocks within a page top to bottom
+    pub fn get_all_text_blocks(&self, line_gap_threshold: f32) -> Vec<(i32, String)> {
+        self.pages
+            .iter()
+            .flat_map(|page| {
+                page.get_text_blocks(line_gap_threshold)
+                    .into_iter()
+                    .map(|block| (page.page_number, block))
+            })
+            .collect()
+    }
+
+    /// The document as a nested hierarchy of sections, paragraphs, words,
+    /// tables, rows and cells.
+    ///
+    /// A new section starts at every paragraph with role `sectionHeading`;
+    /// content before the first heading forms a leading section whose `heading`
+    /// is None. Tables are placed in the section they appear in by reading
+    /// order, and paragraphs inside tables appear only as table cells.
+    ///
+    /// Returns:
+    ///     dict: `title` (str | None) and `sections`, a list of dicts with
+    ///         `heading`, `paragraphs` (dicts with `role`, `content` and `words`,
+    ///         a list of str) and `tables` (dicts with `row_count`,
+    ///         `column_count` and `rows`, each a list of cell dicts with
+    ///         `column_index`, `kind` and `content`)
+    pub fn get_structure_tree(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let mut words: Vec<(usize, &str)> = self
+            .pages
+            .iter()
+            .flat_map(|page| page.words.iter().flatten())
+            .map(|word| (word.span.offset, word.content.as_str()))
+            .collect();
+        words.sort_by_key(|(offset, _)| *offset);
+
+        let tables: Vec<&DocumentTable> = self.tables.iter().flatten().collect();
+        let in_table = |offset: usize| {
+            tables.iter().flat_map(|table| &table.spans).any(|span| {
+                span.offset <= offset && offset < span.offset + span.length
+            })
+        };
+
+        enum Node<'a> {
+            Paragraph(&'a DocumentParagraph),
+            Table(&'a DocumentTable),
+        }
+        let mut nodes: Vec<(usize, Node)> = self
+            .paragraphs
+            .iter()
+            .flatten()
+            .filter(|paragraph| paragraph.role.as_deref() != Some("title"))
+            .filter_map(|paragraph| Some((paragraph.spans.first()?.offset, paragraph)))
+            .filter(|(offset, _)| !in_table(*offset))
+            .map(|(offset, paragraph)| (offset, Node::Paragraph(paragraph)))
+            .chain(tables.iter().map(|table| {
+                let offset = table.spans.first().map_or(usize::MAX, |span| span.offset);
+                (offset, Node::Table(table))
+            }))
+            .collect();
+        nodes.sort_by_key(|(offset, _)| *offset);
+
+        let mut sections = vec![serde_json::json!({ "heading": null, "paragraphs": [], "tables": [] })];
+        for (_, node) in nodes {
+            match node {
+                Node::Paragraph(paragraph) if paragraph.role.as_deref() == Some("sectionHeading") => {
+                    sections.push(serde_json::json!({
+                        "heading": paragraph.content,
+                        "paragraphs": [],
+                        "tables": [],
+                    }));
+                }
+                Node::Paragraph(paragraph) => {
+                    let paragraph_words: Vec<&str> = paragraph
+                        .spans
+                        .iter()
+                        .flat_map(|span| {
+                            let start = words.partition_point(|(offset, _)| *offset < span.offset);
+                            let end = words.partition_point(|(offset, _)| *offset < span.offset + span.length);
+                            words[start..end].iter().map(|(_, content)| *content)
+                        })
+                        .collect();
+                    section_items(&mut sections, "paragraphs").push(serde_json::json!({
+                        "role": paragraph.role,
+                        "content": paragraph.content,
+                        "words": paragraph_words,
+                    }));
+                }
+                Node::Table(table) => {
+                    let mut rows: BTreeMap<i32, Vec<&DocumentTableCell>> = BTreeMap::new();
+                    for cell in &table.cells {
+                        rows.entry(cell.row_index).or_default().push(cell);
+                    }
+                    let rows: Vec<serde_json::Value> = rows
+                        .into_values()
+                        .map(|mut cells| {
+                            cells.sort_by_key(|cell| cell.column_index);
+                            cells
+                                .iter()
+                                .map(|cell| serde_json::json!({
+                                    "column_index": cell.column_index,
+                                    "kind": cell.kind,
+                                    "content": cell.content,
+                                }))
+                                .collect()
+                        })
+                        .collect();
+                    section_items(&mut sections, "tables").push(serde_json::json!({
+                        "row_count": table.row_count,
+                        "column_count": table.column_count,
+                        "rows": rows,
+                    }));
+                }
+            }
+        }
+        if sections[0]["paragraphs"].as_array().is_some_and(Vec::is_empty)
+            && sections[0]["tables"].as_array().is_some_and(Vec::is_empty)
+        {
+            sections.remove(0);
+        }
+
+        let tree = serde_json::json!({ "title": self.get_title(), "sections": sections });
+        Ok(pythonize(py, &tree)?.unbind())
+    }
+}
+
+/// The `key` list ("paragraphs" or "tables") of the last section in a
+/// structure tree under construction.
+fn section_items<'a>(sections: &'a mut [serde_json::Value], key: &str) -> &'a mut Vec<serde_json::Value> {
+    sections
+        .last_mut()
+        .and_then(|section| section[key].as_array_mut())
+        .expect("every section has paragraphs and tables lists")
+}
+
+/// A table's cell contents keyed by `(row_index, column_index)`.
+fn table_cells(table: Option<&DocumentTable>) -> BTreeMap<(i32, i32), &str> {
+    table
+        .map(|table| {
+            table
+                .cells
+                .iter()
+                .map(|cell| ((cell.row_index, cell.column_index), cell.content.as_str()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Moves each span from the original content onto the rewritten one.
+fn remap_spans(spans: &mut [DocumentSpan], map: &OffsetMap) {
+    for span in spans {
+        (span.offset, span.length) = map.map_span(span.offset, span.length);
+    }
+}
+
+/// Remaps an element's spans and, if any of them covered a replaced word,
+/// rebuilds its content from the rewritten text.
+fn remap_element(content: &mut String, spans: &mut [DocumentSpan], map: &OffsetMap, chars: &[char]) {
+    let touched = spans.iter().any(|span| map.touches(span.offset, span.length));
+    remap_spans(spans, map);
+    if touched {
+        *content = spans
+            .iter()
+            .map(|span| span_text(chars, span.offset, span.length))
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+}
+
+/// Remaps a field and its nested array and object members.
+fn remap_field(field: &mut DocumentField, map: &OffsetMap, chars: &[char]) {
+    match &mut field.content {
+        Some(content) => remap_element(content, &mut field.spans, map, chars),
+        None => remap_spans(&mut field.spans, map),
+    }
+    for member in field.value_array.iter_mut().flatten() {
+        remap_field(member, map, chars);
+    }
+    for member in field.value_object.iter_mut().flat_map(|object| object.values_mut()) {
+        remap_field(member, map, chars);
+    }
+}
+
+/// Sorted, deduplicated page numbers of `regions`.
+fn region_pages<'a>(regions: impl Iterator<Item = &'a BoundingRegion>) -> Vec<i32> {
+    regions
+        .map(|region| region.page_number)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+impl AnalyzeResult {
+    fn paragraphs_with_role<'a>(
+        &'a self,
+        role: &'a str,
+    ) -> impl Iterator<Item = &'a DocumentParagraph> + 'a {
+        self.paragraphs
+            .iter()
+            .flatten()
+            .filter(move |paragraph| paragraph.role.as_deref() == Some(role))
+    }
+
+    /// Fraction of `content` (in characters) covered by each detected locale,
+    /// largest first; equal fractions are ordered by locale.
+    fn language_coverage(&self) -> Vec<(&str, f64)> {
+        let total = self.content.chars().count();
+        if total == 0 {
+            return Vec::new();
+        }
+        let mut covered: BTreeMap<&str, usize> = BTreeMap::new();
+        for language in self.languages.iter().flatten() {
+            let length: usize = language.spans.iter().map(|span| span.length).sum();
+            *covered.entry(language.locale.as_str()).or_default() += length;
+        }
+        let mut coverage: Vec<(&str, f64)> = covered
+            .into_iter()
+            .map(|(locale, length)| (locale, (length as f64 / total as f64).min(1.0)))
+            .collect();
+        coverage.sort_by(|a, b| b.1.total_cmp(&a.1));
+        coverage
+    }
+
+    /// Axis-aligned bounds of every table region on page `page_number`.
+    fn table_bounds(&self, page_number: i32) -> Vec<(f32, f32, f32, f32)> {
+        self.tables
+            .iter()
+            .flatten()
+            .flat_map(|table| table.bounding_regions.iter().flatten())
+            .filter(|region| region.page_number == page_number)
+            .filter_map(|region| polygon_bounds(&region.polygon))
+            .collect()
+    }
+}
+
+/// Mean confidence of the words whose offsets fall inside `spans`, or 1.0 when
+/// no word does. `words` must be sorted by offset.
+fn mean_confidence(words: &[(usize, f32)], spans: &[DocumentSpan]) -> f32 {
+    let (mut total, mut count) = (0.0, 0);
+    for span in spans {
+        let start = words.partition_point(|(offset, _)| *offset < span.offset);
+        let end = words.partition_point(|(offset, _)| *offset < span.offset + span.length);
+        for (_, confidence) in &words[start..end] {
+            total += confidence;
+            count += 1;
+        }
+    }
+    if count == 0 { 1.0 } else { total / count as f32 }
+}
+
+pub(crate) fn page_text(chars: &[char], page: &DocumentPage) -> String {
+    page.spans
+        .iter()
+        .map(|span| span_text(chars, span.offset, span.length))
+        .collect()
 }
-impl_to_dict!(AnalyzeResult);
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[pyclass]
 #[serde(rename_all(deserialize="camelCase"))]
@@ -43,10 +1124,233 @@ pub struct DocumentPage {
     #[pyo3(get, set)]
     pub words: Option<Vec<DocumentWord>>,
     #[pyo3(get, set)]
+    pub selection_marks: Option<Vec<DocumentSelectionMark>>,
+    #[pyo3(get, set)]
+    pub barcodes: Option<Vec<DocumentBarcode>>,
+    #[pyo3(get, set)]
+    pub formulas: Option<Vec<DocumentFormula>>,
+    #[pyo3(get, set)]
     pub spans: Vec<DocumentSpan>,
+    /// Bounds of the result's tables on this page, attached by `AnalyzeResult.pages`.
+    #[serde(skip)]
+    pub(crate) table_bounds: Vec<(f32, f32, f32, f32)>,
 }
 impl_to_dict!(DocumentPage);
 
+#[pymethods]
+impl DocumentPage {
+    /// Split the page width into equal vertical bands.
+    ///
+    /// Args:
+    ///     n_columns (int): Number of columns to divide the page into
+    ///
+    /// Returns:
+    ///     list[float]: The `n_columns + 1` boundary x-coordinates `[0, w/n, ..., w]`
+    ///
+    /// Raises:
+    ///     ValueError: If the page has no width or `n_columns` is 0
+    pub fn column_boundaries(&self, n_columns: u32) -> PyResult<Vec<f32>> {
+        let width = self
+            .width
+            .ok_or_else(|| PyValueError::new_err("Page has no width"))?;
+        if n_columns == 0 {
+            return Err(PyValueError::new_err("n_columns must be at least 1"));
+        }
+        let step = width / n_columns as f32;
+        Ok((0..=n_columns)
+            .map(|i| if i == n_columns { width } else { i as f32 * step })
+            .collect())
+    }
+
+    /// Words whose polygon centroid falls inside an axis-aligned rectangle.
+    ///
+    /// Only the centroid is tested, so a word straddling the edge is included
+    /// or not depending on which side its centre lies. Edges are inclusive.
+    ///
+    /// Args:
+    ///     x_min (float): Left edge, in the page's unit
+    ///     y_min (float): Top edge
+    ///     x_max (float): Right edge
+    ///     y_max (float): Bottom edge
+    ///
+    /// Returns:
+    ///     list[DocumentWord]: Matching words in reading order; empty if the
+    ///         rectangle is inverted
+    pub fn select_words_in_rect(&self, x_min: f32, y_min: f32, x_max: f32, y_max: f32) -> Vec<DocumentWord> {
+        self.words
+            .iter()
+            .flatten()
+            .filter(|word| {
+                word.polygon
+                    .as_deref()
+                    .and_then(polygon_centroid)
+                    .is_some_and(|(x, y)| (x_min..=x_max).contains(&x) && (y_min..=y_max).contains(&y))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Bucket the page's words into a `cols` × `rows` grid of equal cells.
+    ///
+    /// Each word goes to the cell containing its polygon centroid; centroids on
+    /// or past the page edge are clamped into the outermost cells. Words without
+    /// a polygon are left out.
+    ///
+    /// Args:
+    ///     cols (int): Number of columns
+    ///     rows (int): Number of rows
+    ///
+    /// Returns:
+    ///     list[list[list[DocumentWord]]]: `grid[row][col]`, top-to-bottom and
+    ///         left-to-right, each cell's words in reading order
+    ///
+    /// Raises:
+    ///     ValueError: If the page has no width or height, or `cols` or `rows` is 0
+    pub fn get_word_grid(&self, cols: u32, rows: u32) -> PyResult<Vec<Vec<Vec<DocumentWord>>>> {
+        let (Some(width), Some(height)) = (self.width, self.height) else {
+            return Err(PyValueError::new_err("Page has no width or height"));
+        };
+        if cols == 0 || rows == 0 {
+            return Err(PyValueError::new_err("cols and rows must be at least 1"));
+        }
+
+        let mut grid = vec![vec![Vec::new(); cols as usize]; rows as usize];
+        let cell = |position: f32, extent: f32, count: u32| {
+            ((position / extent * count as f32).max(0.0) as usize).min(count as usize - 1)
+        };
+        for word in self.words.iter().flatten() {
+            if let Some((x, y)) = word.polygon.as_deref().and_then(polygon_centroid) {
+                grid[cell(y, height, rows)][cell(x, width, cols)].push(word.clone());
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Group the page's lines into blocks separated by vertical whitespace.
+    ///
+    /// Lines are taken in reading order; a new block starts when the gap between
+    /// the top of a line and the bottom of the previous one exceeds the
+    /// threshold. A line without a polygon stays in the current block.
+    ///
+    /// Args:
+    ///     line_gap_threshold (float): Largest gap, in the page's unit, that
+    ///         still keeps two lines in the same block
+    ///
+    /// Returns:
+    ///     list[str]: Each block's lines joined with newlines; empty if the
+    ///         page has no lines
+    pub fn get_text_blocks(&self, line_gap_threshold: f32) -> Vec<String> {
+        let mut blocks: Vec<Vec<&str>> = Vec::new();
+        let mut previous_bottom: Option<f32> = None;
+        for line in self.lines.iter().flatten() {
+            let bounds = line.polygon.as_deref().and_then(polygon_bounds);
+            let gap_exceeded = match (bounds, previous_bottom) {
+                (Some((_, top, _, _)), Some(bottom)) => top - bottom > line_gap_threshold,
+                _ => false,
+            };
+            match blocks.last_mut() {
+                Some(block) if !gap_exceeded => block.push(&line.content),
+                _ => blocks.push(vec![&line.content]),
+            }
+            if let Some((_, _, _, bottom)) = bounds {
+                previous_bottom = Some(bottom);
+            }
+        }
+        blocks.into_iter().map(|lines| lines.join("\n")).collect()
+    }
+
+    /// Median confidence of the page's words, less sensitive than the mean to
+    /// a few badly recognized words.
+    ///
+    /// Returns:
+    ///     float | None: The middle confidence, or the mean of the two middle
+    ///         ones for an even count; None if the page has no words
+    pub fn median_word_confidence(&self) -> Option<f32> {
+        let confidences = self.sorted_word_confidences();
+        let middle = confidences.len() / 2;
+        match confidences.len() {
+            0 => None,
+            len if len % 2 == 1 => Some(confidences[middle]),
+            _ => Some((confidences[middle - 1] + confidences[middle]) / 2.0),
+        }
+    }
+
+    /// Lowest confidence of the page's words.
+    ///
+    /// Returns:
+    ///     float | None: The minimum, or None if the page has no words
+    pub fn min_word_confidence(&self) -> Option<f32> {
+        self.words.iter().flatten().map(|word| word.confidence).min_by(f32::total_cmp)
+    }
+
+    /// Highest confidence of the page's words.
+    ///
+    /// Returns:
+    ///     float | None: The maximum, or None if the page has no words
+    pub fn max_word_confidence(&self) -> Option<f32> {
+        self.words.iter().flatten().map(|word| word.confidence).max_by(f32::total_cmp)
+    }
+
+    /// Classify the page by how much of its area is covered by tables and words.
+    ///
+    /// Areas are measured with axis-aligned bounding boxes. A page more than 80%
+    /// covered by tables is `"table"`; one with under 5% covered by words is
+    /// `"image"`; a page without tables is `"text"`; anything else is `"mixed"`.
+    /// Tables are those of the result the page was read from.
+    ///
+    /// Returns:
+    ///     str: One of `"table"`, `"image"`, `"text"` or `"mixed"`
+    pub fn page_type(&self) -> &'static str {
+        self.classify(&self.table_bounds)
+    }
+}
+
+impl DocumentPage {
+    fn sorted_word_confidences(&self) -> Vec<f32> {
+        let mut confidences: Vec<f32> = self.words.iter().flatten().map(|word| word.confidence).collect();
+        confidences.sort_by(f32::total_cmp);
+        confidences
+    }
+
+    pub(crate) fn contains_offset(&self, offset: usize) -> bool {
+        self.spans
+            .iter()
+            .any(|span| (span.offset..span.offset + span.length).contains(&offset))
+    }
+
+    fn area(&self) -> Option<f32> {
+        let area = self.width? * self.height?;
+        (area > 0.0).then_some(area)
+    }
+
+    fn classify(&self, table_bounds: &[(f32, f32, f32, f32)]) -> &'static str {
+        let table_coverage = self.coverage(table_bounds.iter().copied());
+        let word_coverage = self.word_coverage();
+        if table_coverage > 0.8 {
+            "table"
+        } else if word_coverage < 0.05 {
+            "image"
+        } else if table_coverage == 0.0 {
+            "text"
+        } else {
+            "mixed"
+        }
+    }
+
+    /// Fraction of the page's area inside `bounds`; 0 when the page has no
+    /// dimensions.
+    fn coverage(&self, bounds: impl Iterator<Item = (f32, f32, f32, f32)>) -> f32 {
+        let covered: f32 = bounds.map(|(x_min, y_min, x_max, y_max)| (x_max - x_min) * (y_max - y_min)).sum();
+        self.area().map_or(0.0, |area| (covered / area).min(1.0))
+    }
+
+    /// Fraction of the page's area inside word bounding boxes; 0 when the page
+    /// has no dimensions.
+    fn word_coverage(&self) -> f32 {
+        self.coverage(self.words.iter().flatten().filter_map(|word| word.polygon.as_deref().and_then(polygon_bounds)))
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[pyclass]
 #[serde(rename_all(deserialize="camelCase"))]
@@ -58,6 +1362,90 @@ pub struct DocumentLine {
     #[pyo3(get, set)]
     pub spans: Vec<DocumentSpan>,
 }
+impl_to_dict!(DocumentLine);
+
+/// A checkbox or radio button detected on a page.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentSelectionMark {
+    /// "selected" or "unselected".
+    #[pyo3(get, set)]
+    pub state: String,
+    #[pyo3(get, set)]
+    pub polygon: Option<Vec<f32>>,
+    #[pyo3(get, set)]
+    pub span: DocumentSpan,
+    #[pyo3(get, set)]
+    pub confidence: f32,
+}
+impl_to_dict!(DocumentSelectionMark);
+
+#[pymethods]
+impl DocumentSelectionMark {
+    /// Whether the mark is checked.
+    ///
+    /// Returns:
+    ///     bool: True if `state` is "selected"
+    pub fn is_selected(&self) -> bool {
+        self.state == "selected"
+    }
+}
+
+/// Barcode kinds that encode data in two dimensions.
+const BARCODE_KINDS_2D: &[&str] = &["QRCode", "MicroQRCode", "PDF417", "DataMatrix", "Aztec", "MaxiCode"];
+
+/// A barcode detected on a page (requires the `barcodes` feature).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentBarcode {
+    /// Symbology, e.g. "QRCode", "PDF417", "Code128" or "EAN13".
+    #[pyo3(get, set)]
+    pub kind: String,
+    #[pyo3(get, set)]
+    pub value: String,
+    #[pyo3(get, set)]
+    pub polygon: Option<Vec<f32>>,
+    #[pyo3(get, set)]
+    pub span: DocumentSpan,
+    #[pyo3(get, set)]
+    pub confidence: f32,
+}
+impl_to_dict!(DocumentBarcode);
+
+#[pymethods]
+impl DocumentBarcode {
+    /// Whether the barcode is two-dimensional (QR, Micro QR, PDF417, Data
+    /// Matrix, Aztec or MaxiCode) rather than linear.
+    ///
+    /// Returns:
+    ///     bool: True for 2D symbologies
+    pub fn kind_is_2d(&self) -> bool {
+        BARCODE_KINDS_2D.contains(&self.kind.as_str())
+    }
+}
+
+/// A mathematical formula detected on a page (requires the `formulas` feature).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentFormula {
+    /// "inline" or "display".
+    #[pyo3(get, set)]
+    pub kind: String,
+    /// The formula as LaTeX.
+    #[pyo3(get, set)]
+    pub value: String,
+    #[pyo3(get, set)]
+    pub polygon: Option<Vec<f32>>,
+    #[pyo3(get, set)]
+    pub span: DocumentSpan,
+    #[pyo3(get, set)]
+    pub confidence: f32,
+}
+impl_to_dict!(DocumentFormula);
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[pyclass]
 #[serde(rename_all(deserialize="camelCase"))]
@@ -71,6 +1459,7 @@ pub struct DocumentWord {
     #[pyo3(get, set)]
     pub confidence: f32,
 }
+impl_to_dict!(DocumentWord);
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[pyclass]
@@ -98,20 +1487,105 @@ pub struct DocumentTable {
     #[pyo3(get, set)]
     pub cells: Vec<DocumentTableCell>,
     #[pyo3(get, set)]
+    pub caption: Option<DocumentCaption>,
+    #[pyo3(get, set)]
     pub bounding_regions: Option<Vec<BoundingRegion>>,
     #[pyo3(get, set)]
     pub spans: Vec<DocumentSpan>,
 }
 impl_to_dict!(DocumentTable);
 
+#[pymethods]
+impl DocumentTable {
+    /// Index of the table's header row.
+    ///
+    /// Returns:
+    ///     int | None: Row index of the first cell with kind `columnHeader`, or
+    ///         None if the table has no column headers
+    pub fn detect_header_row(&self) -> Option<i32> {
+        self.cells
+            .iter()
+            .filter(|cell| cell.kind.as_deref() == Some("columnHeader"))
+            .map(|cell| cell.row_index)
+            .min()
+    }
+
+    /// Index of the table's header column.
+    ///
+    /// Returns:
+    ///     int | None: Column index of the first cell with kind `rowHeader`, or
+    ///         None if the table has no row headers
+    pub fn detect_header_column(&self) -> Option<i32> {
+        self.cells
+            .iter()
+            .filter(|cell| cell.kind.as_deref() == Some("rowHeader"))
+            .map(|cell| cell.column_index)
+            .min()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentCaption {
+    #[pyo3(get, set)]
+    pub content: String,
+    #[pyo3(get, set)]
+    pub bounding_regions: Option<Vec<BoundingRegion>>,
+    #[pyo3(get, set)]
+    pub spans: Vec<DocumentSpan>,
+}
+impl_to_dict!(DocumentCaption);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentFigure {
+    #[pyo3(get, set)]
+    pub id: Option<String>,
+    #[pyo3(get, set)]
+    pub bounding_regions: Option<Vec<BoundingRegion>>,
+    #[pyo3(get, set)]
+    pub spans: Vec<DocumentSpan>,
+    #[pyo3(get, set)]
+    pub caption: Option<DocumentCaption>,
+    /// References to the figure's content, e.g. "/paragraphs/3".
+    #[pyo3(get, set)]
+    pub elements: Option<Vec<String>>,
+}
+impl_to_dict!(DocumentFigure);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentSection {
+    #[pyo3(get, set)]
+    pub spans: Vec<DocumentSpan>,
+    /// References to the section's content in reading order, e.g.
+    /// "/paragraphs/3" or "/sections/1" for a nested section.
+    #[pyo3(get, set)]
+    pub elements: Option<Vec<String>>,
+}
+impl_to_dict!(DocumentSection);
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[pyclass]
 #[serde(rename_all(deserialize="camelCase"))]
 pub struct DocumentTableCell {
+    /// "columnHeader", "rowHeader", "stubHead" or "description"; None for
+    /// ordinary content cells.
+    #[pyo3(get, set)]
+    pub kind: Option<String>,
     #[pyo3(get, set)]
     pub row_index: i32,
     #[pyo3(get, set)]
     pub column_index: i32,
+    /// Rows covered by a merged cell; None means 1.
+    #[pyo3(get, set)]
+    pub row_span: Option<i32>,
+    /// Columns covered by a merged cell; None means 1.
+    #[pyo3(get, set)]
+    pub column_span: Option<i32>,
     #[pyo3(get, set)]
     pub content: String,
     #[pyo3(get, set)]
@@ -120,7 +1594,34 @@ pub struct DocumentTableCell {
     pub spans: Vec<DocumentSpan>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pymethods]
+impl DocumentTableCell {
+    fn __repr__(&self) -> String {
+        format!(
+            "DocumentTableCell(row_index={}, column_index={}, content={:?})",
+            self.row_index, self.column_index, self.content
+        )
+    }
+
+    /// Cells compare equal on position, content and spans. Bounding regions are
+    /// ignored since their coordinates may differ by float precision.
+    fn __eq__(&self, other: &Self) -> bool {
+        self.row_index == other.row_index
+            && self.column_index == other.column_index
+            && self.content == other.content
+            && self.spans == other.spans
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.row_index.hash(&mut hasher);
+        self.column_index.hash(&mut hasher);
+        self.content.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[pyclass]
 #[serde(rename_all(deserialize="camelCase"))]
 pub struct DocumentSpan {
@@ -141,6 +1642,33 @@ pub struct BoundingRegion {
     pub polygon: Vec<f32>,
 }
 
+/// Tolerance for polygon coordinates, which may not survive a serialization
+/// round trip bit for bit.
+const POLYGON_EPSILON: f32 = 1e-4;
+
+#[pymethods]
+impl BoundingRegion {
+    fn __repr__(&self) -> String {
+        format!(
+            "BoundingRegion(page={}, polygon=[...{} points...])",
+            self.page_number,
+            self.polygon.len() / 2
+        )
+    }
+
+    /// Regions compare equal on page number and on polygon coordinates within
+    /// `1e-4`.
+    fn __eq__(&self, other: &Self) -> bool {
+        self.page_number == other.page_number
+            && self.polygon.len() == other.polygon.len()
+            && self
+                .polygon
+                .iter()
+                .zip(&other.polygon)
+                .all(|(a, b)| (a - b).abs() <= POLYGON_EPSILON)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[pyclass]
 #[serde(rename_all(deserialize="camelCase"))]
@@ -152,3 +1680,531 @@ pub struct DocumentLanguage {
     #[pyo3(get, set)]
     pub confidence: f32,
 }
+
+#[pymethods]
+impl DocumentLanguage {
+    fn __repr__(&self) -> String {
+        format!(
+            "DocumentLanguage(locale='{}', confidence={}, spans={})",
+            self.locale,
+            self.confidence,
+            self.spans.len()
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct AnalyzedDocument {
+    #[pyo3(get, set)]
+    pub doc_type: String,
+    #[pyo3(get, set)]
+    pub confidence: Option<f32>,
+    #[pyo3(get, set)]
+    pub bounding_regions: Option<Vec<BoundingRegion>>,
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub spans: Vec<DocumentSpan>,
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub fields: HashMap<String, DocumentField>,
+}
+impl_to_dict!(AnalyzedDocument);
+
+/// An extracted field. Which `value_*` member is set depends on `type`; the
+/// `value` property returns it as the matching Python type.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentField {
+    #[pyo3(get, set, name = "type")]
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[pyo3(get, set)]
+    pub content: Option<String>,
+    #[pyo3(get, set)]
+    pub confidence: Option<f32>,
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bounding_regions: Option<Vec<BoundingRegion>>,
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<DocumentSpan>,
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_string: Option<String>,
+    /// ISO 8601 date, e.g. "2024-03-31".
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_date: Option<String>,
+    /// ISO 8601 time, e.g. "13:45:00".
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_time: Option<String>,
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_phone_number: Option<String>,
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_number: Option<f64>,
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_integer: Option<i64>,
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_boolean: Option<bool>,
+    /// "selected" or "unselected".
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_selection_mark: Option<String>,
+    /// "signed" or "unsigned".
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_signature: Option<String>,
+    /// ISO 3166-1 alpha-3 code, e.g. "USA".
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_country_region: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_currency: Option<CurrencyValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_address: Option<AddressValue>,
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_array: Option<Vec<DocumentField>>,
+    #[pyo3(get, set)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_object: Option<HashMap<String, DocumentField>>,
+}
+impl_to_dict!(DocumentField);
+
+#[pymethods]
+impl DocumentField {
+    /// The field's value as a Python object, chosen by `type`: `str` for string,
+    /// phone number, selection mark, signature and country/region fields,
+    /// `datetime.date`/`datetime.time` for dates and times (the ISO string if it
+    /// does not parse), `float`, `int` and `bool` for numbers, integers and
+    /// booleans, a dict for currencies (`amount`, `currency_symbol`,
+    /// `currency_code`) and addresses, a list of `DocumentField` for arrays and
+    /// a dict of name to `DocumentField` for objects. None when the service
+    /// returned no value.
+    #[getter]
+    pub fn value(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let value = match self.field_type.as_str() {
+            "string" => self.value_string.as_deref().into_pyobject(py)?.into_any(),
+            "date" => iso_value(py, self.value_date.as_deref(), "date")?,
+            "time" => iso_value(py, self.value_time.as_deref(), "time")?,
+            "phoneNumber" => self.value_phone_number.as_deref().into_pyobject(py)?.into_any(),
+            "number" => self.value_number.into_pyobject(py)?.into_any(),
+            "integer" => self.value_integer.into_pyobject(py)?.into_any(),
+            "boolean" => self.value_boolean.into_pyobject(py)?.into_any(),
+            "selectionMark" => self.value_selection_mark.as_deref().into_pyobject(py)?.into_any(),
+            "signature" => self.value_signature.as_deref().into_pyobject(py)?.into_any(),
+            "countryRegion" => self.value_country_region.as_deref().into_pyobject(py)?.into_any(),
+            "currency" => pythonize(py, &self.value_currency)?,
+            "address" => pythonize(py, &self.value_address)?,
+            "array" => self.value_array.clone().into_pyobject(py)?.into_any(),
+            "object" => self.value_object.clone().into_pyobject(py)?.into_any(),
+            _ => py.None().into_bound(py),
+        };
+        Ok(value.unbind())
+    }
+}
+
+/// Parses an ISO date or time string with `datetime.<kind>.fromisoformat`,
+/// falling back to the string itself.
+fn iso_value<'py>(py: Python<'py>, value: Option<&str>, kind: &str) -> PyResult<Bound<'py, PyAny>> {
+    let Some(value) = value else {
+        return Ok(py.None().into_bound(py));
+    };
+    let parsed = py
+        .import("datetime")?
+        .getattr(kind)?
+        .call_method1("fromisoformat", (value,));
+    Ok(parsed.unwrap_or_else(|_| PyString::new(py, value).into_any()))
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct CurrencyValue {
+    pub amount: f64,
+    pub currency_symbol: Option<String>,
+    /// ISO 4217 code, e.g. "USD".
+    pub currency_code: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct AddressValue {
+    pub house_number: Option<String>,
+    pub po_box: Option<String>,
+    pub road: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub postal_code: Option<String>,
+    pub country_region: Option<String>,
+    pub street_address: Option<String>,
+    pub unit: Option<String>,
+    pub city_district: Option<String>,
+    pub state_district: Option<String>,
+    pub suburb: Option<String>,
+    pub house: Option<String>,
+    pub level: Option<String>,
+}
+
+/// Font and handwriting information for the text covered by `spans`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentStyle {
+    #[pyo3(get, set)]
+    pub is_handwritten: Option<bool>,
+    #[pyo3(get, set)]
+    pub similar_font_family: Option<String>,
+    /// "normal" or "italic".
+    #[pyo3(get, set)]
+    pub font_style: Option<String>,
+    /// "normal" or "bold".
+    #[pyo3(get, set)]
+    pub font_weight: Option<String>,
+    /// Foreground color as `#rrggbb`.
+    #[pyo3(get, set)]
+    pub color: Option<String>,
+    /// Background color as `#rrggbb`.
+    #[pyo3(get, set)]
+    pub background_color: Option<String>,
+    #[pyo3(get, set)]
+    pub spans: Vec<DocumentSpan>,
+    #[pyo3(get, set)]
+    pub confidence: f32,
+}
+impl_to_dict!(DocumentStyle);
+
+/// A named entity recognized by Azure AI Language.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentEntity {
+    #[pyo3(get, set)]
+    pub text: String,
+    /// e.g. "Person", "Organization", "DateTime".
+    #[pyo3(get, set)]
+    pub category: String,
+    #[pyo3(get, set)]
+    pub subcategory: Option<String>,
+    /// Offset into `AnalyzeResult.content`, in characters.
+    #[pyo3(get, set)]
+    pub offset: usize,
+    #[pyo3(get, set)]
+    pub length: usize,
+    #[pyo3(get, set)]
+    pub confidence_score: f32,
+}
+impl_to_dict!(DocumentEntity);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentKeyValuePair {
+    #[pyo3(get, set)]
+    pub key: DocumentKeyValueElement,
+    #[pyo3(get, set)]
+    pub value: Option<DocumentKeyValueElement>,
+    #[pyo3(get, set)]
+    pub confidence: f32,
+}
+impl_to_dict!(DocumentKeyValuePair);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+#[serde(rename_all(deserialize="camelCase"))]
+pub struct DocumentKeyValueElement {
+    #[pyo3(get, set)]
+    pub content: String,
+    #[pyo3(get, set)]
+    pub bounding_regions: Option<Vec<BoundingRegion>>,
+    #[pyo3(get, set)]
+    pub spans: Vec<DocumentSpan>,
+}
+impl_to_dict!(DocumentKeyValueElement);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+pub struct ConfidenceFilteredContent {
+    #[pyo3(get, set)]
+    pub content: String,
+    #[pyo3(get, set)]
+    pub pages: HashMap<i32, String>,
+    #[pyo3(get, set)]
+    pub dropped_words: HashMap<i32, usize>,
+}
+impl_to_dict!(ConfidenceFilteredContent);
+
+/// One analyze request behind a result merged by `analyze_document_paged`.
+/// Each request is billed separately for the pages it analyzed.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+pub struct PageRangeAnalysis {
+    /// The `pages` parameter sent, e.g. `"101-200"`.
+    #[pyo3(get, set)]
+    pub pages: String,
+    /// Number of pages the service analyzed (and billed) for this range.
+    #[pyo3(get, set)]
+    pub page_count: usize,
+    /// How many times the range was submitted, including retries.
+    #[pyo3(get, set)]
+    pub attempts: u32,
+}
+impl_to_dict!(PageRangeAnalysis);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two pages of words; `beta` sits between two kept words on one line and
+    /// `epsilon` ends a line, so dropping it must not join `delta` and `zeta`.
+    fn low_confidence_words() -> AnalyzeResult {
+        let words = |entries: &[(&str, usize, f32)]| {
+            entries
+                .iter()
+                .map(|&(content, offset, confidence)| {
+                    serde_json::json!({
+                        "content": content,
+                        "span": { "offset": offset, "length": content.chars().count() },
+                        "confidence": confidence,
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        serde_json::from_value(serde_json::json!({
+            "apiVersion": "2024-11-30",
+            "modelId": "prebuilt-read",
+            "content": "alpha beta gamma\ndelta epsilon\nzeta",
+            "pages": [
+                {
+                    "pageNumber": 1,
+                    "spans": [{ "offset": 0, "length": 16 }],
+                    "words": words(&[("alpha", 0, 0.99), ("beta", 6, 0.2), ("gamma", 11, 0.98)]),
+                },
+                {
+                    "pageNumber": 2,
+                    "spans": [{ "offset": 17, "length": 18 }],
+                    "words": words(&[("delta", 17, 0.97), ("epsilon", 23, 0.3), ("zeta", 31, 0.95)]),
+                },
+            ],
+        }))
+        .unwrap()
+    }
+
+    /// Four 10x10 pages: mostly table, words only, table beside words, and blank.
+    fn page_layouts() -> AnalyzeResult {
+        let rect = |x_max: f32| serde_json::json!([0.0, 0.0, x_max, 0.0, x_max, 10.0, 0.0, 10.0]);
+        let page = |page_number: i32, words: serde_json::Value| {
+            serde_json::json!({
+                "pageNumber": page_number,
+                "width": 10.0,
+                "height": 10.0,
+                "spans": [],
+                "words": words,
+            })
+        };
+        let word = serde_json::json!([{
+            "content": "text",
+            "polygon": rect(5.0),
+            "span": { "offset": 0, "length": 4 },
+            "confidence": 0.99,
+        }]);
+        let table = |page_number: i32, x_max: f32| {
+            serde_json::json!({
+                "rowCount": 1,
+                "columnCount": 1,
+                "cells": [],
+                "boundingRegions": [{ "pageNumber": page_number, "polygon": rect(x_max) }],
+                "spans": [],
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "apiVersion": "2024-11-30",
+            "modelId": "prebuilt-layout",
+            "content": "text",
+            "pages": [page(1, word.clone()), page(2, word.clone()), page(3, word), page(4, serde_json::json!([]))],
+            "tables": [table(1, 9.0), table(3, 4.0)],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn pages_read_from_a_result_classify_themselves() {
+        let result = page_layouts();
+        let types: Vec<_> = result.pages().iter().map(DocumentPage::page_type).collect();
+        assert_eq!(types, ["table", "text", "mixed", "image"]);
+
+        Python::initialize();
+        Python::attach(|py| {
+            let by_page: BTreeMap<i32, String> = result.page_types(py).unwrap().extract(py).unwrap();
+            assert_eq!(by_page.into_values().collect::<Vec<_>>(), types);
+        });
+    }
+
+    #[test]
+    fn dropped_words_leave_one_separator_between_kept_words() {
+        let result = low_confidence_words();
+
+        let removed = result.content_above_confidence(0.5, "");
+        assert_eq!(removed.content, "alpha gamma\ndelta\nzeta");
+        assert_eq!(removed.pages[&1], "alpha gamma");
+        assert_eq!(removed.pages[&2], "delta\nzeta");
+
+        let marked = result.content_above_confidence(0.5, "\u{fffd}");
+        assert_eq!(marked.content, "alpha \u{fffd} gamma\ndelta \u{fffd}\nzeta");
+        assert_eq!(marked.pages[&1], "alpha \u{fffd} gamma");
+        assert_eq!(marked.pages[&2], "delta \u{fffd}\nzeta");
+
+        for filtered in [&removed, &marked] {
+            assert_eq!(filtered.dropped_words, HashMap::from([(1, 1), (2, 1)]));
+        }
+        let kept = result.content_above_confidence(0.1, "");
+        assert_eq!(kept.content, result.content);
+        assert_eq!(kept.dropped_words, HashMap::from([(1, 0), (2, 0)]));
+    }
+
+    fn invoice() -> AnalyzedDocument {
+        let result: AnalyzeResult = serde_json::from_str(include_str!("testdata/prebuilt_invoice.json")).unwrap();
+        assert_eq!(result.model_id, "prebuilt-invoice");
+        let mut documents = result.documents.unwrap();
+        assert_eq!(documents.len(), 1);
+        documents.remove(0)
+    }
+
+    #[test]
+    fn prebuilt_invoice_fields_deserialize() {
+        let document = invoice();
+        assert_eq!(document.doc_type, "invoice");
+
+        let id = &document.fields["InvoiceId"];
+        assert_eq!(id.field_type, "string");
+        assert_eq!(id.value_string.as_deref(), Some("INV-100"));
+        assert_eq!(id.bounding_regions.as_ref().unwrap()[0].page_number, 1);
+
+        assert_eq!(document.fields["InvoiceDate"].value_date.as_deref(), Some("2019-11-15"));
+
+        let total = document.fields["InvoiceTotal"].value_currency.as_ref().unwrap();
+        assert_eq!(total.amount, 110.0);
+        assert_eq!(total.currency_code.as_deref(), Some("USD"));
+
+        let address = document.fields["CustomerAddress"].value_address.as_ref().unwrap();
+        assert_eq!(address.street_address.as_deref(), Some("123 Other St"));
+        assert_eq!(address.postal_code.as_deref(), Some("98052"));
+
+        let items = document.fields["Items"].value_array.as_ref().unwrap();
+        assert_eq!(items.len(), 2);
+        let first = items[0].value_object.as_ref().unwrap();
+        assert_eq!(first["Description"].value_string.as_deref(), Some("Consulting Services"));
+        assert_eq!(first["Quantity"].value_number, Some(2.0));
+        assert_eq!(first["Amount"].value_currency.as_ref().unwrap().amount, 60.0);
+        let second = items[1].value_object.as_ref().unwrap();
+        assert!(!second.contains_key("Quantity"));
+        assert_eq!(second["Amount"].value_currency.as_ref().unwrap().amount, 30.0);
+    }
+
+    #[test]
+    fn layout_key_value_pairs_deserialize() {
+        let result: AnalyzeResult =
+            serde_json::from_str(include_str!("testdata/layout_key_value_pairs.json")).unwrap();
+        let pairs = result.key_value_pairs.as_ref().unwrap();
+        assert_eq!(pairs.len(), 3);
+
+        let name = &pairs[0];
+        assert_eq!(name.key.content, "Name:");
+        assert_eq!(name.confidence, 0.952);
+        let regions = name.key.bounding_regions.as_ref().unwrap();
+        assert_eq!(regions[0].page_number, 1);
+        assert_eq!(regions[0].polygon.len(), 8);
+        let value = name.value.as_ref().unwrap();
+        assert_eq!(value.content, "Jane Doe");
+        assert_eq!((value.spans[0].offset, value.spans[0].length), (6, 8));
+
+        assert!(pairs[2].value.is_none());
+
+        let on_second_page = result.get_key_value_pairs_on_page(2);
+        assert_eq!(on_second_page.len(), 1);
+        assert_eq!(on_second_page[0].key.content, "Signature:");
+        assert_eq!(result.get_key_value_pairs_on_page(1).len(), 2);
+    }
+
+    #[test]
+    fn layout_barcodes_deserialize() {
+        let result: AnalyzeResult =
+            serde_json::from_str(include_str!("testdata/layout_barcodes_formulas.json")).unwrap();
+        let barcodes = result.pages[0].barcodes.as_ref().unwrap();
+        assert_eq!(barcodes.len(), 2);
+
+        let qr = &barcodes[0];
+        assert_eq!(qr.kind, "QRCode");
+        assert_eq!(qr.value, "https://example.com/track/1Z999AA10123456784");
+        assert_eq!(qr.polygon.as_ref().unwrap().len(), 8);
+        assert_eq!((qr.span.offset, qr.span.length), (15, 9));
+        assert_eq!(qr.confidence, 0.995);
+        assert!(qr.kind_is_2d());
+        assert!(!barcodes[1].kind_is_2d());
+
+        assert!(result.pages[1].barcodes.is_none());
+    }
+
+    #[test]
+    fn layout_formulas_deserialize() {
+        let result: AnalyzeResult =
+            serde_json::from_str(include_str!("testdata/layout_barcodes_formulas.json")).unwrap();
+        assert!(result.pages[0].formulas.is_none());
+        let formulas = result.pages[1].formulas.as_ref().unwrap();
+        assert_eq!(formulas.len(), 2);
+
+        assert_eq!(formulas[0].kind, "inline");
+        assert_eq!(formulas[0].value, "E = m c ^ { 2 }");
+        assert_eq!((formulas[0].span.offset, formulas[0].span.length), (43, 9));
+        let display = &formulas[1];
+        assert_eq!(display.kind, "display");
+        assert!(display.value.starts_with("\\int _ { 0 }"), "{}", display.value);
+        assert_eq!(display.confidence, 0.871);
+
+        assert_eq!(result.pages_with_formulas(), [2]);
+    }
+
+    #[test]
+    fn prebuilt_invoice_values_convert_to_python() {
+        let document = invoice();
+        Python::initialize();
+        Python::attach(|py| {
+            let date = document.fields["InvoiceDate"].value(py).unwrap().into_bound(py);
+            assert_eq!(date.get_type().name().unwrap().to_string(), "date");
+            assert_eq!(date.call_method0("isoformat").unwrap().extract::<String>().unwrap(), "2019-11-15");
+
+            let total = document.fields["InvoiceTotal"].value(py).unwrap().into_bound(py);
+            assert_eq!(total.get_item("amount").unwrap().extract::<f64>().unwrap(), 110.0);
+            assert_eq!(total.get_item("currency_symbol").unwrap().extract::<String>().unwrap(), "$");
+
+            let items = document.fields["Items"].value(py).unwrap().into_bound(py);
+            let items = items.extract::<Vec<DocumentField>>().unwrap();
+            let description = items[1].value_object.as_ref().unwrap()["Description"].value(py).unwrap();
+            assert_eq!(description.extract::<String>(py).unwrap(), "Document Fee");
+
+            // Nested arrays and objects survive `to_dict` with their values intact.
+            let dict = document.to_dict(py).unwrap().into_bound(py);
+            let amount = dict
+                .get_item("fields").unwrap()
+                .get_item("Items").unwrap()
+                .get_item("value_array").unwrap()
+                .get_item(0).unwrap()
+                .get_item("value_object").unwrap()
+                .get_item("Amount").unwrap()
+                .get_item("value_currency").unwrap()
+                .get_item("amount").unwrap();
+            assert_eq!(amount.extract::<f64>().unwrap(), 60.0);
+        });
+    }
+}
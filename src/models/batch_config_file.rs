@@ -0,0 +1,50 @@
+use std::path::{Path, PathBuf};
+
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use serde::Deserialize;
+
+/// A batch job described in a JSON file, as read by
+/// `process_batch_from_config_file`.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BatchConfigFile {
+    pub model_id: String,
+    pub document_urls: Option<Vec<String>>,
+    pub file_paths: Option<Vec<PathBuf>>,
+    pub features: Option<Vec<String>>,
+    pub output_format: Option<String>,
+}
+
+/// The documents of a config file.
+pub enum BatchConfigInputs {
+    Urls(Vec<String>),
+    FilePaths(Vec<PathBuf>),
+}
+
+impl BatchConfigFile {
+    /// Reads and parses `path`. Relative `file_paths` are resolved against the
+    /// directory containing the config file.
+    pub fn load(path: &Path) -> PyResult<(Self, BatchConfigInputs)> {
+        let text = std::fs::read_to_string(path).map_err(|err| {
+            PyIOError::new_err(format!("Failed to read config file {}: {}", path.display(), err))
+        })?;
+        let mut config: BatchConfigFile = serde_json::from_str(&text).map_err(|err| {
+            PyValueError::new_err(format!("Invalid config file {}: {}", path.display(), err))
+        })?;
+        let inputs = match (config.document_urls.take(), config.file_paths.take()) {
+            (Some(urls), None) => BatchConfigInputs::Urls(urls),
+            (None, Some(paths)) => {
+                let base = path.parent().unwrap_or(Path::new(""));
+                BatchConfigInputs::FilePaths(paths.into_iter().map(|file| base.join(file)).collect())
+            }
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Config file {} must contain exactly one of 'document_urls' and 'file_paths'",
+                    path.display()
+                )));
+            }
+        };
+        Ok((config, inputs))
+    }
+}
@@ -0,0 +1,157 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Reusable settings for the batch methods, built up with `with_*` calls.
+///
+/// Every `with_*` method returns a new config, so a base config can be shared
+/// and specialized per call. Settings made on the config take precedence over
+/// the corresponding keyword arguments of the batch method it is passed to;
+/// settings left unset fall back to those arguments.
+///
+/// Example:
+///     >>> config = (
+///     ...     BatchProcessingConfig()
+///     ...     .with_features(["ocrHighResolution"])
+///     ...     .with_output_format("markdown")
+///     ...     .with_max_rps(5)
+///     ... )
+///     >>> results = client.process_batch_documents_from_urls(
+///     ...     "prebuilt-layout", urls, config=config
+///     ... )
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+#[pyo3(from_py_object)]
+pub struct BatchProcessingConfig {
+    #[pyo3(get)]
+    pub features: Option<Vec<String>>,
+    #[pyo3(get)]
+    pub output_format: Option<String>,
+    #[pyo3(get)]
+    pub max_rps: Option<usize>,
+    /// Per-document timeout in seconds.
+    #[pyo3(get)]
+    pub timeout_secs: Option<f64>,
+    #[pyo3(get)]
+    pub poll_interval: Option<f64>,
+    #[pyo3(get)]
+    pub max_retries: Option<u32>,
+    #[pyo3(get)]
+    pub initial_backoff_ms: Option<u64>,
+}
+
+#[pymethods]
+impl BatchProcessingConfig {
+    /// Create an empty config; every setting falls back to the batch method's
+    /// arguments.
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Analysis features to enable, e.g. ['ocrHighResolution', 'formulas'].
+    ///
+    /// Returns:
+    ///     BatchProcessingConfig: A copy with `features` set
+    pub fn with_features(&self, features: Vec<String>) -> Self {
+        Self { features: Some(features), ..self.clone() }
+    }
+
+    /// Output content format, 'text' or 'markdown'.
+    ///
+    /// Returns:
+    ///     BatchProcessingConfig: A copy with `output_format` set
+    ///
+    /// Raises:
+    ///     ValueError: If the format is neither 'text' nor 'markdown'
+    pub fn with_output_format(&self, output_format: String) -> PyResult<Self> {
+        if !matches!(output_format.trim().to_lowercase().as_str(), "text" | "markdown") {
+            return Err(PyValueError::new_err(format!(
+                "Invalid output format: '{}'. Expected 'text' or 'markdown'.",
+                output_format
+            )));
+        }
+        Ok(Self { output_format: Some(output_format), ..self.clone() })
+    }
+
+    /// Maximum concurrent requests per resource.
+    ///
+    /// Returns:
+    ///     BatchProcessingConfig: A copy with `max_rps` set
+    ///
+    /// Raises:
+    ///     ValueError: If `max_rps` is 0
+    pub fn with_max_rps(&self, max_rps: usize) -> PyResult<Self> {
+        if max_rps == 0 {
+            return Err(PyValueError::new_err("max_rps must be at least 1"));
+        }
+        Ok(Self { max_rps: Some(max_rps), ..self.clone() })
+    }
+
+    /// Timeout for each document, in seconds, covering submission and
+    /// polling. Must not exceed the client's `batch_timeout_secs`.
+    ///
+    /// Returns:
+    ///     BatchProcessingConfig: A copy with `timeout_secs` set
+    ///
+    /// Raises:
+    ///     ValueError: If `timeout_secs` is negative or not finite
+    pub fn with_timeout_per_document(&self, timeout_secs: f64) -> PyResult<Self> {
+        Ok(Self { timeout_secs: Some(non_negative_secs("timeout_secs", timeout_secs)?), ..self.clone() })
+    }
+
+    /// Status polling and retry settings. Arguments left as None keep the
+    /// current value.
+    ///
+    /// Args:
+    ///     poll_interval (float | None): Delay before the first status poll, in
+    ///         seconds
+    ///     max_retries (int | None): Retries of the analyze request and of each
+    ///         status poll on 429 and 5xx responses
+    ///     initial_backoff_ms (int | None): Delay before the first retry without a
+    ///         `Retry-After`, doubling on each further retry
+    ///
+    /// Returns:
+    ///     BatchProcessingConfig: A copy with the given settings
+    ///
+    /// Raises:
+    ///     ValueError: If `poll_interval` is negative or not finite
+    #[pyo3(signature = (poll_interval=None, max_retries=None, initial_backoff_ms=None))]
+    pub fn with_polling_config(
+        &self,
+        poll_interval: Option<f64>,
+        max_retries: Option<u32>,
+        initial_backoff_ms: Option<u64>,
+    ) -> PyResult<Self> {
+        let poll_interval = poll_interval
+            .map(|secs| non_negative_secs("poll_interval", secs))
+            .transpose()?;
+        Ok(Self {
+            poll_interval: poll_interval.or(self.poll_interval),
+            max_retries: max_retries.or(self.max_retries),
+            initial_backoff_ms: initial_backoff_ms.or(self.initial_backoff_ms),
+            ..self.clone()
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "BatchProcessingConfig(features={:?}, output_format={:?}, max_rps={:?}, timeout_secs={:?}, \
+             poll_interval={:?}, max_retries={:?}, initial_backoff_ms={:?})",
+            self.features,
+            self.output_format,
+            self.max_rps,
+            self.timeout_secs,
+            self.poll_interval,
+            self.max_retries,
+            self.initial_backoff_ms
+        )
+    }
+}
+
+fn non_negative_secs(name: &str, secs: f64) -> PyResult<f64> {
+    if secs.is_finite() && secs >= 0.0 {
+        Ok(secs)
+    } else {
+        Err(PyValueError::new_err(format!("{} must be a non-negative number", name)))
+    }
+}
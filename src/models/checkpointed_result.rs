@@ -0,0 +1,27 @@
+use pyo3::prelude::*;
+use pythonize::pythonize;
+use serde::{Deserialize, Serialize};
+
+use crate::impl_to_dict;
+
+/// Placeholder returned for a document that a checkpoint shows as already
+/// completed, when its result was not saved to an output directory.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[pyclass]
+pub struct CheckpointedResult {
+    #[pyo3(get, set)]
+    pub source: String,
+    #[pyo3(get, set)]
+    pub status: String,
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub index: Option<usize>,
+}
+impl_to_dict!(CheckpointedResult);
+
+#[pymethods]
+impl CheckpointedResult {
+    fn __repr__(&self) -> String {
+        format!("CheckpointedResult(source={:?}, status={:?})", self.source, self.status)
+    }
+}
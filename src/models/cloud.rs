@@ -0,0 +1,81 @@
+use std::str::FromStr;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// The Azure cloud a resource lives in.
+///
+/// Determines the endpoint domain suffix, the default domain for bare resource
+/// names and the Entra ID (AAD) scope and authority used for token auth.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AzureCloud {
+    #[default]
+    Public,
+    UsGov,
+    China,
+    /// Private endpoints, gateways and proxies; no suffix is enforced.
+    Custom,
+}
+
+impl AzureCloud {
+    /// Host suffixes accepted for this cloud, or `None` if any host is allowed.
+    pub fn domain_suffixes(&self) -> Option<&'static [&'static str]> {
+        match self {
+            AzureCloud::Public => Some(&["cognitiveservices.azure.com", "api.cognitive.microsoft.com"]),
+            AzureCloud::UsGov => Some(&["cognitiveservices.azure.us"]),
+            AzureCloud::China => Some(&["cognitiveservices.azure.cn"]),
+            AzureCloud::Custom => None,
+        }
+    }
+
+    /// Domain appended to bare resource names.
+    pub fn default_domain(&self) -> Option<&'static str> {
+        self.domain_suffixes().and_then(|suffixes| suffixes.first().copied())
+    }
+
+    /// Token scope requested from Entra ID for this cloud.
+    pub fn aad_scope(&self) -> &'static str {
+        match self {
+            AzureCloud::Public | AzureCloud::Custom => "https://cognitiveservices.azure.com/.default",
+            AzureCloud::UsGov => "https://cognitiveservices.azure.us/.default",
+            AzureCloud::China => "https://cognitiveservices.azure.cn/.default",
+        }
+    }
+
+    /// Entra ID authority host for this cloud.
+    pub fn aad_authority(&self) -> &'static str {
+        match self {
+            AzureCloud::Public | AzureCloud::Custom => "https://login.microsoftonline.com",
+            AzureCloud::UsGov => "https://login.microsoftonline.us",
+            AzureCloud::China => "https://login.chinacloudapi.cn",
+        }
+    }
+}
+
+impl FromStr for AzureCloud {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "public" => Ok(AzureCloud::Public),
+            "usgov" => Ok(AzureCloud::UsGov),
+            "china" => Ok(AzureCloud::China),
+            "custom" => Ok(AzureCloud::Custom),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid cloud: '{}'. Expected 'public', 'usgov', 'china' or 'custom'.",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for AzureCloud {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AzureCloud::Public => write!(f, "public"),
+            AzureCloud::UsGov => write!(f, "usgov"),
+            AzureCloud::China => write!(f, "china"),
+            AzureCloud::Custom => write!(f, "custom"),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+/// Outcome of checking one credential against its endpoint.
+#[derive(Serialize, Clone, Debug)]
+pub struct CredentialCheck {
+    pub endpoint: String,
+    /// One of `ok`, `auth_failed`, `throttled`, `unreachable` or `error`.
+    pub status: &'static str,
+    pub http_status: Option<u16>,
+    pub detail: Option<String>,
+}
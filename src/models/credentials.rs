@@ -1,5 +1,40 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use secrecy::SecretString;
+use secrecy::{ExposeSecret, SecretString};
+use tracing::debug;
+
+use std::str::FromStr;
+
+use crate::models::AzureCloud;
+use crate::utils::endpoint::{has_domain_suffix, normalize_endpoint};
+
+/// Characters of the API key left visible at each end by `masked_key`.
+const MASKED_KEY_VISIBLE_CHARS: usize = 4;
+
+/// Largest accepted `weight`. Bounds the precomputed weighted round-robin
+/// order, whose length is the sum of the (reduced) weights.
+const MAX_WEIGHT: u32 = 10_000;
+
+/// Environment variables read by `from_env`, following the Azure SDK naming.
+const ENDPOINT_ENV_VAR: &str = "AZURE_DI_ENDPOINT";
+const API_KEY_ENV_VAR: &str = "AZURE_DI_API_KEY";
+
+/// How requests are authenticated.
+#[derive(Clone)]
+pub enum CredentialKind {
+    /// Resource key, sent as `Ocp-Apim-Subscription-Key`.
+    ApiKey(SecretString),
+    /// Entra ID access token, sent as `Authorization: Bearer <token>`.
+    BearerToken(SecretString),
+}
+
+impl CredentialKind {
+    fn secret(&self) -> &SecretString {
+        match self {
+            CredentialKind::ApiKey(secret) | CredentialKind::BearerToken(secret) => secret,
+        }
+    }
+}
 
 #[pyclass]
 #[derive(Clone)]
@@ -11,22 +46,263 @@ use secrecy::SecretString;
 ///
 /// # Fields
 ///
-/// * `api_key` - A secret string containing the API key for authentication.
-///   This field is kept private to prevent accidental exposure.
-/// * `endpoint` - The API endpoint URL. This field is exposed to Python via
+/// * `kind` - The API key or Entra ID bearer token used for authentication.
+///   Not exposed to Python to prevent accidental exposure.
+/// * `endpoint` - The API endpoint URL, stored in canonical form
+///   (`https://<host>` without trailing slashes or API paths). This field is
+///   exposed to Python via a read-only getter.
+/// * `cloud` - The Azure cloud the resource belongs to.
+/// * `weight` - Relative share of a batch's documents routed to this
+///   credential. Exposed to Python via a read-only getter.
 pub struct Credentials {
-    pub api_key: SecretString,
+    pub kind: CredentialKind,
     #[pyo3(get)]
     pub endpoint: String,
+    pub cloud: AzureCloud,
+    #[pyo3(get)]
+    pub weight: u32,
 }
 #[pymethods]
 impl Credentials {
+    /// Create credentials for a Document Intelligence resource.
+    ///
+    /// Args:
+    ///     endpoint (str): Endpoint URL, host, or (with `expand_resource_name`) bare
+    ///         resource name. Normalized to `https://<host>` with trailing slashes
+    ///         and any API path removed.
+    ///     api_key (str | None): The subscription key
+    ///     expand_resource_name (bool): Append the cloud's default domain to bare
+    ///         resource names like `myresource`. Defaults to False.
+    ///     cloud (str | None): One of 'public', 'usgov', 'china' or 'custom'. When
+    ///         given, the endpoint must use that cloud's domain (except for
+    ///         'custom'). Defaults to None, meaning the public cloud without
+    ///         suffix validation.
+    ///     bearer_token (str | None): Entra ID access token to send as
+    ///         `Authorization: Bearer` instead of an API key, e.g. from
+    ///         `azure.identity` with `aad_scope`. The token is used as given and
+    ///         not refreshed. Defaults to None.
+    ///     weight (int): Relative share of documents routed to this credential,
+    ///         e.g. 10 for an endpoint with ten times the quota of one with
+    ///         weight 1. Between 1 and 10000. Defaults to 1.
+    ///
+    /// Raises:
+    ///     ValueError: If the endpoint cannot be normalized or does not belong
+    ///         to the requested cloud, unless exactly one of `api_key` and
+    ///         `bearer_token` is given, or if `weight` is out of range
     #[new]
-    #[pyo3(signature=(endpoint, api_key))]
-    pub fn new(endpoint: String, api_key: String) -> Self {
-        Self {
-            api_key: SecretString::from(api_key),
-            endpoint,
+    #[pyo3(signature=(endpoint, api_key=None, expand_resource_name=false, cloud=None, bearer_token=None, weight=1))]
+    pub fn new(
+        endpoint: String,
+        api_key: Option<String>,
+        expand_resource_name: bool,
+        cloud: Option<String>,
+        bearer_token: Option<String>,
+        weight: u32,
+    ) -> PyResult<Self> {
+        if !(1..=MAX_WEIGHT).contains(&weight) {
+            return Err(PyValueError::new_err(format!(
+                "weight must be between 1 and {}",
+                MAX_WEIGHT
+            )));
+        }
+        let kind = match (api_key, bearer_token) {
+            (Some(api_key), None) => CredentialKind::ApiKey(SecretString::from(api_key)),
+            (None, Some(token)) => CredentialKind::BearerToken(SecretString::from(token)),
+            (Some(_), Some(_)) => {
+                return Err(PyValueError::new_err("Pass either api_key or bearer_token, not both"));
+            }
+            (None, None) => {
+                return Err(PyValueError::new_err("Either api_key or bearer_token is required"));
+            }
+        };
+        Ok(Self { weight, ..Self::with_kind(endpoint, kind, expand_resource_name, cloud)? })
+    }
+
+    /// Create credentials that authenticate with an Entra ID access token.
+    ///
+    /// Args:
+    ///     endpoint (str): Endpoint URL, host, or resource name, as for the
+    ///         constructor.
+    ///     token (str): Access token for the cloud's `aad_scope`, sent as
+    ///         `Authorization: Bearer`. It is used as given and not refreshed.
+    ///     expand_resource_name (bool): As for the constructor. Defaults to False.
+    ///     cloud (str | None): As for the constructor. Defaults to None.
+    ///
+    /// Returns:
+    ///     Credentials: The token credentials
+    ///
+    /// Raises:
+    ///     ValueError: If the endpoint cannot be normalized or does not belong
+    ///         to the requested cloud
+    #[staticmethod]
+    #[pyo3(signature=(endpoint, token, expand_resource_name=false, cloud=None))]
+    pub fn from_bearer_token(
+        endpoint: String,
+        token: String,
+        expand_resource_name: bool,
+        cloud: Option<String>,
+    ) -> PyResult<Self> {
+        let kind = CredentialKind::BearerToken(SecretString::from(token));
+        Self::with_kind(endpoint, kind, expand_resource_name, cloud)
+    }
+
+    /// Create credentials from the `AZURE_DI_ENDPOINT` and `AZURE_DI_API_KEY`
+    /// environment variables.
+    ///
+    /// Returns:
+    ///     Credentials: API-key credentials for the endpoint
+    ///
+    /// Raises:
+    ///     ValueError: If either variable is unset or empty, or the endpoint
+    ///         cannot be normalized
+    #[staticmethod]
+    pub fn from_env() -> PyResult<Self> {
+        Self::from_env_prefix("")
+    }
+
+    /// Create credentials from `<prefix>_AZURE_DI_ENDPOINT` and
+    /// `<prefix>_AZURE_DI_API_KEY`, for setups with several resources.
+    ///
+    /// Args:
+    ///     prefix (str): Variable name prefix, e.g. 'STAGE' for
+    ///         `STAGE_AZURE_DI_ENDPOINT`. A trailing underscore is optional; an
+    ///         empty prefix reads the same variables as `from_env`.
+    ///
+    /// Returns:
+    ///     Credentials: API-key credentials for the endpoint
+    ///
+    /// Raises:
+    ///     ValueError: If either variable is unset or empty, or the endpoint
+    ///         cannot be normalized
+    #[staticmethod]
+    pub fn from_env_prefix(prefix: &str) -> PyResult<Self> {
+        let endpoint = read_env_var(prefix, ENDPOINT_ENV_VAR)?;
+        let api_key = read_env_var(prefix, API_KEY_ENV_VAR)?;
+        Self::with_kind(endpoint, CredentialKind::ApiKey(SecretString::from(api_key)), false, None)
+    }
+
+    /// Whether this credential authenticates with an Entra ID bearer token
+    /// rather than an API key.
+    #[getter]
+    pub fn is_bearer_token(&self) -> bool {
+        matches!(self.kind, CredentialKind::BearerToken(_))
+    }
+
+    /// The Azure cloud this credential targets.
+    #[getter]
+    pub fn cloud(&self) -> String {
+        self.cloud.to_string()
+    }
+
+    /// Entra ID token scope for this credential's cloud, for use with e.g.
+    /// `azure.identity` when acquiring a bearer token.
+    #[getter]
+    pub fn aad_scope(&self) -> &'static str {
+        self.cloud.aad_scope()
+    }
+
+    /// Entra ID authority host for this credential's cloud.
+    #[getter]
+    pub fn aad_authority(&self) -> &'static str {
+        self.cloud.aad_authority()
+    }
+
+    /// The API key or bearer token with all but its first and last 4 characters
+    /// replaced by `*`, safe to log when checking which secret is in use.
+    ///
+    /// Keys of 8 characters or fewer are masked entirely, since showing 8
+    /// characters of them would reveal the whole key.
+    ///
+    /// Returns:
+    ///     str: The masked key, as long as the original
+    pub fn masked_key(&self) -> String {
+        let key: Vec<char> = self.kind.secret().expose_secret().chars().collect();
+        if key.len() <= 2 * MASKED_KEY_VISIBLE_CHARS {
+            return "*".repeat(key.len());
+        }
+        let hidden = key.len() - 2 * MASKED_KEY_VISIBLE_CHARS;
+        key[..MASKED_KEY_VISIBLE_CHARS]
+            .iter()
+            .chain(std::iter::repeat_n(&'*', hidden))
+            .chain(&key[key.len() - MASKED_KEY_VISIBLE_CHARS..])
+            .collect()
+    }
+}
+
+impl Credentials {
+    /// Normalizes `endpoint` and checks it against `cloud`, shared by the
+    /// API-key and bearer-token constructors.
+    fn with_kind(
+        endpoint: String,
+        kind: CredentialKind,
+        expand_resource_name: bool,
+        cloud: Option<String>,
+    ) -> PyResult<Self> {
+        let explicit_cloud = cloud.as_deref().map(AzureCloud::from_str).transpose()?;
+        let cloud = explicit_cloud.unwrap_or_default();
+
+        let normalized = normalize_endpoint(&endpoint, expand_resource_name, cloud.default_domain())
+            .map_err(PyValueError::new_err)?;
+        if normalized != endpoint {
+            debug!(original = endpoint.as_str(), normalized = normalized.as_str(), "Normalized endpoint");
+        }
+
+        if explicit_cloud.is_some()
+            && let Some(suffixes) = cloud.domain_suffixes()
+            && !has_domain_suffix(&normalized, suffixes)
+        {
+            return Err(PyValueError::new_err(format!(
+                "Endpoint '{}' is not a '{}' cloud endpoint (expected a host ending in {})",
+                normalized,
+                cloud,
+                suffixes.join(" or ")
+            )));
+        }
+
+        Ok(Self {
+            kind,
+            endpoint: normalized,
+            cloud,
+            weight: 1,
+        })
+    }
+}
+
+/// Reads `<prefix>_<name>` (or `name` for an empty prefix), rejecting unset
+/// and empty values.
+fn read_env_var(prefix: &str, name: &str) -> PyResult<String> {
+    let var = match prefix.trim_end_matches('_') {
+        "" => name.to_string(),
+        prefix => format!("{}_{}", prefix, name),
+    };
+    match std::env::var(&var) {
+        Ok(value) if !value.trim().is_empty() => Ok(value),
+        Ok(_) => Err(PyValueError::new_err(format!("Environment variable {} is empty", var))),
+        Err(std::env::VarError::NotPresent) => {
+            Err(PyValueError::new_err(format!("Environment variable {} is not set", var)))
+        }
+        Err(std::env::VarError::NotUnicode(_)) => {
+            Err(PyValueError::new_err(format!("Environment variable {} is not valid Unicode", var)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_weight(weight: u32) -> PyResult<Credentials> {
+        Credentials::new("https://a.example.com".to_string(), Some("key".to_string()), false, None, None, weight)
+    }
+
+    #[test]
+    fn weights_must_be_between_one_and_the_maximum() {
+        assert_eq!(with_weight(1).unwrap().weight, 1);
+        assert_eq!(with_weight(MAX_WEIGHT).unwrap().weight, MAX_WEIGHT);
+        Python::initialize();
+        for weight in [0, MAX_WEIGHT + 1] {
+            let err = with_weight(weight).err().expect("expected an error");
+            Python::attach(|py| assert!(err.value(py).to_string().contains("weight must be between 1 and 10000")));
         }
     }
 }
@@ -0,0 +1,109 @@
+use std::path::Path;
+
+use bytes::Bytes;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::utils::{
+    SUPPORTED_CONTENT_TYPES, UNSUPPORTED_CONTENT_TYPE, get_content_type, sniff_content_type,
+};
+
+/// Bytes requested per `read()` call when draining a Python file-like object.
+const READ_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// An in-memory document ready to be uploaded.
+#[derive(Clone, Debug)]
+pub struct DocumentBytes {
+    /// Name used in logs and error messages (e.g. a file or archive entry name).
+    pub name: String,
+    /// Reference-counted, so copies kept for credential failover share the buffer.
+    pub data: Bytes,
+    pub content_type: String,
+}
+
+impl DocumentBytes {
+    /// Wraps bytes passed in from Python. The content type is `content_type`
+    /// when given, else taken from the name's extension, else sniffed from the
+    /// data's magic bytes. Unnamed documents are called `document-{index}`.
+    pub fn from_bytes(
+        name: Option<String>,
+        data: Vec<u8>,
+        content_type: Option<String>,
+        index: usize,
+    ) -> Self {
+        let content_type = content_type.unwrap_or_else(|| {
+            let by_name = name.as_deref().map_or(UNSUPPORTED_CONTENT_TYPE, get_content_type);
+            if by_name == UNSUPPORTED_CONTENT_TYPE {
+                sniff_content_type(&data).to_string()
+            } else {
+                by_name.to_string()
+            }
+        });
+        Self {
+            name: name.unwrap_or_else(|| format!("document-{}", index)),
+            data: data.into(),
+            content_type,
+        }
+    }
+
+    /// Checks a caller-supplied MIME type against the types the service accepts,
+    /// returning it normalized to lowercase. `documents[index]` is named in the error.
+    pub fn validate_content_type(content_type: &str, index: usize) -> PyResult<String> {
+        let normalized = content_type.trim().to_ascii_lowercase();
+        if SUPPORTED_CONTENT_TYPES.contains(&normalized.as_str()) {
+            return Ok(normalized);
+        }
+        Err(PyValueError::new_err(format!(
+            "documents[{}] has unsupported content type '{}'. Expected one of: {}",
+            index,
+            content_type,
+            SUPPORTED_CONTENT_TYPES.join(", ")
+        )))
+    }
+
+    /// Drains a Python object with a binary `read(size)` method, such as an open
+    /// file, a `zipfile.ZipExtFile` or a streaming HTTP body. The stream is read
+    /// once, from its current position to the end. Its `name` attribute, when
+    /// present, picks the content type; `index` names it otherwise.
+    pub fn from_file_object(file: &Bound<'_, PyAny>, index: usize) -> PyResult<Self> {
+        if !file.hasattr("read")? {
+            return Err(PyTypeError::new_err(format!(
+                "files[{}] has no read() method and is not a PIL image or image array (got {})",
+                index,
+                file.get_type().name()?
+            )));
+        }
+
+        let mut data = Vec::new();
+        loop {
+            let chunk = file.call_method1("read", (READ_CHUNK_SIZE,))?;
+            let bytes: &[u8] = chunk.extract().map_err(|_| {
+                PyTypeError::new_err(format!(
+                    "files[{}].read() must return bytes; open the file in binary mode",
+                    index
+                ))
+            })?;
+            if bytes.is_empty() {
+                break;
+            }
+            data.extend_from_slice(bytes);
+        }
+
+        let name = file
+            .getattr("name")
+            .ok()
+            .and_then(|name| name.extract::<String>().ok())
+            .map(|name| {
+                Path::new(&name)
+                    .file_name()
+                    .map_or(name.clone(), |base| base.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| format!("document-{}", index));
+
+        Ok(Self {
+            content_type: get_content_type(&name).to_string(),
+            name,
+            data: data.into(),
+        })
+    }
+}
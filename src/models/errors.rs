@@ -0,0 +1,165 @@
+use std::fmt;
+
+use pyo3::exceptions::PyBaseException;
+use pyo3::prelude::*;
+use reqwest::StatusCode;
+
+use crate::models::exceptions;
+
+/// Why a document in a batch failed. Mapped onto the Python exception
+/// hierarchy by [`DocumentAnalysisError::into_exception`].
+#[derive(Debug)]
+pub enum DocumentAnalysisError {
+    /// The document's task panicked.
+    TaskPanic(String),
+    /// The client was closed while the document was in flight.
+    Cancelled(String),
+    /// The analyze request or a status poll got an error status.
+    HttpError { status_code: u16, error_code: Option<String>, message: String },
+    /// The operation at `operation_location` reported status `failed`.
+    AnalysisFailed {
+        error_code: Option<String>,
+        message: String,
+        operation_location: String,
+    },
+    /// The document or batch deadline passed before the analysis finished.
+    PollingTimeout(String),
+    /// A status response or the finished result could not be parsed.
+    DeserializationError(String),
+    /// A local file could not be read.
+    FileReadError { path: String, message: String },
+    /// The document failed local validation and was not submitted, e.g. a
+    /// truncated TIFF.
+    InvalidDocument { source: String, message: String },
+    /// Anything else, e.g. connection failures or a missing header.
+    Other(String),
+}
+
+impl DocumentAnalysisError {
+    /// Recovers the failure from an analyze error: either a
+    /// `DocumentAnalysisError` raised on purpose, or a `reqwest` error carrying
+    /// an HTTP status.
+    pub fn from_error(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<DocumentAnalysisError>() {
+            Ok(failure) => return failure,
+            Err(err) => err,
+        };
+        match err.downcast_ref::<reqwest::Error>().and_then(reqwest::Error::status) {
+            Some(status) => DocumentAnalysisError::HttpError {
+                status_code: status.as_u16(),
+                error_code: None,
+                message: format!("API Error: {}", err),
+            },
+            None => DocumentAnalysisError::Other(format!("API Error: {}", err)),
+        }
+    }
+
+    /// The value of `AnalysisError.kind` for this failure.
+    fn kind(&self) -> &'static str {
+        match self {
+            DocumentAnalysisError::TaskPanic(_) => "panic",
+            DocumentAnalysisError::Cancelled(_) => "cancelled",
+            DocumentAnalysisError::HttpError { status_code, .. } => {
+                match StatusCode::from_u16(*status_code) {
+                    Ok(StatusCode::TOO_MANY_REQUESTS) => "rate_limit",
+                    Ok(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => "authentication",
+                    Ok(status) if status.is_client_error() => "invalid_request",
+                    _ => "http",
+                }
+            }
+            DocumentAnalysisError::AnalysisFailed { .. } => "analysis_failed",
+            DocumentAnalysisError::PollingTimeout(_) => "timeout",
+            DocumentAnalysisError::DeserializationError(_) => "deserialization",
+            DocumentAnalysisError::FileReadError { .. } => "file_read",
+            DocumentAnalysisError::InvalidDocument { .. } => "invalid_document",
+            DocumentAnalysisError::Other(_) => "error",
+        }
+    }
+
+    /// Builds the Python exception for a batch result slot. `source` is set
+    /// later, when results are tagged with their inputs.
+    pub fn into_exception(self, py: Python) -> PyResult<Py<PyAny>> {
+        let kind = self.kind();
+        let message = self.to_string();
+        let (error, status_code, error_code, operation_location) = match self {
+            DocumentAnalysisError::HttpError { status_code, error_code, .. } => {
+                let error = match kind {
+                    "rate_limit" => exceptions::RateLimitError::new_err(message),
+                    "authentication" => exceptions::AuthenticationError::new_err(message),
+                    "invalid_request" => exceptions::InvalidRequestError::new_err(message),
+                    _ => exceptions::DocumentAnalysisError::new_err(message),
+                };
+                (error, Some(status_code), error_code, None)
+            }
+            DocumentAnalysisError::AnalysisFailed { error_code, operation_location, .. } => (
+                exceptions::AnalysisFailedError::new_err(message),
+                None,
+                error_code,
+                Some(operation_location),
+            ),
+            DocumentAnalysisError::PollingTimeout(_) => {
+                (exceptions::PollTimeoutError::new_err(message), None, None, None)
+            }
+            DocumentAnalysisError::InvalidDocument { .. } => {
+                (exceptions::InvalidRequestError::new_err(message), None, None, None)
+            }
+            DocumentAnalysisError::TaskPanic(_)
+            | DocumentAnalysisError::Cancelled(_)
+            | DocumentAnalysisError::DeserializationError(_)
+            | DocumentAnalysisError::FileReadError { .. }
+            | DocumentAnalysisError::Other(_) => {
+                (exceptions::DocumentAnalysisError::new_err(message), None, None, None)
+            }
+        };
+        let error = error.into_value(py);
+        set_details(error.bind(py), kind, status_code, error_code, operation_location)?;
+        Ok(error.into_any())
+    }
+}
+
+impl fmt::Display for DocumentAnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocumentAnalysisError::TaskPanic(message) => write!(f, "Task panicked: {}", message),
+            DocumentAnalysisError::DeserializationError(message) => {
+                write!(f, "Deserialization Error: {}", message)
+            }
+            DocumentAnalysisError::FileReadError { path, message } => {
+                write!(f, "Failed to read file {}: {}", path, message)
+            }
+            DocumentAnalysisError::InvalidDocument { source, message } => {
+                write!(f, "Invalid document {}: {}", source, message)
+            }
+            DocumentAnalysisError::HttpError { message, .. }
+            | DocumentAnalysisError::AnalysisFailed { message, .. }
+            | DocumentAnalysisError::PollingTimeout(message)
+            | DocumentAnalysisError::Cancelled(message)
+            | DocumentAnalysisError::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for DocumentAnalysisError {}
+
+/// Builds a `DocumentAnalysisError` instance for a batch result slot that
+/// failed before reaching the service.
+pub fn analysis_error(py: Python, kind: &str, message: String) -> PyResult<Py<PyAny>> {
+    let error = exceptions::DocumentAnalysisError::new_err(message).into_value(py);
+    set_details(error.bind(py), kind, None, None, None)?;
+    Ok(error.into_any())
+}
+
+fn set_details(
+    error: &Bound<'_, PyBaseException>,
+    kind: &str,
+    status_code: Option<u16>,
+    error_code: Option<String>,
+    operation_location: Option<String>,
+) -> PyResult<()> {
+    error.setattr("kind", kind)?;
+    error.setattr("status_code", status_code)?;
+    error.setattr("error_code", error_code)?;
+    error.setattr("source", None::<String>)?;
+    error.setattr("operation_location", operation_location)?;
+    Ok(())
+}
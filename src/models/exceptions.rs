@@ -0,0 +1,46 @@
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+
+create_exception!(
+    rusty_di_runner,
+    AnalysisError,
+    PyException,
+    "A document in a batch could not be analyzed. `kind` says why."
+);
+create_exception!(
+    rusty_di_runner,
+    DocumentAnalysisError,
+    AnalysisError,
+    "Base class of the typed batch failures. Carries `status_code`, `error_code`, \
+     `source` and `operation_location`, each None when not known."
+);
+create_exception!(
+    rusty_di_runner,
+    RateLimitError,
+    DocumentAnalysisError,
+    "The service throttled the document (HTTP 429) and retries were exhausted."
+);
+create_exception!(
+    rusty_di_runner,
+    AuthenticationError,
+    DocumentAnalysisError,
+    "The service rejected the credential (HTTP 401 or 403)."
+);
+create_exception!(
+    rusty_di_runner,
+    InvalidRequestError,
+    DocumentAnalysisError,
+    "The service rejected the request (any other HTTP 4xx), or the document failed local validation."
+);
+create_exception!(
+    rusty_di_runner,
+    AnalysisFailedError,
+    DocumentAnalysisError,
+    "The analyze operation finished with status 'failed'."
+);
+create_exception!(
+    rusty_di_runner,
+    PollTimeoutError,
+    DocumentAnalysisError,
+    "The document or batch timeout passed before the analysis finished."
+);
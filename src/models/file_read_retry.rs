@@ -0,0 +1,34 @@
+use std::{io, time::Duration};
+
+/// Retries for reading local documents, for network mounts that fail transiently.
+#[derive(Clone, Copy, Debug)]
+pub struct FileReadRetry {
+    /// Total read attempts per file, including the first.
+    pub attempts: u32,
+    /// Delay between attempts.
+    pub delay: Duration,
+}
+
+impl FileReadRetry {
+    /// Whether `err` may succeed on a later attempt. Missing files and
+    /// permission errors never do.
+    pub fn is_retryable(err: &io::Error) -> bool {
+        matches!(err.kind(), io::ErrorKind::Interrupted | io::ErrorKind::TimedOut)
+            || err.raw_os_error() == Some(EIO)
+    }
+}
+
+impl Default for FileReadRetry {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            delay: Duration::from_millis(500),
+        }
+    }
+}
+
+/// OS error code for a low-level I/O failure (`EIO` / `ERROR_IO_DEVICE`).
+#[cfg(windows)]
+const EIO: i32 = 1117;
+#[cfg(not(windows))]
+const EIO: i32 = 5;
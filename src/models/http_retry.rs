@@ -0,0 +1,115 @@
+use std::{collections::HashMap, time::Duration};
+
+use reqwest::StatusCode;
+
+use crate::utils::random_unit;
+
+/// Fraction of each retry delay that is randomized.
+const RETRY_JITTER: f64 = 0.2;
+
+/// Retries of the analyze request and status polls on throttling (429) and
+/// transient server errors (500, 502, 503, 504).
+#[derive(Clone, Copy, Debug)]
+pub struct HttpRetry {
+    /// Retries per request after the first attempt; 0 disables retrying.
+    pub max_retries: u32,
+    /// Delay before the first retry when the service sends no `Retry-After`.
+    /// Doubles with each further retry.
+    pub initial_backoff: Duration,
+    /// Cap on the backoff delay.
+    pub max_backoff: Duration,
+}
+
+impl HttpRetry {
+    pub fn is_retryable(status: StatusCode) -> bool {
+        matches!(status.as_u16(), 429 | 500 | 502 | 503 | 504)
+    }
+
+    /// Delay before retry number `retry` (1-based), with jitter, never more than
+    /// `max_backoff`.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let max = self.max_backoff.as_secs_f64();
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let base = (self.initial_backoff.as_secs_f64() * 2f64.powi(exponent)).min(max);
+        let jittered = base * (1.0 + RETRY_JITTER * (2.0 * random_unit() - 1.0));
+        Duration::from_secs_f64(jittered.clamp(0.0, max))
+    }
+}
+
+impl Default for HttpRetry {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Per-call changes to the client's [`HttpRetry`], from a batch method's
+/// `max_retries`/`initial_backoff_ms` arguments and its `retry_policy` dict.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RetryOverrides {
+    pub max_retries: Option<u32>,
+    pub initial_backoff: Option<Duration>,
+    pub max_backoff: Option<Duration>,
+}
+
+impl RetryOverrides {
+    /// Reads a `retry_policy` dict with any of `max_retries`,
+    /// `initial_backoff_ms` and `max_backoff_ms`, rejecting other keys and
+    /// negative values.
+    pub fn from_policy(policy: &HashMap<String, i64>) -> Result<Self, String> {
+        let mut overrides = Self::default();
+        for (key, &value) in policy {
+            let invalid = || format!("retry_policy['{}'] must be a non-negative integer, got {}", key, value);
+            let value = u64::try_from(value).map_err(|_| invalid())?;
+            match key.as_str() {
+                "max_retries" => overrides.max_retries = Some(u32::try_from(value).map_err(|_| invalid())?),
+                "initial_backoff_ms" => overrides.initial_backoff = Some(Duration::from_millis(value)),
+                "max_backoff_ms" => overrides.max_backoff = Some(Duration::from_millis(value)),
+                other => {
+                    return Err(format!(
+                        "Unknown retry_policy key '{}'. Expected 'max_retries', 'initial_backoff_ms' or 'max_backoff_ms'.",
+                        other
+                    ));
+                }
+            }
+        }
+        Ok(overrides)
+    }
+
+    /// Fills the settings not made here from `fallback`. A setting made in both
+    /// is an error, naming it.
+    pub fn merge(self, fallback: Self) -> Result<Self, &'static str> {
+        fn pick<T>(name: &'static str, first: Option<T>, second: Option<T>) -> Result<Option<T>, &'static str> {
+            match (first, second) {
+                (Some(_), Some(_)) => Err(name),
+                (first, second) => Ok(first.or(second)),
+            }
+        }
+        Ok(Self {
+            max_retries: pick("max_retries", self.max_retries, fallback.max_retries)?,
+            initial_backoff: pick("initial_backoff_ms", self.initial_backoff, fallback.initial_backoff)?,
+            max_backoff: pick("max_backoff_ms", self.max_backoff, fallback.max_backoff)?,
+        })
+    }
+
+    /// `base` with these overrides applied. Fails if the resulting initial
+    /// backoff exceeds the cap.
+    pub fn apply(&self, base: HttpRetry) -> Result<HttpRetry, String> {
+        let retry = HttpRetry {
+            max_retries: self.max_retries.unwrap_or(base.max_retries),
+            initial_backoff: self.initial_backoff.unwrap_or(base.initial_backoff),
+            max_backoff: self.max_backoff.unwrap_or(base.max_backoff),
+        };
+        if retry.initial_backoff > retry.max_backoff {
+            return Err(format!(
+                "initial_backoff_ms ({}) cannot exceed max_backoff_ms ({})",
+                retry.initial_backoff.as_millis(),
+                retry.max_backoff.as_millis()
+            ));
+        }
+        Ok(retry)
+    }
+}
@@ -0,0 +1,141 @@
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::models::DocumentBytes;
+use crate::models::integrations::import_optional;
+
+/// How in-memory images are encoded before upload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ImageEncoding {
+    #[default]
+    Png,
+    Jpeg { quality: u8 },
+}
+
+impl ImageEncoding {
+    /// `jpeg_quality` is taken as any Python int so out-of-range values raise
+    /// `ValueError` rather than an `OverflowError` from the conversion.
+    pub fn new(format: Option<&str>, jpeg_quality: i64) -> PyResult<Self> {
+        match format.map(|s| s.trim().to_lowercase()).as_deref() {
+            None | Some("png") => Ok(ImageEncoding::Png),
+            Some("jpeg") | Some("jpg") => {
+                if !(1..=100).contains(&jpeg_quality) {
+                    return Err(PyValueError::new_err(format!(
+                        "jpeg_quality must be between 1 and 100, got {}",
+                        jpeg_quality
+                    )));
+                }
+                Ok(ImageEncoding::Jpeg { quality: jpeg_quality as u8 })
+            }
+            Some(_) => Err(PyValueError::new_err(format!(
+                "Invalid image format: '{}'. Expected 'png' or 'jpeg'.",
+                format.unwrap_or_default()
+            ))),
+        }
+    }
+}
+
+/// Encodes `obj` if it is a PIL image or an image array (anything with numpy-style
+/// `shape` and `dtype`), returning `None` for every other object.
+pub fn encode_image(
+    obj: &Bound<'_, PyAny>,
+    index: usize,
+    encoding: ImageEncoding,
+) -> PyResult<Option<DocumentBytes>> {
+    let image = if is_pil_image(obj)? {
+        obj.clone()
+    } else if obj.hasattr("shape")? && obj.hasattr("dtype")? {
+        array_to_image(obj, index)?
+    } else {
+        return Ok(None);
+    };
+    save_image(&image, index, encoding).map(Some)
+}
+
+fn is_pil_image(obj: &Bound<'_, PyAny>) -> PyResult<bool> {
+    Ok(obj.hasattr("save")? && obj.hasattr("mode")? && obj.hasattr("size")?)
+}
+
+/// Validates an `(H, W)`, `(H, W, 3)` or `(H, W, 4)` uint8 array and wraps it in
+/// a PIL image.
+fn array_to_image<'py>(array: &Bound<'py, PyAny>, index: usize) -> PyResult<Bound<'py, PyAny>> {
+    let dtype = array.getattr("dtype")?.str()?.to_string();
+    if dtype != "uint8" {
+        return Err(PyTypeError::new_err(format!(
+            "files[{}] has dtype {}; only uint8 image arrays are supported \
+             (scale to 0-255 and use .astype('uint8'))",
+            index, dtype
+        )));
+    }
+
+    let shape: Vec<usize> = array.getattr("shape")?.extract()?;
+    let valid = match shape.as_slice() {
+        [height, width] => *height > 0 && *width > 0,
+        [height, width, 3 | 4] => *height > 0 && *width > 0,
+        _ => false,
+    };
+    if !valid {
+        let dims: Vec<String> = shape.iter().map(usize::to_string).collect();
+        return Err(PyValueError::new_err(format!(
+            "files[{}] has shape ({}); expected a non-empty (H, W) grayscale, \
+             (H, W, 3) RGB or (H, W, 4) RGBA array",
+            index,
+            dims.join(", ")
+        )));
+    }
+
+    let pil = import_optional(array.py(), "PIL.Image", "pillow", "Encoding image arrays")?;
+    pil.call_method1("fromarray", (array,))
+}
+
+fn save_image(image: &Bound<'_, PyAny>, index: usize, encoding: ImageEncoding) -> PyResult<DocumentBytes> {
+    let py = image.py();
+    let buffer = py.import("io")?.call_method0("BytesIO")?;
+    let kwargs = PyDict::new(py);
+
+    let (image, extension, content_type) = match encoding {
+        ImageEncoding::Png => {
+            kwargs.set_item("format", "PNG")?;
+            (image.clone(), "png", "image/png")
+        }
+        ImageEncoding::Jpeg { quality } => {
+            kwargs.set_item("format", "JPEG")?;
+            kwargs.set_item("quality", quality)?;
+            // JPEG has no alpha channel or palette.
+            let mode: String = image.getattr("mode")?.extract()?;
+            let image = if matches!(mode.as_str(), "RGB" | "L") {
+                image.clone()
+            } else {
+                image.call_method1("convert", ("RGB",))?
+            };
+            (image, "jpg", "image/jpeg")
+        }
+    };
+    image.call_method("save", (&buffer,), Some(&kwargs))?;
+
+    let encoded = buffer.call_method0("getvalue")?;
+    Ok(DocumentBytes {
+        name: format!("image-{}.{}", index, extension),
+        data: encoded.extract::<&[u8]>()?.to_vec().into(),
+        content_type: content_type.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jpeg_quality_out_of_range_is_a_value_error() {
+        Python::initialize();
+        Python::attach(|py| {
+            for quality in [0, 101, 256, -1, i64::MAX] {
+                let err = ImageEncoding::new(Some("jpeg"), quality).unwrap_err();
+                assert!(err.is_instance_of::<PyValueError>(py), "{}", err);
+            }
+        });
+        assert_eq!(ImageEncoding::new(Some("JPG"), 100).unwrap(), ImageEncoding::Jpeg { quality: 100 });
+        assert_eq!(ImageEncoding::new(None, 1_000).unwrap(), ImageEncoding::Png);
+    }
+}
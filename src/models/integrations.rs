@@ -0,0 +1,231 @@
+//! Bridges from `AnalyzeResult` to third-party Python libraries. The libraries
+//! are imported lazily so none of them is a hard dependency.
+
+use pyo3::exceptions::PyImportError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::models::analyze_result::{AnalyzeResult, page_text};
+use crate::utils::geometry::polygon_bounds;
+
+/// Imports an optional Python dependency, pointing at the pip package on failure.
+pub(crate) fn import_optional<'py>(
+    py: Python<'py>,
+    module: &str,
+    package: &str,
+    feature: &str,
+) -> PyResult<Bound<'py, PyModule>> {
+    py.import(module).map_err(|_| {
+        PyImportError::new_err(format!(
+            "{} requires the '{}' package. Install it with `pip install {}`.",
+            feature, package, package
+        ))
+    })
+}
+
+#[pymethods]
+impl AnalyzeResult {
+    /// Build a spaCy `Doc` whose tokens are aligned with the OCR words.
+    ///
+    /// Every `DocumentWord` becomes one token. Text between words that is not a
+    /// single space (line breaks, markdown, selection marks) becomes its own token,
+    /// so `doc.text == result.content` and token character offsets match the
+    /// words' spans.
+    ///
+    /// Args:
+    ///     vocab (spacy.vocab.Vocab | None): Vocabulary to build the Doc with, e.g.
+    ///         `nlp.vocab` to run a pipeline on the result afterwards. Defaults to a
+    ///         new empty vocabulary.
+    ///
+    /// Returns:
+    ///     spacy.tokens.Doc: The tokenized content
+    ///
+    /// Raises:
+    ///     ImportError: If spaCy is not installed
+    ///
+    /// Example:
+    ///     >>> nlp = spacy.load("en_core_web_sm")
+    ///     >>> doc = nlp(result.to_spacy_doc(nlp.vocab))
+    ///     >>> print([(ent.text, ent.label_) for ent in doc.ents])
+    #[pyo3(signature = (vocab=None))]
+    pub fn to_spacy_doc(&self, py: Python, vocab: Option<Bound<'_, PyAny>>) -> PyResult<Py<PyAny>> {
+        let spacy = import_optional(py, "spacy", "spacy", "to_spacy_doc")?;
+        let vocab = match vocab {
+            Some(vocab) => vocab,
+            None => spacy.getattr("vocab")?.getattr("Vocab")?.call0()?,
+        };
+
+        let chars: Vec<char> = self.content.chars().collect();
+        let mut word_spans: Vec<(usize, usize)> = self
+            .pages
+            .iter()
+            .flat_map(|page| page.words.iter().flatten())
+            .map(|word| (word.span.offset, word.span.length))
+            .collect();
+        word_spans.sort_unstable();
+        let (words, spaces) = aligned_tokens(&chars, &word_spans);
+
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("words", words)?;
+        kwargs.set_item("spaces", spaces)?;
+        let doc = spacy
+            .getattr("tokens")?
+            .getattr("Doc")?
+            .call((vocab,), Some(&kwargs))?;
+        Ok(doc.unbind())
+    }
+
+    /// Build a Hugging Face `datasets.DatasetDict` with a word-level and a page-level split.
+    ///
+    /// The `words` split has one row per OCR word with columns `page_number`,
+    /// `word`, `confidence`, `x_min`, `y_min`, `x_max` and `y_max`; the
+    /// coordinates are the word polygon's bounding box in the page's unit (None
+    /// when the word has no polygon). The `pages` split has one row per page with
+    /// columns `page_number` and `content`, the text of that page, so page text
+    /// is stored once and joined to words on `page_number`.
+    ///
+    /// Returns:
+    ///     datasets.DatasetDict: The `words` and `pages` datasets
+    ///
+    /// Raises:
+    ///     ImportError: If the `datasets` package is not installed
+    pub fn to_hf_dataset_dict(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let datasets = import_optional(py, "datasets", "datasets", "to_hf_dataset_dict")?;
+        let chars: Vec<char> = self.content.chars().collect();
+
+        let mut word_pages = Vec::new();
+        let mut words = Vec::new();
+        let mut confidences = Vec::new();
+        let (mut x_min, mut y_min, mut x_max, mut y_max) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        let mut page_numbers = Vec::new();
+        let mut contents = Vec::new();
+        for page in &self.pages {
+            page_numbers.push(page.page_number);
+            contents.push(page_text(&chars, page));
+            for word in page.words.iter().flatten() {
+                let word_bounds = word.polygon.as_deref().and_then(polygon_bounds);
+                word_pages.push(page.page_number);
+                words.push(word.content.as_str());
+                confidences.push(word.confidence);
+                x_min.push(word_bounds.map(|bounds| bounds.0));
+                y_min.push(word_bounds.map(|bounds| bounds.1));
+                x_max.push(word_bounds.map(|bounds| bounds.2));
+                y_max.push(word_bounds.map(|bounds| bounds.3));
+            }
+        }
+
+        let word_columns = PyDict::new(py);
+        word_columns.set_item("page_number", word_pages)?;
+        word_columns.set_item("word", words)?;
+        word_columns.set_item("confidence", confidences)?;
+        word_columns.set_item("x_min", x_min)?;
+        word_columns.set_item("y_min", y_min)?;
+        word_columns.set_item("x_max", x_max)?;
+        word_columns.set_item("y_max", y_max)?;
+
+        let page_columns = PyDict::new(py);
+        page_columns.set_item("page_number", page_numbers)?;
+        page_columns.set_item("content", contents)?;
+
+        let dataset = datasets.getattr("Dataset")?;
+        let splits = PyDict::new(py);
+        splits.set_item("words", dataset.call_method1("from_dict", (word_columns,))?)?;
+        splits.set_item("pages", dataset.call_method1("from_dict", (page_columns,))?)?;
+        let dataset_dict = datasets.getattr("DatasetDict")?.call1((splits,))?;
+        Ok(dataset_dict.unbind())
+    }
+
+    /// Build a `pyarrow.Table` with one row per OCR word.
+    ///
+    /// Columns are `page_number`, `content` (the word), `confidence`, `offset`
+    /// and `length` (the word's span in `content`), and the four polygon corners
+    /// `x0`, `y0` ... `x3`, `y3` in the page's unit. Corners are null when the
+    /// word has no four-point polygon.
+    ///
+    /// Returns:
+    ///     pyarrow.Table: The word-level table
+    ///
+    /// Raises:
+    ///     ImportError: If the `pyarrow` package is not installed
+    pub fn to_arrow_table(&self, py: Python) -> PyResult<Py<PyAny>> {
+        const CORNER_COLUMNS: [&str; 8] = ["x0", "y0", "x1", "y1", "x2", "y2", "x3", "y3"];
+        let pyarrow = import_optional(py, "pyarrow", "pyarrow", "to_arrow_table")?;
+
+        let mut page_numbers = Vec::new();
+        let mut contents = Vec::new();
+        let mut confidences = Vec::new();
+        let mut offsets = Vec::new();
+        let mut lengths = Vec::new();
+        let mut corners: [Vec<Option<f32>>; 8] = Default::default();
+        for page in &self.pages {
+            for word in page.words.iter().flatten() {
+                page_numbers.push(page.page_number);
+                contents.push(word.content.as_str());
+                confidences.push(word.confidence);
+                offsets.push(word.span.offset);
+                lengths.push(word.span.length);
+                let polygon = word.polygon.as_deref().filter(|polygon| polygon.len() >= 8);
+                for (index, column) in corners.iter_mut().enumerate() {
+                    column.push(polygon.map(|polygon| polygon[index]));
+                }
+            }
+        }
+
+        let columns = PyDict::new(py);
+        columns.set_item("page_number", page_numbers)?;
+        columns.set_item("content", contents)?;
+        columns.set_item("confidence", confidences)?;
+        columns.set_item("offset", offsets)?;
+        columns.set_item("length", lengths)?;
+        for (name, values) in CORNER_COLUMNS.into_iter().zip(corners) {
+            columns.set_item(name, values)?;
+        }
+
+        let table = pyarrow.call_method1("table", (columns,))?;
+        Ok(table.unbind())
+    }
+}
+
+/// Splits `chars` into spaCy `words`/`spaces` lists with one token per word span.
+///
+/// A single space after a token becomes that token's trailing-space flag; any
+/// other text between words is emitted as a separate token so the tokens and
+/// flags reproduce `chars` exactly.
+fn aligned_tokens(chars: &[char], word_spans: &[(usize, usize)]) -> (Vec<String>, Vec<bool>) {
+    let mut words: Vec<String> = Vec::new();
+    let mut spaces: Vec<bool> = Vec::new();
+    let mut cursor = 0;
+
+    for &(offset, length) in word_spans {
+        let end = (offset + length).min(chars.len());
+        if offset < cursor || offset >= end {
+            continue;
+        }
+        push_gap(&chars[cursor..offset], &mut words, &mut spaces);
+        words.push(chars[offset..end].iter().collect());
+        spaces.push(false);
+        cursor = end;
+    }
+    push_gap(&chars[cursor.min(chars.len())..], &mut words, &mut spaces);
+
+    (words, spaces)
+}
+
+/// Emits the text between two words, folding a leading space into the previous token.
+fn push_gap(gap: &[char], words: &mut Vec<String>, spaces: &mut Vec<bool>) {
+    let mut gap = gap;
+    if gap.first() == Some(&' ')
+        && let Some(last) = spaces.last_mut()
+        && !*last
+    {
+        *last = true;
+        gap = &gap[1..];
+    }
+    if gap.is_empty() {
+        return;
+    }
+    let trailing_space = gap.len() > 1 && gap.last() == Some(&' ');
+    let token = if trailing_space { &gap[..gap.len() - 1] } else { gap };
+    words.push(token.iter().collect());
+    spaces.push(trailing_space);
+}
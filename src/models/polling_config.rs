@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use crate::models::HttpRetry;
+use crate::utils::random_unit;
+
+/// Controls how often the analyze operation is polled for completion.
+///
+/// Without a `Retry-After` hint the delay starts at `interval` and grows by
+/// `multiplier` after every poll up to `max_interval`, so long analyses are not
+/// polled (and billed against quota) every second.
+#[derive(Clone, Copy, Debug)]
+pub struct PollingConfig {
+    /// Delay before the first poll, where the backoff starts.
+    pub interval: Duration,
+    /// Cap on the backoff delay, jitter included.
+    pub max_interval: Duration,
+    /// Factor applied to the delay after each poll.
+    pub multiplier: f64,
+    /// Fraction of each delay that is randomized (0.1 spreads it over ±10%), so
+    /// documents submitted together don't poll in lockstep.
+    pub jitter: f64,
+    /// Upper bound applied to `Retry-After` values sent by the service.
+    pub max_retry_after: Duration,
+    /// Largest status response body accepted, in bytes. Larger results fail the
+    /// document instead of exhausting memory. `None` disables the guard.
+    pub max_result_bytes: Option<usize>,
+    /// Retries of throttled or failed submissions and polls.
+    pub retry: HttpRetry,
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+            max_interval: Duration::from_secs(10),
+            multiplier: 1.5,
+            jitter: 0.1,
+            max_retry_after: Duration::from_secs(30),
+            max_result_bytes: None,
+            retry: HttpRetry::default(),
+        }
+    }
+}
+
+impl PollingConfig {
+    /// Delay before poll number `poll` (1-based) when the service sent no
+    /// `Retry-After`: `interval * multiplier^(poll - 1)`, jittered, and never
+    /// more than `max_interval`.
+    pub fn backoff(&self, poll: u32) -> Duration {
+        let max = self.max_interval.as_secs_f64();
+        let exponent = poll.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let base = (self.interval.as_secs_f64() * self.multiplier.powi(exponent)).min(max);
+        let jittered = base * (1.0 + self.jitter * (2.0 * random_unit() - 1.0));
+        Duration::from_secs_f64(jittered.clamp(0.0, max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_to_the_cap_and_never_exceeds_it() {
+        let config = PollingConfig::default();
+        for poll in (1..=60).chain([u32::MAX]) {
+            assert!(config.backoff(poll) <= config.max_interval, "poll {} waited {:?}", poll, config.backoff(poll));
+        }
+        // Without jitter the sequence is exactly interval * multiplier^(poll - 1), capped.
+        let exact = PollingConfig { jitter: 0.0, ..config };
+        let delays: Vec<u128> = (1..=10).map(|poll| exact.backoff(poll).as_millis()).collect();
+        assert_eq!(delays, [500, 750, 1125, 1687, 2531, 3796, 5695, 8542, 10_000, 10_000]);
+    }
+
+    #[test]
+    fn jitter_spreads_polls_within_its_fraction() {
+        let config = PollingConfig { interval: Duration::from_secs(1), ..PollingConfig::default() };
+        let delays: Vec<f64> = (0..200).map(|_| config.backoff(1).as_secs_f64()).collect();
+        assert!(delays.iter().all(|delay| (0.9..=1.1).contains(delay)), "{:?}", delays);
+
+        // Documents polling together should not all wake at the same instant.
+        let (min, max) = delays.iter().fold((f64::MAX, f64::MIN), |(min, max), &d| (min.min(d), max.max(d)));
+        assert!(max - min > 0.1, "delays bunched within {:.3}s", max - min);
+        let mut distinct = delays.clone();
+        distinct.sort_by(f64::total_cmp);
+        distinct.dedup();
+        assert!(distinct.len() > 150);
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde_json::value::RawValue;
+
+use crate::models::AnalyzeResult;
+
+/// Sections of an analyze result that `select` can keep, as
+/// `(Python name, JSON key)`.
+const SELECTABLE_SECTIONS: &[(&str, &str)] = &[
+    ("content", "content"),
+    ("pages", "pages"),
+    ("paragraphs", "paragraphs"),
+    ("tables", "tables"),
+    ("languages", "languages"),
+    ("documents", "documents"),
+    ("key_value_pairs", "keyValuePairs"),
+    ("styles", "styles"),
+    ("figures", "figures"),
+    ("sections", "sections"),
+];
+
+/// Keys every result keeps regardless of `select`.
+const ALWAYS_KEPT: &[&str] = &["apiVersion", "modelId"];
+
+/// The subset of result sections to deserialize, as chosen by `select`.
+pub struct Projection {
+    sections: Vec<&'static (&'static str, &'static str)>,
+}
+
+impl Projection {
+    /// Validates `select` up front, failing on unknown section names.
+    pub fn new(select: &[String]) -> PyResult<Self> {
+        let sections = select
+            .iter()
+            .map(|name| {
+                SELECTABLE_SECTIONS
+                    .iter()
+                    .find(|(python_name, _)| python_name == name)
+                    .ok_or_else(|| {
+                        let valid: Vec<&str> = SELECTABLE_SECTIONS.iter().map(|(name, _)| *name).collect();
+                        PyValueError::new_err(format!(
+                            "Unknown section '{}' in select. Expected any of: {}",
+                            name,
+                            valid.join(", ")
+                        ))
+                    })
+            })
+            .collect::<PyResult<_>>()?;
+        Ok(Self { sections })
+    }
+
+    /// Deserializes only the selected sections of `raw`. Unselected sections are
+    /// skipped as raw text and never parsed into values, and are left out of
+    /// the result's `to_dict`.
+    pub fn parse(&self, raw: &str) -> serde_json::Result<AnalyzeResult> {
+        let sections: HashMap<String, &RawValue> = serde_json::from_str(raw)?;
+        let mut projected = String::from("{");
+        for (key, value) in sections {
+            if !ALWAYS_KEPT.contains(&key.as_str()) && !self.sections.iter().any(|(_, json_key)| *json_key == key) {
+                continue;
+            }
+            if projected.len() > 1 {
+                projected.push(',');
+            }
+            projected.push_str(&serde_json::to_string(&key)?);
+            projected.push(':');
+            projected.push_str(value.get());
+        }
+        projected.push('}');
+        let mut result: AnalyzeResult = serde_json::from_str(&projected)?;
+        result.selected = Some(self.sections.iter().map(|(python_name, _)| *python_name).collect());
+        Ok(result)
+    }
+}
+
+/// The names of the sections `select` can keep that are not in `selected`.
+pub fn unselected_sections(selected: &[&str]) -> impl Iterator<Item = &'static str> {
+    SELECTABLE_SECTIONS
+        .iter()
+        .map(|(python_name, _)| *python_name)
+        .filter(move |python_name| !selected.contains(python_name))
+}
+
+/// Deserializes a raw result, applying `projection` if there is one.
+pub fn parse_result(raw: &str, projection: Option<&Projection>) -> serde_json::Result<AnalyzeResult> {
+    match projection {
+        Some(projection) => projection.parse(raw),
+        None => serde_json::from_str(raw),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::types::PyDict;
+
+    use super::*;
+
+    const RAW: &str = r#"{"apiVersion":"2024-11-30","modelId":"prebuilt-layout","content":"Hi","pages":[],"tables":[]}"#;
+
+    fn dict_keys(result: &AnalyzeResult) -> Vec<String> {
+        Python::initialize();
+        Python::attach(|py| {
+            let dict = result.to_dict(py).unwrap();
+            dict.bind(py).cast::<PyDict>().unwrap().keys().extract().unwrap()
+        })
+    }
+
+    #[test]
+    fn only_projected_results_omit_sections_from_to_dict() {
+        let full = dict_keys(&parse_result(RAW, None).unwrap());
+        for section in ["content", "pages", "tables", "paragraphs", "key_value_pairs", "figures"] {
+            assert!(full.iter().any(|key| key == section), "{} missing from {:?}", section, full);
+        }
+
+        let projection = Projection::new(&["content".to_string()]).unwrap();
+        let projected = dict_keys(&parse_result(RAW, Some(&projection)).unwrap());
+        assert!(projected.iter().any(|key| key == "content"));
+        for (section, _) in SELECTABLE_SECTIONS.iter().filter(|(name, _)| *name != "content") {
+            assert!(!projected.iter().any(|key| key == section), "{} kept in {:?}", section, projected);
+        }
+    }
+}
@@ -1,8 +1,57 @@
-use serde_json::Value;
+use std::fmt;
+
+use serde_json::value::RawValue;
 
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct StatusResponse {
     pub status: String,
+    /// Progress reported by the service for some operations while running.
+    #[serde(rename = "percentCompleted", default, skip_serializing_if = "Option::is_none")]
+    pub percent_completed: Option<u32>,
+    /// Kept as raw JSON so the (potentially huge) result is parsed only once,
+    /// straight into `AnalyzeResult`.
     #[serde(rename = "analyzeResult")]
-    pub result: Option<Value>,
+    pub result: Option<Box<RawValue>>,
+    /// Why the operation failed, when `status` is "failed".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorDetail>,
+}
+
+/// Body of an error response from the service: `{"error": {...}}`.
+#[derive(serde::Deserialize)]
+pub struct ErrorResponse {
+    pub error: ErrorDetail,
+}
+
+/// The service's error object. The top-level code is generic (e.g.
+/// "InvalidRequest"); the specific reason is usually in `innererror`.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct ErrorDetail {
+    #[serde(default)]
+    pub code: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub innererror: Option<Box<ErrorDetail>>,
+}
+
+impl ErrorDetail {
+    /// The most specific error code, e.g. "InvalidContentDimensions".
+    pub fn innermost_code(&self) -> &str {
+        let mut detail = self;
+        while let Some(inner) = &detail.innererror {
+            detail = inner;
+        }
+        &detail.code
+    }
+}
+
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)?;
+        if let Some(inner) = &self.innererror {
+            write!(f, " ({})", inner)?;
+        }
+        Ok(())
+    }
 }
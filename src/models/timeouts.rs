@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+/// Deadlines applied while processing a batch.
+///
+/// The document timeout starts once a document gets its rate-limit permit and
+/// covers submission plus polling. The batch timeout starts when the batch is
+/// launched and also covers time spent waiting for a permit, so a document
+/// never outlives the batch deadline.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timeouts {
+    pub document: Option<Duration>,
+    pub batch: Option<Duration>,
+}
+
+impl Timeouts {
+    pub fn validate(&self) -> Result<(), String> {
+        if let (Some(document), Some(batch)) = (self.document, self.batch)
+            && document > batch
+        {
+            return Err(format!(
+                "Per-document timeout ({}s) cannot exceed the batch timeout ({}s)",
+                document.as_secs_f64(),
+                batch.as_secs_f64()
+            ));
+        }
+        Ok(())
+    }
+}
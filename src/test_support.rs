@@ -0,0 +1,298 @@
+//! Helpers shared by the unit tests: a client with test-friendly defaults and a
+//! minimal HTTP server that answers with canned responses.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use pyo3::PyResult;
+
+use crate::models::{
+    AnalyzeOptions, BatchSettings, Credentials, FileReadRetry, PollingConfig, Timeouts,
+    analysis_client::RustyAnalysisClient,
+};
+
+/// Constructor arguments tests change; everything else uses the defaults,
+/// except that polling is fast so batches against [`MockServer`] finish quickly.
+pub struct ClientOptions {
+    pub timeout_secs: Option<f64>,
+    pub batch_timeout_secs: Option<f64>,
+    pub auth_failure_threshold: u32,
+    pub auth_probe_interval_secs: f64,
+    pub rate_limiter_group: Option<String>,
+    pub rate_limiter_rps: f64,
+    /// True by default so tests may point several credentials at one server.
+    pub allow_duplicate_endpoints: bool,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            timeout_secs: None,
+            batch_timeout_secs: None,
+            auth_failure_threshold: 3,
+            auth_probe_interval_secs: 60.0,
+            rate_limiter_group: None,
+            rate_limiter_rps: 15.0,
+            allow_duplicate_endpoints: true,
+        }
+    }
+}
+
+pub fn credentials(endpoint: &str, weight: u32) -> Credentials {
+    Credentials::new(endpoint.to_string(), Some("test-key".to_string()), false, None, None, weight).unwrap()
+}
+
+pub fn client(credentials: Vec<Credentials>, options: ClientOptions) -> RustyAnalysisClient {
+    try_client(credentials, options).unwrap()
+}
+
+pub fn try_client(credentials: Vec<Credentials>, options: ClientOptions) -> PyResult<RustyAnalysisClient> {
+    RustyAnalysisClient::new(
+        credentials,
+        false,
+        30.0,
+        None,
+        None,
+        10.0,
+        None,
+        0.2,
+        options.timeout_secs,
+        options.batch_timeout_secs,
+        0.01,
+        options.rate_limiter_group,
+        options.rate_limiter_rps,
+        5.0,
+        3,
+        0.5,
+        None,
+        options.allow_duplicate_endpoints,
+        None,
+        "log",
+        0.05,
+        1.5,
+        0.1,
+        options.auth_failure_threshold,
+        options.auth_probe_interval_secs,
+    )
+}
+
+/// Batch settings for `prebuilt-layout` with fast polling and no retry delay.
+pub fn settings(semaphore_size: usize) -> BatchSettings {
+    let mut polling = PollingConfig {
+        interval: Duration::from_millis(10),
+        max_interval: Duration::from_millis(50),
+        ..PollingConfig::default()
+    };
+    polling.retry.initial_backoff = Duration::from_millis(10);
+    BatchSettings {
+        options: AnalyzeOptions {
+            model_id: "prebuilt-layout".to_string(),
+            features: None,
+            output_format: None,
+            pages: None,
+            locale: None,
+            query_fields: None,
+        },
+        semaphore_size,
+        polling,
+        timeouts: Timeouts::default(),
+        file_read: FileReadRetry::default(),
+        priorities: None,
+        checkpoint: None,
+    }
+}
+
+/// A request received by [`MockServer`].
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: String,
+    /// Path and query string, e.g. `/documentintelligence/...?api-version=...`.
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// Base URL of the server, for building `operation-location` headers.
+    pub server: String,
+    pub received_at: Instant,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The `urlSource` of an analyze request made from a URL.
+    pub fn url_source(&self) -> Option<String> {
+        let body: serde_json::Value = serde_json::from_slice(&self.body).ok()?;
+        body["urlSource"].as_str().map(str::to_string)
+    }
+}
+
+/// A canned response, optionally sent after a delay.
+#[derive(Clone, Debug)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub delay: Duration,
+    /// Send the body with `transfer-encoding: chunked` in pieces of this many
+    /// bytes instead of behind a `content-length`.
+    pub chunk_size: Option<usize>,
+}
+
+impl Response {
+    pub fn new(status: u16) -> Self {
+        Self { status, headers: Vec::new(), body: Vec::new(), delay: Duration::ZERO, chunk_size: None }
+    }
+
+    pub fn json(status: u16, body: serde_json::Value) -> Self {
+        Self::new(status)
+            .header("content-type", "application/json")
+            .body(body.to_string().into_bytes())
+    }
+
+    /// The 202 that starts an analyze operation polled at `path` on `request`'s server.
+    pub fn accepted(request: &Request, path: &str) -> Self {
+        Self::new(202).header("operation-location", &format!("{}{}", request.server, path))
+    }
+
+    /// A succeeded status response carrying `result`.
+    pub fn succeeded(result: serde_json::Value) -> Self {
+        Self::json(200, serde_json::json!({ "status": "succeeded", "analyzeResult": result }))
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn body(mut self, body: Vec<u8>) -> Self {
+        self.body = body;
+        self
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn chunked(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+}
+
+type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// An HTTP/1.1 server on a random local port, answering every request with
+/// `handler` on its own thread. Lives until the test process exits.
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    pub fn start(handler: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+        {
+            let (url, requests) = (url.clone(), requests.clone());
+            thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let (url, requests, handler) = (url.clone(), requests.clone(), handler.clone());
+                    thread::spawn(move || serve(stream, &url, &requests, &*handler));
+                }
+            });
+        }
+        Self { url, requests }
+    }
+
+    /// Every request received so far, in arrival order.
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Answers requests on one connection until the client closes it.
+fn serve(stream: TcpStream, url: &str, requests: &Mutex<Vec<Request>>, handler: &Handler) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut stream = stream;
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            return;
+        }
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_string(), value.trim().to_string()));
+            }
+        }
+        let length = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        if reader.read_exact(&mut body).is_err() {
+            return;
+        }
+
+        let request = Request {
+            method: method.to_string(),
+            target: target.to_string(),
+            headers,
+            body,
+            server: url.to_string(),
+            received_at: Instant::now(),
+        };
+        requests.lock().unwrap().push(request.clone());
+        let response = handler(&request);
+        thread::sleep(response.delay);
+
+        let mut head = match response.chunk_size {
+            Some(_) => format!("HTTP/1.1 {} Mock\r\ntransfer-encoding: chunked\r\n", response.status),
+            None => format!("HTTP/1.1 {} Mock\r\ncontent-length: {}\r\n", response.status, response.body.len()),
+        };
+        for (name, value) in &response.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str("\r\n");
+        let body = match response.chunk_size {
+            Some(size) => {
+                let mut body = Vec::new();
+                for chunk in response.body.chunks(size.max(1)) {
+                    body.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+                    body.extend_from_slice(chunk);
+                    body.extend_from_slice(b"\r\n");
+                }
+                body.extend_from_slice(b"0\r\n\r\n");
+                body
+            }
+            None => response.body,
+        };
+        if stream.write_all(head.as_bytes()).is_err() || stream.write_all(&body).is_err() {
+            return;
+        }
+    }
+}
@@ -1,5 +1,15 @@
+pub mod archive;
+pub mod endpoint;
+pub mod geometry;
 pub mod helpers;
 pub mod logger;
 pub mod macros;
+pub mod merge;
+pub mod report;
+pub mod text;
+pub mod tiff;
 
-pub use helpers::get_content_type;
+pub use helpers::{
+    SUPPORTED_CONTENT_TYPES, UNSUPPORTED_CONTENT_TYPE, get_content_type, long_path, parse_retry_after,
+    random_unit, sniff_content_type,
+};
@@ -0,0 +1,89 @@
+use std::{collections::HashSet, fs::File, io::Read, path::Path};
+
+use zip::ZipArchive;
+
+use crate::models::DocumentBytes;
+use crate::utils::{UNSUPPORTED_CONTENT_TYPE, get_content_type, long_path};
+
+/// Largest document the service accepts; bigger entries are rejected while they
+/// are decompressed, whatever size their header declares.
+const MAX_ENTRY_BYTES: u64 = 500 * 1024 * 1024;
+
+/// Reads every supported document in a ZIP archive into memory.
+///
+/// Directories and entries whose extension has no known content type are skipped.
+/// Entries keep their full path inside the archive as their name, so an archive
+/// holding the same name twice is rejected rather than having results collide.
+pub fn read_zip_documents(zip_path: &Path) -> anyhow::Result<Vec<DocumentBytes>> {
+    let file = File::open(long_path(zip_path))
+        .map_err(|e| anyhow::anyhow!("Failed to open archive {}: {}", zip_path.display(), e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| anyhow::anyhow!("Failed to read archive {}: {}", zip_path.display(), e))?;
+
+    let mut documents = Vec::new();
+    let mut names = HashSet::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if !entry.is_file() {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let content_type = get_content_type(&name);
+        if content_type == UNSUPPORTED_CONTENT_TYPE {
+            continue;
+        }
+
+        if !names.insert(name.clone()) {
+            anyhow::bail!("Archive {} contains {} more than once", zip_path.display(), name);
+        }
+
+        let mut data = Vec::new();
+        (&mut entry)
+            .take(MAX_ENTRY_BYTES + 1)
+            .read_to_end(&mut data)
+            .map_err(|e| anyhow::anyhow!("Failed to extract {} from {}: {}", name, zip_path.display(), e))?;
+        if data.len() as u64 > MAX_ENTRY_BYTES {
+            anyhow::bail!(
+                "{} in {} exceeds the {} MB document limit",
+                name,
+                zip_path.display(),
+                MAX_ENTRY_BYTES / (1024 * 1024)
+            );
+        }
+        documents.push(DocumentBytes {
+            name,
+            data: data.into(),
+            content_type: content_type.to_string(),
+        });
+    }
+    Ok(documents)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use super::*;
+
+    fn archive(test: &str, entries: &[(&str, &[u8])]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("rusty-di-{}-{}.zip", test, std::process::id()));
+        let mut writer = ZipWriter::new(File::create(&path).unwrap());
+        for (name, data) in entries {
+            writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap();
+        path
+    }
+
+    #[test]
+    fn reads_supported_entries_and_skips_the_rest() {
+        let path = archive("skips", &[("a.pdf", b"%PDF"), ("notes.txt", b"skip"), ("scans/b.png", b"png")]);
+        let documents = read_zip_documents(&path).unwrap();
+        let names: Vec<&str> = documents.iter().map(|document| document.name.as_str()).collect();
+        assert_eq!(names, ["a.pdf", "scans/b.png"]);
+        assert_eq!(&documents[0].data[..], b"%PDF");
+    }
+}
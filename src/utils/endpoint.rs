@@ -0,0 +1,140 @@
+/// API path prefixes that users sometimes paste along with the endpoint.
+const API_PATH_PREFIXES: [&str; 2] = ["/documentintelligence", "/formrecognizer"];
+
+/// Canonicalizes a user-supplied endpoint into `scheme://host[/prefix]`.
+///
+/// Accepts full URLs, hosts without a scheme (`https://` is assumed) and, when
+/// `expand_resource_name` is set, bare resource names which get `default_domain`
+/// appended. Trailing slashes, query strings and any included API path are
+/// stripped.
+pub fn normalize_endpoint(
+    raw: &str,
+    expand_resource_name: bool,
+    default_domain: Option<&str>,
+) -> Result<String, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err("Endpoint must not be empty".to_string());
+    }
+
+    let (scheme, rest) = match trimmed.split_once("://") {
+        Some((scheme, rest)) => (scheme.to_ascii_lowercase(), rest),
+        None => ("https".to_string(), trimmed),
+    };
+    if scheme != "http" && scheme != "https" {
+        return Err(format!(
+            "Invalid endpoint '{}': scheme must be http or https",
+            raw
+        ));
+    }
+
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (host, mut path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, ""),
+    };
+    if host.is_empty() {
+        return Err(format!("Invalid endpoint '{}': missing host", raw));
+    }
+
+    if let Some(index) = API_PATH_PREFIXES
+        .iter()
+        .filter_map(|prefix| path.find(prefix))
+        .min()
+    {
+        path = &path[..index];
+    }
+    let path = path.trim_end_matches('/');
+
+    let mut host = host.to_ascii_lowercase();
+    let is_bare_name = !host.contains(['.', ':']) && host != "localhost";
+    if is_bare_name {
+        let Some(domain) = default_domain else {
+            return Err(format!(
+                "Endpoint '{}' looks like a bare resource name, but the cloud has no \
+                 default domain; pass the full endpoint",
+                raw
+            ));
+        };
+        if !expand_resource_name {
+            return Err(format!(
+                "Endpoint '{}' looks like a bare resource name; pass the full endpoint \
+                 (e.g. https://{}.{}) or set expand_resource_name=True",
+                raw, host, domain
+            ));
+        }
+        host = format!("{}.{}", host, domain);
+    }
+
+    Ok(format!("{}://{}{}", scheme, host, path))
+}
+
+/// Whether the endpoint's host ends with one of `suffixes`.
+pub fn has_domain_suffix(endpoint: &str, suffixes: &[&str]) -> bool {
+    let host = endpoint
+        .split_once("://")
+        .map_or(endpoint, |(_, rest)| rest)
+        .split(['/', ':'])
+        .next()
+        .unwrap_or_default();
+    suffixes
+        .iter()
+        .any(|suffix| host == *suffix || host.ends_with(&format!(".{}", suffix)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOMAIN: &str = "cognitiveservices.azure.com";
+
+    #[test]
+    fn endpoints_normalize() {
+        // (raw, expand_resource_name, default_domain, expected)
+        let cases = [
+            ("myresource", true, Some(DOMAIN), Ok("https://myresource.cognitiveservices.azure.com")),
+            ("myresource", false, Some(DOMAIN), Err("looks like a bare resource name; pass the full endpoint")),
+            ("myresource", true, None, Err("the cloud has no default domain")),
+            ("myresource.cognitiveservices.azure.com", false, Some(DOMAIN), Ok("https://myresource.cognitiveservices.azure.com")),
+            ("HTTPS://MyResource.CognitiveServices.Azure.com", false, Some(DOMAIN), Ok("https://myresource.cognitiveservices.azure.com")),
+            ("https://a.example.com/", false, None, Ok("https://a.example.com")),
+            ("  https://a.example.com//  ", false, None, Ok("https://a.example.com")),
+            ("https://a.example.com/documentintelligence/documentModels/prebuilt-layout:analyze", false, None, Ok("https://a.example.com")),
+            ("https://a.example.com/formrecognizer/documentModels/prebuilt-read:analyze", false, None, Ok("https://a.example.com")),
+            ("https://a.example.com/proxy/documentintelligence/info", false, None, Ok("https://a.example.com/proxy")),
+            ("https://a.example.com/?api-version=2024-11-30#top", false, None, Ok("https://a.example.com")),
+            ("http://localhost:5000", false, None, Ok("http://localhost:5000")),
+            ("localhost", true, None, Ok("https://localhost")),
+            ("", true, Some(DOMAIN), Err("must not be empty")),
+            ("   ", true, Some(DOMAIN), Err("must not be empty")),
+            ("ftp://a.example.com", false, None, Err("scheme must be http or https")),
+            ("https:///documentintelligence", false, None, Err("missing host")),
+        ];
+        for (raw, expand, domain, expected) in cases {
+            let actual = normalize_endpoint(raw, expand, domain);
+            match (expected, &actual) {
+                (Ok(expected), Ok(actual)) => assert_eq!(actual, expected, "{:?}", raw),
+                (Err(expected), Err(actual)) => assert!(actual.contains(expected), "{:?}: {}", raw, actual),
+                _ => panic!("{:?}: expected {:?}, got {:?}", raw, expected, actual),
+            }
+        }
+    }
+
+    #[test]
+    fn domain_suffixes_match_whole_labels() {
+        let suffixes = ["cognitiveservices.azure.com", "cognitiveservices.azure.us"];
+        let cases = [
+            ("https://a.cognitiveservices.azure.com", true),
+            ("https://a.cognitiveservices.azure.us/proxy", true),
+            ("https://a.cognitiveservices.azure.com:443", true),
+            ("a.cognitiveservices.azure.com", true),
+            ("https://cognitiveservices.azure.com", true),
+            ("https://evilcognitiveservices.azure.com", false),
+            ("https://a.cognitiveservices.azure.com.example.org", false),
+            ("http://localhost:5000", false),
+        ];
+        for (endpoint, expected) in cases {
+            assert_eq!(has_domain_suffix(endpoint, &suffixes), expected, "{}", endpoint);
+        }
+    }
+}
@@ -0,0 +1,29 @@
+/// Centroid of a flat `[x0, y0, x1, y1, ...]` polygon, or `None` if it has no points.
+pub fn polygon_centroid(polygon: &[f32]) -> Option<(f32, f32)> {
+    let points = polygon.len() / 2;
+    if points == 0 {
+        return None;
+    }
+    let (sum_x, sum_y) = polygon
+        .chunks_exact(2)
+        .fold((0.0, 0.0), |(x, y), point| (x + point[0], y + point[1]));
+    Some((sum_x / points as f32, sum_y / points as f32))
+}
+
+/// Axis-aligned bounding box `(x_min, y_min, x_max, y_max)` of a flat polygon,
+/// or `None` if it has no points.
+pub fn polygon_bounds(polygon: &[f32]) -> Option<(f32, f32, f32, f32)> {
+    let mut points = polygon.chunks_exact(2);
+    let first = points.next()?;
+    Some(points.fold(
+        (first[0], first[1], first[0], first[1]),
+        |(x_min, y_min, x_max, y_max), point| {
+            (
+                x_min.min(point[0]),
+                y_min.min(point[1]),
+                x_max.max(point[0]),
+                y_max.max(point[1]),
+            )
+        },
+    ))
+}
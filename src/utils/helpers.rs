@@ -1,13 +1,118 @@
+use std::borrow::Cow;
+use std::hash::{BuildHasher, RandomState};
 use std::path::Path;
+use std::time::Duration;
 
-pub fn get_content_type(file_path: &str) -> &'static str {
-    let path = Path::new(file_path);
-    match path.extension().and_then(|s| s.to_str()) {
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+/// Content type returned for extensions the service does not accept.
+pub const UNSUPPORTED_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Content types the service accepts, as produced by [`get_content_type`].
+pub const SUPPORTED_CONTENT_TYPES: [&str; 5] =
+    ["application/pdf", "image/jpeg", "image/png", "image/tiff", "image/bmp"];
+
+pub fn get_content_type(file_path: impl AsRef<Path>) -> &'static str {
+    let path = file_path.as_ref();
+    let extension = path.extension().and_then(|s| s.to_str()).map(str::to_ascii_lowercase);
+    match extension.as_deref() {
         Some("pdf") => "application/pdf",
         Some("jpg") | Some("jpeg") => "image/jpeg",
         Some("png") => "image/png",
         Some("tiff") | Some("tif") => "image/tiff",
         Some("bmp") => "image/bmp",
-        _ => "application/octet-stream",
+        _ => UNSUPPORTED_CONTENT_TYPE,
+    }
+}
+
+/// Detects the content type of a document from its leading magic bytes,
+/// recognizing the same formats as [`get_content_type`].
+pub fn sniff_content_type(data: &[u8]) -> &'static str {
+    match data {
+        [b'%', b'P', b'D', b'F', ..] => "application/pdf",
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => "image/png",
+        [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => "image/tiff",
+        [b'I', b'I', 0x2B, 0x00, ..] | [b'M', b'M', 0x00, 0x2B, ..] => "image/tiff",
+        [b'B', b'M', ..] => "image/bmp",
+        _ => UNSUPPORTED_CONTENT_TYPE,
+    }
+}
+
+/// A uniformly distributed value in [0, 1), for jitter. Every `RandomState` is
+/// keyed differently, which is random enough without an RNG dependency.
+pub fn random_unit() -> f64 {
+    let bits = RandomState::new().hash_one(0u8);
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Reads a `Retry-After` header expressed in (possibly fractional) seconds.
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?;
+    let seconds = value.trim().parse::<f64>().ok()?;
+    (seconds.is_finite() && seconds >= 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+/// Prefixes paths too long for `MAX_PATH` with `\\?\` so Windows can open them.
+/// Short and already-verbatim paths, and every path on other platforms, are
+/// returned unchanged.
+#[cfg(windows)]
+pub fn long_path(path: &Path) -> Cow<'_, Path> {
+    use std::ffi::OsString;
+    use std::path::{Component, PathBuf};
+
+    const MAX_PATH: usize = 260;
+    let verbatim = matches!(path.components().next(), Some(Component::Prefix(prefix)) if prefix.kind().is_verbatim());
+    if verbatim {
+        return Cow::Borrowed(path);
+    }
+    // Verbatim paths skip normalization, so resolve `.`/`..` and relative parts
+    // first. The limit applies to the resolved path.
+    let absolute = match std::path::absolute(path) {
+        Ok(absolute) if absolute.as_os_str().len() >= MAX_PATH => absolute,
+        _ => return Cow::Borrowed(path),
+    };
+    let mut prefixed = OsString::from(r"\\?\");
+    match absolute.to_str().and_then(|s| s.strip_prefix(r"\\")) {
+        Some(share) => {
+            prefixed.push(r"UNC\");
+            prefixed.push(share);
+        }
+        None => prefixed.push(&absolute),
+    }
+    Cow::Owned(PathBuf::from(prefixed))
+}
+
+#[cfg(not(windows))]
+pub fn long_path(path: &Path) -> Cow<'_, Path> {
+    Cow::Borrowed(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(windows)]
+    #[test]
+    fn long_windows_paths_get_a_verbatim_prefix() {
+        let dir = "a".repeat(300);
+
+        let local = format!(r"C:\{}\scan.pdf", dir);
+        assert_eq!(&*long_path(Path::new(&local)), Path::new(&format!(r"\\?\{}", local)));
+
+        let unc = format!(r"\\server\share\{}\scan.pdf", dir);
+        let expected = format!(r"\\?\UNC\server\share\{}\scan.pdf", dir);
+        assert_eq!(&*long_path(Path::new(&unc)), Path::new(&expected));
+
+        // Already verbatim and short paths are left alone.
+        assert!(matches!(long_path(Path::new(&expected)), Cow::Borrowed(_)));
+        assert!(matches!(long_path(Path::new(r"C:\scan.pdf")), Cow::Borrowed(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn paths_are_unchanged_off_windows() {
+        let long = format!("/{}/scan.pdf", "a".repeat(300));
+        assert!(matches!(long_path(Path::new(&long)), Cow::Borrowed(path) if path == Path::new(&long)));
     }
 }
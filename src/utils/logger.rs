@@ -2,6 +2,12 @@ use once_cell::sync::OnceCell;
 use tracing_subscriber::fmt;
 
 static TRACING_INIT: OnceCell<()> = OnceCell::new();
+
+/// Whether `init_tracing` has installed the global subscriber in this process.
+pub fn tracing_initialized() -> bool {
+    TRACING_INIT.get().is_some()
+}
+
 pub fn init_tracing() {
     TRACING_INIT.get_or_init(|| {
         // Set up the formatting (e.g., colorful output)
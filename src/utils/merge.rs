@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+/// Merges the raw analyze results of consecutive page ranges of one document.
+///
+/// Parts must be in page order. Contents are joined with a newline, every `span`
+/// and `spans` offset is shifted by the length (in characters) of the content
+/// before it, `/collection/index` element references (as used by sections and
+/// figures) are shifted past the items already merged, and all other top-level
+/// arrays are concatenated. Page numbers are kept as returned, since the service
+/// numbers pages relative to the whole document even when `pages` is set.
+pub fn merge_analyze_results(parts: Vec<Value>) -> Value {
+    let mut parts = parts.into_iter();
+    let Some(mut merged) = parts.next() else {
+        return Value::Object(Map::new());
+    };
+
+    for mut part in parts {
+        let Some(merged_obj) = merged.as_object_mut() else {
+            break;
+        };
+        let merged_content = merged_obj
+            .get("content")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let separator = if merged_content.is_empty() { "" } else { "\n" };
+        let shift = (merged_content.chars().count() + separator.len()) as u64;
+        let counts: HashMap<String, usize> = merged_obj
+            .iter()
+            .filter_map(|(key, value)| Some((key.clone(), value.as_array()?.len())))
+            .collect();
+
+        shift_offsets(&mut part, shift);
+        shift_element_refs(&mut part, &counts);
+
+        let Value::Object(part_obj) = part else {
+            continue;
+        };
+        for (key, value) in part_obj {
+            match (merged_obj.get_mut(&key), value) {
+                (Some(Value::String(content)), Value::String(more)) if key == "content" => {
+                    content.push_str(separator);
+                    content.push_str(&more);
+                }
+                (Some(Value::Array(existing)), Value::Array(items)) => existing.extend(items),
+                (None, value) => {
+                    merged_obj.insert(key, value);
+                }
+                _ => {}
+            }
+        }
+    }
+    merged
+}
+
+fn shift_offsets(value: &mut Value, shift: u64) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match (key.as_str(), child) {
+                    ("span", Value::Object(span)) => shift_span(span, shift),
+                    ("spans", Value::Array(spans)) => spans
+                        .iter_mut()
+                        .filter_map(Value::as_object_mut)
+                        .for_each(|span| shift_span(span, shift)),
+                    (_, child) => shift_offsets(child, shift),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| shift_offsets(item, shift)),
+        _ => {}
+    }
+}
+
+fn shift_span(span: &mut Map<String, Value>, shift: u64) {
+    if let Some(offset) = span.get("offset").and_then(Value::as_u64) {
+        span.insert("offset".to_string(), Value::from(offset + shift));
+    }
+}
+
+fn shift_element_refs(value: &mut Value, counts: &HashMap<String, usize>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match (key.as_str(), child) {
+                    ("elements", Value::Array(elements)) => {
+                        for element in elements.iter_mut() {
+                            if let Some(shifted) = element.as_str().and_then(|r| shift_ref(r, counts)) {
+                                *element = Value::String(shifted);
+                            }
+                        }
+                    }
+                    (_, child) => shift_element_refs(child, counts),
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| shift_element_refs(item, counts)),
+        _ => {}
+    }
+}
+
+/// Rewrites a reference like `/paragraphs/3` to point past the items merged so far.
+fn shift_ref(reference: &str, counts: &HashMap<String, usize>) -> Option<String> {
+    let (collection, index) = reference.strip_prefix('/')?.split_once('/')?;
+    let index: usize = index.parse().ok()?;
+    let shift = counts.get(collection).copied().unwrap_or(0);
+    Some(format!("/{}/{}", collection, index + shift))
+}
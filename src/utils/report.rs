@@ -0,0 +1,359 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use reqwest::Url;
+
+/// Longest failure message quoted in a report, in characters.
+const MAX_MESSAGE_CHARS: usize = 200;
+/// Documents listed under "Slowest documents".
+const SLOWEST_DOCUMENTS: usize = 10;
+
+/// Output format of `generate_report`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl FromStr for ReportFormat {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "html" => Ok(ReportFormat::Html),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid report format: '{}'. Expected 'markdown' or 'html'.",
+                s
+            ))),
+        }
+    }
+}
+
+/// One failed document, as listed in the report.
+pub struct ReportFailure {
+    pub source: Option<String>,
+    /// `AnalysisError.kind` when set, otherwise the exception's type name.
+    pub category: String,
+    pub message: String,
+}
+
+/// One document the batch started, with what it measured about it.
+pub struct ReportTiming {
+    pub source: Option<String>,
+    pub elapsed_secs: f64,
+    /// 429 responses the document was retried on.
+    pub throttled: u32,
+    /// Endpoint of the credential that produced the outcome.
+    pub endpoint: Option<String>,
+}
+
+/// Everything an end-of-batch report is rendered from.
+pub struct BatchReport {
+    pub documents: usize,
+    pub succeeded: usize,
+    /// Pages in the successful results that were loaded (checkpointed
+    /// placeholders carry no pages).
+    pub pages: usize,
+    pub failures: Vec<ReportFailure>,
+    /// Every document the batch started, successes and failures alike.
+    pub timings: Vec<ReportTiming>,
+    /// Strip query strings (e.g. SAS tokens) from URLs in the report.
+    pub redact_urls: bool,
+}
+
+/// A titled table of the report.
+struct Section {
+    title: &'static str,
+    headers: &'static [&'static str],
+    rows: Vec<Vec<String>>,
+}
+
+impl BatchReport {
+    pub fn render(&self, format: ReportFormat) -> String {
+        let summary = self.summary();
+        let sections: Vec<Section> = [self.failure_categories(), self.failed_documents(), self.slowest(), self.per_credential()]
+            .into_iter()
+            .filter(|section| !section.rows.is_empty())
+            .collect();
+        match format {
+            ReportFormat::Markdown => to_markdown(&summary, &sections),
+            ReportFormat::Html => to_html(&summary, &sections),
+        }
+    }
+
+    fn success_rate(&self) -> f64 {
+        if self.documents == 0 {
+            return 0.0;
+        }
+        100.0 * self.succeeded as f64 / self.documents as f64
+    }
+
+    /// A URL or path as shown in the report.
+    fn location(&self, source: &str) -> String {
+        if self.redact_urls {
+            redact_url(source)
+        } else {
+            source.to_string()
+        }
+    }
+
+    fn display_source(&self, source: Option<&str>) -> String {
+        source.map_or_else(|| "-".to_string(), |source| self.location(source))
+    }
+
+    /// First line of `message`, cut to `MAX_MESSAGE_CHARS`, with URLs redacted
+    /// if the report redacts them.
+    fn short_message(&self, message: &str) -> String {
+        let line = message.lines().next().unwrap_or_default();
+        let redacted: Vec<String> = line
+            .split(' ')
+            .map(|word| {
+                let url = word.trim_matches(|c| matches!(c, '(' | ')' | '"' | '\'' | ','));
+                if url.is_empty() {
+                    word.to_string()
+                } else {
+                    word.replace(url, &self.location(url))
+                }
+            })
+            .collect();
+        let redacted = redacted.join(" ");
+        if redacted.chars().count() <= MAX_MESSAGE_CHARS {
+            return redacted;
+        }
+        let mut short: String = redacted.chars().take(MAX_MESSAGE_CHARS).collect();
+        short.push('…');
+        short
+    }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        let throttled: u32 = self.timings.iter().map(|timing| timing.throttled).sum();
+        let throttled_documents = self.timings.iter().filter(|timing| timing.throttled > 0).count();
+        vec![
+            ("Documents", self.documents.to_string()),
+            ("Succeeded", self.succeeded.to_string()),
+            ("Failed", self.failures.len().to_string()),
+            ("Success rate", format!("{:.1}%", self.success_rate())),
+            ("Pages analyzed", self.pages.to_string()),
+            ("Throttling events", format!("{} ({} documents)", throttled, throttled_documents)),
+            ("URL redaction", if self.redact_urls { "on" } else { "off" }.to_string()),
+        ]
+    }
+
+    /// Failure counts per category, most frequent first.
+    fn failure_categories(&self) -> Section {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for failure in &self.failures {
+            *counts.entry(failure.category.as_str()).or_default() += 1;
+        }
+        let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+        counts.sort_by_key(|&(_, count)| Reverse(count));
+        Section {
+            title: "Failures by category",
+            headers: &["Category", "Count"],
+            rows: counts.into_iter().map(|(category, count)| vec![category.to_string(), count.to_string()]).collect(),
+        }
+    }
+
+    fn failed_documents(&self) -> Section {
+        Section {
+            title: "Failed documents",
+            headers: &["Source", "Category", "Message"],
+            rows: self
+                .failures
+                .iter()
+                .map(|failure| {
+                    vec![
+                        self.display_source(failure.source.as_deref()),
+                        failure.category.clone(),
+                        self.short_message(&failure.message),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// The `SLOWEST_DOCUMENTS` documents that took longest, slowest first.
+    fn slowest(&self) -> Section {
+        let mut timings: Vec<&ReportTiming> = self.timings.iter().collect();
+        timings.sort_by(|a, b| b.elapsed_secs.total_cmp(&a.elapsed_secs));
+        Section {
+            title: "Slowest documents",
+            headers: &["Source", "Time (s)", "Throttled"],
+            rows: timings
+                .into_iter()
+                .take(SLOWEST_DOCUMENTS)
+                .map(|timing| {
+                    vec![
+                        self.display_source(timing.source.as_deref()),
+                        format!("{:.2}", timing.elapsed_secs),
+                        timing.throttled.to_string(),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// How this batch's documents were spread over the credentials.
+    fn per_credential(&self) -> Section {
+        let mut endpoints: BTreeMap<&str, (usize, u32, f64)> = BTreeMap::new();
+        for timing in &self.timings {
+            if let Some(endpoint) = &timing.endpoint {
+                let (documents, throttled, secs) = endpoints.entry(endpoint).or_default();
+                *documents += 1;
+                *throttled += timing.throttled;
+                *secs += timing.elapsed_secs;
+            }
+        }
+        Section {
+            title: "Per-credential distribution",
+            headers: &["Endpoint", "Documents", "Throttled", "Mean time (s)"],
+            rows: endpoints
+                .into_iter()
+                .map(|(endpoint, (documents, throttled, secs))| {
+                    vec![
+                        self.location(endpoint),
+                        documents.to_string(),
+                        throttled.to_string(),
+                        format!("{:.2}", secs / documents as f64),
+                    ]
+                })
+                .collect(),
+        }
+    }
+}
+
+fn to_markdown(summary: &[(&str, String)], sections: &[Section]) -> String {
+    let mut out = String::from("# Batch report\n\n| Metric | Value |\n| --- | --- |\n");
+    for (metric, value) in summary {
+        let _ = writeln!(out, "| {} | {} |", metric, markdown_cell(value));
+    }
+    for section in sections {
+        let _ = writeln!(out, "\n## {}\n", section.title);
+        let _ = writeln!(out, "| {} |", section.headers.join(" | "));
+        let _ = writeln!(out, "|{}", " --- |".repeat(section.headers.len()));
+        for row in &section.rows {
+            let cells: Vec<String> = row.iter().map(|cell| markdown_cell(cell)).collect();
+            let _ = writeln!(out, "| {} |", cells.join(" | "));
+        }
+    }
+    out
+}
+
+fn to_html(summary: &[(&str, String)], sections: &[Section]) -> String {
+    let mut out = String::from("<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Batch report</title></head>\n<body>\n<h1>Batch report</h1>\n<table>\n");
+    for (metric, value) in summary {
+        let _ = writeln!(out, "<tr><th>{}</th><td>{}</td></tr>", metric, escape_html(value));
+    }
+    out.push_str("</table>\n");
+    for section in sections {
+        let _ = writeln!(out, "<h2>{}</h2>\n<table>", section.title);
+        let headers: String = section.headers.iter().map(|header| format!("<th>{}</th>", header)).collect();
+        let _ = writeln!(out, "<tr>{}</tr>", headers);
+        for row in &section.rows {
+            let cells: String = row.iter().map(|cell| format!("<td>{}</td>", escape_html(cell))).collect();
+            let _ = writeln!(out, "<tr>{}</tr>", cells);
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Drops the query string and fragment of `http(s)` URLs, which commonly carry
+/// SAS tokens or other secrets. Anything else (file paths, names) is unchanged.
+pub fn redact_url(source: &str) -> String {
+    match Url::parse(source) {
+        Ok(mut url) if matches!(url.scheme(), "http" | "https") => {
+            let had_secrets = url.query().is_some() || url.fragment().is_some();
+            url.set_query(None);
+            url.set_fragment(None);
+            let mut redacted = url.to_string();
+            if had_secrets {
+                redacted.push_str("?<redacted>");
+            }
+            redacted
+        }
+        _ => source.to_string(),
+    }
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A batch of five documents over two endpoints: two successes (one of them
+    /// throttled), a rate-limited failure, a timeout with a SAS URL in its
+    /// message, and a document cancelled before it was started.
+    fn fixture(redact_urls: bool) -> BatchReport {
+        let (east, west) = ("https://east.example.com", "https://west.example.com");
+        let timing = |source: &str, elapsed_secs, throttled, endpoint: &str| ReportTiming {
+            source: Some(source.to_string()),
+            elapsed_secs,
+            throttled,
+            endpoint: Some(endpoint.to_string()),
+        };
+        BatchReport {
+            documents: 5,
+            succeeded: 2,
+            pages: 7,
+            failures: vec![
+                ReportFailure {
+                    source: Some("https://blob.example.com/c.pdf?sig=secret".to_string()),
+                    category: "rate_limit".to_string(),
+                    message: "API Error: HTTP 429 Too Many Requests".to_string(),
+                },
+                ReportFailure {
+                    source: Some("/data/d|e.pdf".to_string()),
+                    category: "timeout".to_string(),
+                    message: "Document https://blob.example.com/d.pdf?sig=secret timed out after 30s".to_string(),
+                },
+                ReportFailure {
+                    source: None,
+                    category: "cancelled".to_string(),
+                    message: "Document was cancelled because the client was closed".to_string(),
+                },
+            ],
+            timings: vec![
+                timing("https://blob.example.com/a.pdf?sig=secret", 1.25, 0, east),
+                timing("/data/b.pdf", 4.5, 2, west),
+                timing("https://blob.example.com/c.pdf?sig=secret", 9.0, 3, east),
+                timing("/data/d|e.pdf", 30.0, 0, west),
+            ],
+            redact_urls,
+        }
+    }
+
+    #[test]
+    fn markdown_report_matches_snapshot() {
+        assert_eq!(fixture(true).render(ReportFormat::Markdown), include_str!("testdata/batch_report.md"));
+    }
+
+    #[test]
+    fn html_report_matches_snapshot() {
+        assert_eq!(fixture(true).render(ReportFormat::Html), include_str!("testdata/batch_report.html"));
+    }
+
+    #[test]
+    fn urls_are_kept_when_redaction_is_off() {
+        let report = fixture(false).render(ReportFormat::Markdown);
+        assert!(report.contains("| URL redaction | off |"));
+        assert!(report.contains("https://blob.example.com/c.pdf?sig=secret"));
+        assert!(!fixture(true).render(ReportFormat::Markdown).contains("sig=secret"));
+    }
+}
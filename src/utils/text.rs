@@ -0,0 +1,143 @@
+/// Returns the text covered by a span, with `offset` and `length` in character units.
+pub fn span_text(chars: &[char], offset: usize, length: usize) -> String {
+    let start = offset.min(chars.len());
+    let end = (offset + length).min(chars.len());
+    chars[start..end].iter().collect()
+}
+
+/// Rebuilds `chars[start..end]` with the given spans removed.
+///
+/// `spans` are `(offset, length)` pairs in character units and must be sorted by
+/// offset. Each removed span is replaced by `replacement`; when the replacement is
+/// empty the surrounding spaces are collapsed so neighbouring words neither merge
+/// nor end up separated by a double space.
+pub fn remove_spans(
+    chars: &[char],
+    start: usize,
+    end: usize,
+    spans: &[(usize, usize)],
+    replacement: &str,
+) -> String {
+    let end = end.min(chars.len());
+    let mut out = String::with_capacity(end.saturating_sub(start));
+    let mut cursor = start;
+
+    for &(offset, length) in spans {
+        if offset < cursor || offset >= end {
+            continue;
+        }
+        out.extend(&chars[cursor..offset]);
+        cursor = (offset + length).min(end);
+
+        if !replacement.is_empty() {
+            out.push_str(replacement);
+            continue;
+        }
+
+        let next_is_break = chars.get(cursor).is_none_or(|c| c.is_whitespace());
+        if next_is_break {
+            let trimmed = out.trim_end_matches([' ', '\t']).len();
+            out.truncate(trimmed);
+        }
+        if out.is_empty() || out.ends_with('\n') {
+            while cursor < end && matches!(chars[cursor], ' ' | '\t') {
+                cursor += 1;
+            }
+        }
+    }
+
+    if cursor < end {
+        out.extend(&chars[cursor..end]);
+    }
+    out
+}
+
+/// Common English words that carry little meaning on their own, lowercase.
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "am", "an", "and", "any", "are",
+    "as", "at", "be", "because", "been", "before", "being", "below", "between", "both", "but",
+    "by", "can", "could", "did", "do", "does", "doing", "down", "during", "each", "few", "for",
+    "from", "further", "had", "has", "have", "having", "he", "her", "here", "hers", "herself",
+    "him", "himself", "his", "how", "i", "if", "in", "into", "is", "it", "its", "itself", "just",
+    "may", "me", "might", "more", "most", "must", "my", "myself", "no", "nor", "not", "now", "of",
+    "off", "on", "once", "only", "or", "other", "our", "ours", "ourselves", "out", "over", "own",
+    "same", "shall", "she", "should", "so", "some", "such", "than", "that", "the", "their",
+    "theirs", "them", "themselves", "then", "there", "these", "they", "this", "those", "through",
+    "to", "too", "under", "until", "up", "upon", "us", "very", "was", "we", "were", "what",
+    "when", "where", "which", "while", "who", "whom", "why", "will", "with", "would", "you",
+    "your", "yours", "yourself", "yourselves",
+];
+
+/// Whether `word` (already lowercased) is a common English stop word.
+pub fn is_stop_word(word: &str) -> bool {
+    STOP_WORDS.contains(&word)
+}
+
+/// Whether `c` is punctuation, covering ASCII punctuation plus the common
+/// Unicode quotes, dashes, ellipsis and CJK marks found in OCR output.
+pub fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation()
+        || matches!(
+            c,
+            '\u{00A1}'
+                | '\u{00A7}'
+                | '\u{00AB}'
+                | '\u{00B6}'
+                | '\u{00B7}'
+                | '\u{00BB}'
+                | '\u{00BF}'
+                | '\u{2010}'..='\u{2027}'
+                | '\u{2030}'..='\u{205E}'
+                | '\u{3001}'..='\u{3003}'
+                | '\u{3008}'..='\u{3011}'
+        )
+}
+
+/// Maps character offsets in a text to where they end up after some of its spans
+/// were replaced by a fixed string, as [`remove_spans`] does with a non-empty
+/// replacement.
+pub struct OffsetMap<'a> {
+    edits: &'a [(usize, usize)],
+    replacement_len: usize,
+}
+
+impl<'a> OffsetMap<'a> {
+    /// `edits` are the replaced `(offset, length)` pairs, sorted by offset and not
+    /// overlapping; `replacement_len` is in characters.
+    pub fn new(edits: &'a [(usize, usize)], replacement_len: usize) -> Self {
+        Self { edits, replacement_len }
+    }
+
+    /// New position of `position`. A position strictly inside a replaced span
+    /// maps to the start of its replacement, or to its end when `is_end`.
+    pub fn map(&self, position: usize, is_end: bool) -> usize {
+        let mut shifted = position as isize;
+        for &(offset, length) in self.edits {
+            if offset >= position {
+                break;
+            }
+            let delta = self.replacement_len as isize - length as isize;
+            if offset + length <= position {
+                shifted += delta;
+                continue;
+            }
+            let start = (shifted - position as isize + offset as isize) as usize;
+            return if is_end { start + self.replacement_len } else { start };
+        }
+        shifted as usize
+    }
+
+    /// The `(offset, length)` of a span after the replacement.
+    pub fn map_span(&self, offset: usize, length: usize) -> (usize, usize) {
+        let start = self.map(offset, false);
+        let end = self.map(offset + length, true);
+        (start, end.saturating_sub(start))
+    }
+
+    /// Whether any replaced span overlaps `[offset, offset + length)`.
+    pub fn touches(&self, offset: usize, length: usize) -> bool {
+        self.edits
+            .iter()
+            .any(|&(edit, edit_length)| edit < offset + length && offset < edit + edit_length)
+    }
+}
@@ -0,0 +1,258 @@
+//! Counts the pages of a TIFF by walking its chain of image file directories
+//! (IFDs), without decoding any image data.
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufReader, ErrorKind, Read, Seek, SeekFrom},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, bail};
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::utils::long_path;
+
+#[derive(Clone, Copy)]
+enum ByteOrder {
+    Little,
+    Big,
+}
+
+struct TiffReader<R> {
+    inner: R,
+    order: ByteOrder,
+    /// BigTIFF uses 8-byte offsets and counts instead of 4 and 2.
+    big: bool,
+}
+
+impl<R: Read + Seek> TiffReader<R> {
+    fn read_bytes<const N: usize>(&mut self) -> anyhow::Result<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.inner.read_exact(&mut buf).map_err(|e| match e.kind() {
+            ErrorKind::UnexpectedEof => anyhow!("file is truncated"),
+            _ => e.into(),
+        })?;
+        Ok(buf)
+    }
+
+    fn read_u16(&mut self) -> anyhow::Result<u16> {
+        let buf = self.read_bytes()?;
+        Ok(match self.order {
+            ByteOrder::Little => u16::from_le_bytes(buf),
+            ByteOrder::Big => u16::from_be_bytes(buf),
+        })
+    }
+
+    fn read_u32(&mut self) -> anyhow::Result<u32> {
+        let buf = self.read_bytes()?;
+        Ok(match self.order {
+            ByteOrder::Little => u32::from_le_bytes(buf),
+            ByteOrder::Big => u32::from_be_bytes(buf),
+        })
+    }
+
+    fn read_u64(&mut self) -> anyhow::Result<u64> {
+        let buf = self.read_bytes()?;
+        Ok(match self.order {
+            ByteOrder::Little => u64::from_le_bytes(buf),
+            ByteOrder::Big => u64::from_be_bytes(buf),
+        })
+    }
+
+    /// Reads an offset: 4 bytes in classic TIFF, 8 in BigTIFF.
+    fn read_offset(&mut self) -> anyhow::Result<u64> {
+        if self.big {
+            self.read_u64()
+        } else {
+            self.read_u32().map(u64::from)
+        }
+    }
+
+    /// Reads an IFD entry count: 2 bytes in classic TIFF, 8 in BigTIFF.
+    fn read_entry_count(&mut self) -> anyhow::Result<u64> {
+        if self.big {
+            self.read_u64()
+        } else {
+            self.read_u16().map(u64::from)
+        }
+    }
+}
+
+/// Counts the pages (IFDs) of a classic or BigTIFF stream in either byte order.
+///
+/// Only the header and the IFD chain are read. Truncated files, offsets outside
+/// the file, empty IFDs and chains that loop back on themselves are errors.
+pub fn count_tiff_pages<R: Read + Seek>(mut reader: R) -> anyhow::Result<usize> {
+    let len = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    let mut mark = [0u8; 2];
+    reader.read_exact(&mut mark).map_err(|_| anyhow!("file is truncated"))?;
+    let order = match &mark {
+        b"II" => ByteOrder::Little,
+        b"MM" => ByteOrder::Big,
+        _ => bail!("not a TIFF file (bad byte-order mark)"),
+    };
+    let mut tiff = TiffReader { inner: reader, order, big: false };
+
+    let header_len = match tiff.read_u16()? {
+        42 => 8,
+        43 => {
+            if tiff.read_u16()? != 8 || tiff.read_u16()? != 0 {
+                bail!("invalid BigTIFF header");
+            }
+            tiff.big = true;
+            16
+        }
+        magic => bail!("not a TIFF file (magic number {})", magic),
+    };
+    let (count_len, entry_len, offset_len) = if tiff.big { (8, 20, 8) } else { (2, 12, 4) };
+
+    let mut offset = tiff.read_offset()?;
+    if offset == 0 {
+        bail!("TIFF has no pages");
+    }
+
+    let mut visited = HashSet::new();
+    let mut pages = 0;
+    while offset != 0 {
+        let page = pages + 1;
+        if offset < header_len || offset >= len {
+            bail!("IFD {} offset {} is outside the file", page, offset);
+        }
+        if !visited.insert(offset) {
+            bail!("IFD chain loops back to offset {}", offset);
+        }
+
+        tiff.inner.seek(SeekFrom::Start(offset))?;
+        let entries = tiff.read_entry_count()?;
+        if entries == 0 {
+            bail!("IFD {} has no entries", page);
+        }
+        let next_offset_at = entries
+            .checked_mul(entry_len)
+            .and_then(|size| size.checked_add(offset + count_len))
+            .filter(|at| at.saturating_add(offset_len) <= len)
+            .ok_or_else(|| anyhow!("IFD {} with {} entries runs past the end of the file", page, entries))?;
+
+        tiff.inner.seek(SeekFrom::Start(next_offset_at))?;
+        offset = tiff.read_offset()?;
+        pages = page;
+    }
+    Ok(pages)
+}
+
+/// Count the pages of a TIFF file without decoding it.
+///
+/// Walks the file's directory chain only, so it is cheap even for large files.
+/// Classic TIFF and BigTIFF are supported in both byte orders.
+///
+/// Args:
+///     path (str | os.PathLike): Path to a local .tif/.tiff file
+///
+/// Returns:
+///     int: Number of pages
+///
+/// Raises:
+///     IOError: If the file cannot be read
+///     ValueError: If the file is not a TIFF or its structure is invalid or
+///         truncated
+#[pyfunction]
+pub fn tiff_page_count(py: Python, path: PathBuf) -> PyResult<usize> {
+    let file = File::open(long_path(&path))
+        .map_err(|e| PyIOError::new_err(format!("Failed to open file {}: {}", path.display(), e)))?;
+    py.detach(|| count_tiff_pages(BufReader::new(file))).map_err(|e| {
+        match e.downcast_ref::<std::io::Error>() {
+            Some(_) => PyIOError::new_err(format!("Failed to read file {}: {}", path.display(), e)),
+            None => PyValueError::new_err(format!("Invalid TIFF {}: {}", path.display(), e)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Builds a TIFF with `pages` one-entry IFDs laid out back to back.
+    fn tiff(order: ByteOrder, big: bool, pages: usize) -> Vec<u8> {
+        let put = |out: &mut Vec<u8>, value: u64, width: usize| {
+            let bytes = value.to_le_bytes();
+            match order {
+                ByteOrder::Little => out.extend_from_slice(&bytes[..width]),
+                ByteOrder::Big => out.extend(bytes[..width].iter().rev()),
+            }
+        };
+        let (header_len, ifd_len, offset_width, count_width) = if big { (16, 36, 8, 8) } else { (8, 18, 4, 2) };
+
+        let mut out = match order {
+            ByteOrder::Little => b"II".to_vec(),
+            ByteOrder::Big => b"MM".to_vec(),
+        };
+        if big {
+            put(&mut out, 43, 2);
+            put(&mut out, 8, 2);
+            put(&mut out, 0, 2);
+        } else {
+            put(&mut out, 42, 2);
+        }
+        put(&mut out, header_len, offset_width);
+        for page in 1..=pages as u64 {
+            put(&mut out, 1, count_width);
+            // ImageWidth, SHORT, count 1, value 1; the value field fills the entry.
+            put(&mut out, 256, 2);
+            put(&mut out, 3, 2);
+            put(&mut out, 1, offset_width);
+            put(&mut out, 1, offset_width);
+            let next = if page == pages as u64 { 0 } else { header_len + page * ifd_len };
+            put(&mut out, next, offset_width);
+        }
+        out
+    }
+
+    fn count(data: &[u8]) -> anyhow::Result<usize> {
+        count_tiff_pages(Cursor::new(data))
+    }
+
+    #[test]
+    fn counts_classic_and_big_tiffs_in_both_byte_orders() {
+        for order in [ByteOrder::Little, ByteOrder::Big] {
+            for big in [false, true] {
+                assert_eq!(count(&tiff(order, big, 1)).unwrap(), 1);
+                assert_eq!(count(&tiff(order, big, 3)).unwrap(), 3);
+            }
+        }
+    }
+
+    #[test]
+    fn every_truncation_is_an_error() {
+        for order in [ByteOrder::Little, ByteOrder::Big] {
+            for big in [false, true] {
+                let data = tiff(order, big, 2);
+                for len in 0..data.len() {
+                    assert!(count(&data[..len]).is_err(), "{} of {} bytes was accepted", len, data.len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_loops_and_out_of_range_offsets() {
+        let mut looped = tiff(ByteOrder::Little, false, 2);
+        let last = looped.len() - 4;
+        looped[last..].copy_from_slice(&8u32.to_le_bytes());
+        assert!(count(&looped).unwrap_err().to_string().contains("loops back"));
+
+        let mut outside = tiff(ByteOrder::Big, true, 1);
+        outside[8..16].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert!(count(&outside).unwrap_err().to_string().contains("outside the file"));
+
+        let mut huge = tiff(ByteOrder::Little, true, 1);
+        huge[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(count(&huge).unwrap_err().to_string().contains("runs past the end"));
+
+        assert!(count(b"PK\x03\x04 not a tiff").is_err());
+    }
+}
@@ -0,0 +1,100 @@
+"""Regression tests for `to_dict` on words and lines.
+
+Results can only be built by the client, so a local HTTP server stands in for
+the Document Intelligence endpoint.
+"""
+
+import json
+import threading
+from http.server import BaseHTTPRequestHandler, HTTPServer
+
+import pytest
+
+from rusty_di_runner import Credentials, RustyAnalysisClient
+
+RESULT = {
+    "apiVersion": "2024-11-30",
+    "modelId": "prebuilt-layout",
+    "content": "Hello world",
+    "pages": [
+        {
+            "pageNumber": 1,
+            "angle": 0.0,
+            "width": 8.5,
+            "height": 11.0,
+            "unit": "inch",
+            "words": [
+                {
+                    "content": "Hello",
+                    "polygon": [1.0, 1.0, 2.0, 1.0, 2.0, 1.5, 1.0, 1.5],
+                    "confidence": 0.99,
+                    "span": {"offset": 0, "length": 5},
+                },
+            ],
+            "lines": [
+                {
+                    "content": "Hello world",
+                    "polygon": [1.0, 1.0, 4.0, 1.0, 4.0, 1.5, 1.0, 1.5],
+                    "spans": [{"offset": 0, "length": 11}],
+                },
+            ],
+            "spans": [{"offset": 0, "length": 11}],
+        }
+    ],
+}
+
+
+class _Handler(BaseHTTPRequestHandler):
+    def do_POST(self):
+        self.rfile.read(int(self.headers.get("content-length", 0)))
+        host, port = self.server.server_address
+        self.send_response(202)
+        self.send_header("operation-location", "http://{}:{}/operations/1".format(host, port))
+        self.send_header("content-length", "0")
+        self.end_headers()
+
+    def do_GET(self):
+        body = json.dumps({"status": "succeeded", "analyzeResult": RESULT}).encode()
+        self.send_response(200)
+        self.send_header("content-type", "application/json")
+        self.send_header("content-length", str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+    def log_message(self, *args):
+        pass
+
+
+@pytest.fixture(scope="module")
+def page():
+    server = HTTPServer(("127.0.0.1", 0), _Handler)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    try:
+        host, port = server.server_address
+        client = RustyAnalysisClient(
+            [Credentials("http://{}:{}".format(host, port), api_key="test-key")],
+            enable_logs=False,
+            poll_interval=0.01,
+        )
+        [result] = client.process_batch_documents_from_urls(
+            "prebuilt-layout", ["https://example.com/a.pdf"]
+        )
+        client.close()
+        yield result.pages[0]
+    finally:
+        server.shutdown()
+
+
+def test_word_to_dict(page):
+    word = page.words[0].to_dict()
+    assert word["content"] == "Hello"
+    assert word["confidence"] == pytest.approx(0.99)
+    assert word["span"] == {"offset": 0, "length": 5}
+    assert len(word["polygon"]) == 8
+
+
+def test_line_to_dict(page):
+    line = page.lines[0].to_dict()
+    assert line["content"] == "Hello world"
+    assert line["spans"] == [{"offset": 0, "length": 11}]
+    assert len(line["polygon"]) == 8